@@ -0,0 +1,494 @@
+//! Textual assembly format for FuelVM instructions.
+//!
+//! Each instruction is written as its lowercase mnemonic followed by its operands, separated by
+//! whitespace or commas, e.g. `addi $r16 $zero 0x10`. Registers are written with a `$` prefix,
+//! either by their reserved name (`$zero`, `$one`, `$of`, `$pc`, `$ssp`, `$sp`, `$fp`, `$hp`, `$err`,
+//! `$ggas`, `$cgas`, `$bal`, `$is`, `$ret`, `$retl`, `$flag`) or by index (`$r16`). Immediates may be
+//! given in decimal, or in hexadecimal or binary with the `0x` and `0b` prefixes.
+//!
+//! With the `std` feature, [`assemble`] and [`disassemble`] translate whole programs, resolving
+//! labels used as jump targets of `ji`, `jnzi` and `jnei`.
+
+use core::{fmt, str::FromStr};
+
+use crate::{Instruction, Opcode, RegId};
+
+#[cfg(feature = "std")]
+use crate::RawInstruction;
+
+/// The names of the reserved registers, indexed by register ID.
+const REG_NAMES: [&str; RegId::WRITABLE.0 as usize] = [
+    "zero", "one", "of", "pc", "ssp", "sp", "fp", "hp", "err", "ggas", "cgas", "bal", "is", "ret", "retl", "flag",
+];
+
+/// Failed to parse an instruction from its textual assembly representation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The line contains no mnemonic.
+    MissingMnemonic,
+    /// The mnemonic doesn't correspond to any opcode.
+    UnknownMnemonic,
+    /// The operand is not a valid register.
+    InvalidRegister,
+    /// The operand is not a valid immediate value.
+    InvalidImmediate,
+    /// The immediate value doesn't fit the instruction's immediate width.
+    ImmediateOutOfRange,
+    /// The instruction has fewer operands than required.
+    MissingOperand,
+    /// The instruction has more operands than required.
+    UnexpectedOperand,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+impl fmt::Display for RegId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match REG_NAMES.get(self.0 as usize) {
+            Some(name) => write!(f, "${name}"),
+            None => write!(f, "$r{}", self.0),
+        }
+    }
+}
+
+impl FromStr for RegId {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.strip_prefix('$').ok_or(ParseError::InvalidRegister)?;
+
+        if let Some(id) = REG_NAMES.iter().position(|n| n.eq_ignore_ascii_case(name)) {
+            return Ok(Self(id as u8));
+        }
+
+        name.strip_prefix('r')
+            .filter(|id| !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|id| id.parse().ok())
+            .and_then(Self::new_checked)
+            .ok_or(ParseError::InvalidRegister)
+    }
+}
+
+impl Instruction {
+    /// The target instruction index of `ji`, `jnzi` and `jnei`, relative to `$is`.
+    ///
+    /// Returns `None` for every other instruction.
+    pub fn jump_target(&self) -> Option<u32> {
+        match self {
+            Instruction::JI(op) => Some(op.unpack().into()),
+            Instruction::JNZI(op) => Some(op.unpack().1.into()),
+            Instruction::JNEI(op) => Some(u16::from(op.unpack().2).into()),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = tokens(s);
+        let mnemonic = tokens.next().ok_or(ParseError::MissingMnemonic)?;
+        let opcode = Opcode::from_mnemonic(mnemonic).ok_or(ParseError::UnknownMnemonic)?;
+
+        Self::from_operands(opcode, tokens)
+    }
+}
+
+/// Split an instruction into its mnemonic and operands.
+fn tokens(s: &str) -> impl Iterator<Item = &str> {
+    s.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|t| !t.is_empty())
+}
+
+pub(crate) fn parse_reg(operand: Option<&str>) -> Result<RegId, ParseError> {
+    operand.ok_or(ParseError::MissingOperand)?.parse()
+}
+
+pub(crate) fn parse_imm(operand: Option<&str>) -> Result<u64, ParseError> {
+    let operand = operand.ok_or(ParseError::MissingOperand)?;
+
+    let (digits, radix) = if let Some(hex) = operand.strip_prefix("0x") {
+        (hex, 16)
+    } else if let Some(bin) = operand.strip_prefix("0b") {
+        (bin, 2)
+    } else {
+        (operand, 10)
+    };
+
+    // `from_str_radix` accepts a leading sign, which isn't valid for immediates.
+    if digits.starts_with(['+', '-']) {
+        return Err(ParseError::InvalidImmediate);
+    }
+
+    u64::from_str_radix(digits, radix).map_err(|e| match e.kind() {
+        core::num::IntErrorKind::PosOverflow => ParseError::ImmediateOutOfRange,
+        _ => ParseError::InvalidImmediate,
+    })
+}
+
+pub(crate) fn no_more_operands<'a>(mut operands: impl Iterator<Item = &'a str>) -> Result<(), ParseError> {
+    match operands.next() {
+        Some(_) => Err(ParseError::UnexpectedOperand),
+        None => Ok(()),
+    }
+}
+
+/// Failed to assemble a program.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AssemblyError {
+    /// The 1-based line number of the source the error occurred at.
+    pub line: usize,
+    /// The reason assembling the line failed.
+    pub kind: AssemblyErrorKind,
+}
+
+/// The reason an [`AssemblyError`] occurred.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum AssemblyErrorKind {
+    /// The instruction failed to parse.
+    Parse(ParseError),
+    /// The label name is not a valid identifier.
+    InvalidLabel(String),
+    /// The label was already defined earlier in the program.
+    DuplicateLabel(String),
+    /// The operand refers to a label that is never defined.
+    UndefinedLabel(String),
+    /// The label is defined at an offset that is not instruction aligned.
+    UnalignedLabel(String),
+    /// The directive is unknown or its argument is invalid.
+    InvalidDirective(String),
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for AssemblyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {:?}", self.line, self.kind)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AssemblyError {}
+
+/// A label must start with an ASCII letter or `_`, followed by ASCII alphanumerics, `_` or `.`.
+#[cfg(feature = "std")]
+fn is_label(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+/// Strip the comment from a line, and split off the label definitions preceding its contents.
+#[cfg(feature = "std")]
+fn split_labels(line: &str) -> (Vec<&str>, &str) {
+    let mut line = line.split([';', '#']).next().unwrap_or_default().trim();
+    let mut labels = vec![];
+
+    while let Some((label, rest)) = line.split_once(':') {
+        labels.push(label.trim());
+        line = rest.trim();
+    }
+
+    (labels, line)
+}
+
+/// Assemble a program from its textual representation into bytecode.
+///
+/// Every non-empty line holds an instruction or a directive, optionally preceded by `label:`
+/// definitions. Comments start with `;` or `#` and extend to the end of the line.
+///
+/// A label used as an operand is replaced by the index of the instruction following its
+/// definition, which is the target expected by `ji`, `jnzi` and `jnei`.
+///
+/// The supported directives are:
+/// - `.raw <u32>`, emitting the 4 big-endian bytes of the value as an instruction slot.
+/// - `.byte <u8>`, emitting a single byte.
+#[cfg(feature = "std")]
+pub fn assemble(source: &str) -> Result<Vec<u8>, AssemblyError> {
+    use std::collections::HashMap;
+
+    let mut labels = HashMap::new();
+    let mut offset = 0usize;
+
+    for (line_no, line) in source.lines().enumerate() {
+        let error = |kind| AssemblyError {
+            line: line_no + 1,
+            kind,
+        };
+        let (defined, contents) = split_labels(line);
+
+        for label in defined {
+            if !is_label(label) {
+                return Err(error(AssemblyErrorKind::InvalidLabel(label.into())));
+            }
+            if offset % Instruction::SIZE != 0 {
+                return Err(error(AssemblyErrorKind::UnalignedLabel(label.into())));
+            }
+            if labels.insert(label, offset / Instruction::SIZE).is_some() {
+                return Err(error(AssemblyErrorKind::DuplicateLabel(label.into())));
+            }
+        }
+
+        offset += match tokens(contents).next() {
+            Some(".byte") => 1,
+            Some(_) => Instruction::SIZE,
+            None => 0,
+        };
+    }
+
+    let mut bytecode = Vec::with_capacity(offset);
+
+    for (line_no, line) in source.lines().enumerate() {
+        let error = |kind| AssemblyError {
+            line: line_no + 1,
+            kind,
+        };
+        let (_, contents) = split_labels(line);
+        let mut tokens = tokens(contents);
+
+        let mnemonic = match tokens.next() {
+            Some(mnemonic) => mnemonic,
+            None => continue,
+        };
+
+        let operands = tokens
+            .map(|operand| match labels.get(operand) {
+                Some(index) => Ok(index.to_string()),
+                None if is_label(operand) => Err(error(AssemblyErrorKind::UndefinedLabel(operand.into()))),
+                None => Ok(operand.to_string()),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut operands = operands.iter().map(String::as_str);
+
+        match mnemonic {
+            ".raw" | ".byte" => {
+                let invalid = || error(AssemblyErrorKind::InvalidDirective(contents.into()));
+                let value = parse_imm(operands.next()).map_err(|_| invalid())?;
+                no_more_operands(operands).map_err(|_| invalid())?;
+
+                if mnemonic == ".raw" {
+                    let raw = RawInstruction::try_from(value).map_err(|_| invalid())?;
+                    bytecode.extend(raw.to_be_bytes());
+                } else {
+                    bytecode.push(u8::try_from(value).map_err(|_| invalid())?);
+                }
+            }
+            directive if directive.starts_with('.') => {
+                return Err(error(AssemblyErrorKind::InvalidDirective(contents.into())));
+            }
+            mnemonic => {
+                let opcode = Opcode::from_mnemonic(mnemonic)
+                    .ok_or_else(|| error(AssemblyErrorKind::Parse(ParseError::UnknownMnemonic)))?;
+                let instruction =
+                    Instruction::from_operands(opcode, operands).map_err(|e| error(AssemblyErrorKind::Parse(e)))?;
+                bytecode.extend(instruction.to_bytes());
+            }
+        }
+    }
+
+    Ok(bytecode)
+}
+
+/// Disassemble bytecode into its textual representation.
+///
+/// Targets of `ji`, `jnzi` and `jnei` that fall within the program are labeled `L<index>` and
+/// referred to by name. Words that don't decode to an instruction are emitted as `.raw`, and
+/// trailing bytes that don't fill an instruction as `.byte` directives, so that [`assemble`]
+/// reproduces the original bytecode.
+#[cfg(feature = "std")]
+pub fn disassemble(bytecode: &[u8]) -> String {
+    use std::{collections::BTreeSet, fmt::Write};
+
+    let chunks = bytecode.chunks_exact(Instruction::SIZE);
+    let trailing = chunks.remainder();
+    let decoded: Vec<_> = chunks
+        .map(|chunk| {
+            let raw = [chunk[0], chunk[1], chunk[2], chunk[3]];
+            (RawInstruction::from_be_bytes(raw), Instruction::try_from(raw).ok())
+        })
+        .collect();
+
+    let targets: BTreeSet<_> = decoded
+        .iter()
+        .filter_map(|(_, instruction)| instruction.as_ref().and_then(Instruction::jump_target))
+        .filter(|target| (*target as usize) < decoded.len())
+        .collect();
+
+    let mut out = String::new();
+
+    for (index, (raw, instruction)) in decoded.iter().enumerate() {
+        if targets.contains(&(index as u32)) {
+            let _ = writeln!(out, "L{index}:");
+        }
+
+        let _ = match instruction {
+            Some(instruction) => match Instruction::jump_target(instruction).filter(|t| targets.contains(t)) {
+                Some(target) => {
+                    // Replace the trailing immediate of the jump with its label.
+                    let text = instruction.to_string();
+                    let operands = text.rsplit_once(' ').map(|(head, _)| head).unwrap_or(&text);
+                    writeln!(out, "    {operands} L{target}")
+                }
+                None => writeln!(out, "    {instruction}"),
+            },
+            None => writeln!(out, "    .raw {raw:#010x}"),
+        };
+    }
+
+    for byte in trailing {
+        let _ = writeln!(out, "    .byte {byte:#04x}");
+    }
+
+    out
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::op;
+
+    #[test]
+    fn instruction_display_roundtrip() {
+        for byte in 0..=u8::MAX {
+            let Ok(opcode) = Opcode::try_from(byte) else {
+                continue;
+            };
+            let instruction = opcode.test_construct(RegId::new(0x11), RegId::new(0x2a), RegId::ONE, RegId::HP, 0xabc);
+            let text = instruction.to_string();
+            assert!(text.starts_with(opcode.mnemonic()));
+            assert_eq!(Ok(instruction), text.parse(), "{text}");
+        }
+    }
+
+    #[test]
+    fn instruction_display_format() {
+        assert_eq!(op::addi(0x10, RegId::ZERO, 0x10).to_string(), "addi $r16 $zero 0x10");
+        assert_eq!(op::move_(0x10, RegId::SP).to_string(), "move $r16 $sp");
+        assert_eq!(op::ret(RegId::ONE).to_string(), "ret $one");
+        assert_eq!(op::noop().to_string(), "noop");
+    }
+
+    #[test]
+    fn instruction_from_str_accepts_alternative_syntax() {
+        let expected = op::addi(0x10, RegId::ZERO, 0x10);
+        assert_eq!(Ok(expected), "addi $r16 $zero 0x10".parse());
+        assert_eq!(Ok(expected), "ADDI $r16, $r0, 16".parse());
+        assert_eq!(Ok(expected), "addi\t$r16,$ZERO,0b10000".parse());
+    }
+
+    #[test]
+    fn instruction_from_str_errors() {
+        assert_eq!(Err(ParseError::MissingMnemonic), " ".parse::<Instruction>());
+        assert_eq!(Err(ParseError::UnknownMnemonic), "nope $r1".parse::<Instruction>());
+        assert_eq!(Err(ParseError::InvalidRegister), "ret $r64".parse::<Instruction>());
+        assert_eq!(Err(ParseError::InvalidRegister), "ret r1".parse::<Instruction>());
+        assert_eq!(Err(ParseError::InvalidImmediate), "ji -1".parse::<Instruction>());
+        assert_eq!(
+            Err(ParseError::ImmediateOutOfRange),
+            "ji 0x1000000".parse::<Instruction>()
+        );
+        assert_eq!(Err(ParseError::MissingOperand), "move $r16".parse::<Instruction>());
+        assert_eq!(Err(ParseError::UnexpectedOperand), "noop $r16".parse::<Instruction>());
+    }
+
+    #[test]
+    fn assemble_resolves_labels() {
+        let source = "
+            ; count down from 5
+                movi $r16 5
+            loop:
+                subi $r16 $r16 1   # decrement
+                jnzi $r16 loop
+                jnei $r16 $zero end
+                ji loop
+            end: ret $one
+        ";
+        let expected: Vec<u8> = [
+            op::movi(0x10, 5),
+            op::subi(0x10, 0x10, 1),
+            op::jnzi(0x10, 1),
+            op::jnei(0x10, RegId::ZERO, 5),
+            op::ji(1),
+            op::ret(RegId::ONE),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(assemble(source), Ok(expected));
+    }
+
+    #[test]
+    fn assemble_errors() {
+        let err = |source| assemble(source).unwrap_err();
+
+        assert_eq!(
+            err("noop\nji missing"),
+            AssemblyError {
+                line: 2,
+                kind: AssemblyErrorKind::UndefinedLabel("missing".into())
+            }
+        );
+        assert_eq!(
+            err("a: noop\na: noop").kind,
+            AssemblyErrorKind::DuplicateLabel("a".into())
+        );
+        assert_eq!(err("1a: noop").kind, AssemblyErrorKind::InvalidLabel("1a".into()));
+        assert_eq!(
+            err(".byte 1\na: noop").kind,
+            AssemblyErrorKind::UnalignedLabel("a".into())
+        );
+        assert_eq!(
+            err(".word 1").kind,
+            AssemblyErrorKind::InvalidDirective(".word 1".into())
+        );
+        assert_eq!(
+            err(".raw 0x100000000").kind,
+            AssemblyErrorKind::InvalidDirective(".raw 0x100000000".into())
+        );
+        assert_eq!(
+            err("movi $r16").kind,
+            AssemblyErrorKind::Parse(ParseError::MissingOperand)
+        );
+    }
+
+    #[test]
+    fn disassemble_roundtrip() {
+        let mut bytecode: Vec<u8> = [
+            op::movi(0x10, 5),
+            op::subi(0x10, 0x10, 1),
+            op::jnzi(0x10, 1),
+            op::ji(0x100),
+            op::ret(RegId::ONE),
+        ]
+        .into_iter()
+        .collect();
+        bytecode.extend([0xff, 0, 0, 0, 0xaa, 0xbb]);
+
+        let text = disassemble(&bytecode);
+        assert_eq!(
+            text,
+            "    movi $r16 0x5
+L1:
+    subi $r16 $r16 0x1
+    jnzi $r16 L1
+    ji 0x100
+    ret $one
+    .raw 0xff000000
+    .byte 0xaa
+    .byte 0xbb
+"
+        );
+        assert_eq!(assemble(&text), Ok(bytecode));
+    }
+}
@@ -6,6 +6,7 @@
 #![warn(missing_docs)]
 
 mod args;
+mod fasm;
 mod instruction_result;
 // This is `pub` to make documentation for the private `impl_instructions!` macro more accessible.
 #[macro_use]
@@ -14,18 +15,26 @@ pub mod op;
 mod pack;
 mod panic_reason;
 mod unpack;
+#[cfg(feature = "std")]
+mod verify;
 
 #[cfg(all(test, feature = "std"))]
 mod encoding_tests;
 
 #[doc(no_inline)]
 pub use args::{GMArgs, GTFArgs};
+pub use fasm::ParseError;
+#[cfg(feature = "std")]
+pub use fasm::{assemble, disassemble, AssemblyError, AssemblyErrorKind};
 pub use fuel_types::{RegisterId, Word};
 pub use instruction_result::InstructionResult;
 pub use panic_reason::PanicReason;
+#[cfg(feature = "std")]
+pub use verify::{verify, IssueKind, ProgramKind, VerificationIssue, VerificationReport};
 
 /// Represents a 6-bit register ID, guaranteed to be masked by construction.
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RegId(u8);
 
 /// Represents a 12-bit immediate value, guaranteed to be masked by construction.
@@ -179,6 +188,10 @@ impl_instructions! {
     0x4B JNE jne [RegId RegId RegId]
     "Send a message to recipient address with call abi, coins, and output."
     0x4C SMO smo [RegId RegId RegId RegId]
+    "Mint coins of the asset ID derived from the current contract's ID and a sub ID."
+    0x4D MINTS mints [RegId RegId]
+    "Burn coins of the asset ID derived from the current contract's ID and a sub ID."
+    0x4E BURNS burns [RegId RegId]
 
     "Adds a register and an immediate value."
     0x50 ADDI addi [RegId RegId Imm12]
@@ -392,6 +405,34 @@ impl Opcode {
             _ => false,
         }
     }
+
+    /// Check if the opcode stores a value into its `$rA` register.
+    #[allow(clippy::match_like_matches_macro)]
+    pub fn writes_to_ra(&self) -> bool {
+        use Opcode::*;
+        match self {
+            ADD | ADDI | AND | ANDI | DIV | DIVI | EQ | EXP | EXPI | GT | LT | MLOG | MROO | MOD | MODI | MOVE
+            | MOVI | MUL | MULI | NOT | OR | ORI | SLL | SLLI | SRL | SRLI | SUB | SUBI | XOR | XORI | LB | LW
+            | MEQ | BAL | BHEI | CSIZ | SRW | GM | GTF | TIME => true,
+            JI | JNEI | JNZI | JMP | JNE | RET | RETD | CFEI | CFSI | ALOC | MCL | MCLI | MCP | MCPI | SB | SW
+            | BHSH | BURN | BURNS | CALL | CCP | CROO | CB | LDC | LOG | LOGD | MINT | MINTS | RVRT | SMO | SCWQ
+            | SRWQ | SWW | SWWQ | TR | TRO | ECR | K256 | S256 | NOOP | FLAG => false,
+        }
+    }
+
+    /// Check if the opcode stores a value into its `$rB` register.
+    #[allow(clippy::match_like_matches_macro)]
+    pub fn writes_to_rb(&self) -> bool {
+        use Opcode::*;
+        match self {
+            SCWQ | SRW | SRWQ | SWW | SWWQ => true,
+            ADD | ADDI | AND | ANDI | DIV | DIVI | EQ | EXP | EXPI | GT | LT | MLOG | MROO | MOD | MODI | MOVE
+            | MOVI | MUL | MULI | NOT | OR | ORI | SLL | SLLI | SRL | SRLI | SUB | SUBI | XOR | XORI | JI | JNEI
+            | JNZI | JMP | JNE | RET | RETD | CFEI | CFSI | LB | LW | ALOC | MCL | MCLI | MCP | MCPI | MEQ | SB
+            | SW | BAL | BHSH | BHEI | BURN | BURNS | CALL | CCP | CROO | CSIZ | CB | LDC | LOG | LOGD | MINT
+            | MINTS | RVRT | SMO | TR | TRO | ECR | K256 | S256 | NOOP | FLAG | GM | GTF | TIME => false,
+        }
+    }
 }
 
 // Direct conversions
@@ -638,8 +679,8 @@ fn check_predicate_allowed() {
     for byte in 0..u8::MAX {
         if let Ok(repr) = Opcode::try_from(byte) {
             let should_allow = match repr {
-                BAL | BHEI | BHSH | BURN | CALL | CB | CCP | CROO | CSIZ | LDC | LOG | LOGD | MINT | RETD | RVRT
-                | SMO | SCWQ | SRW | SRWQ | SWW | SWWQ | TIME | TR | TRO => false,
+                BAL | BHEI | BHSH | BURN | BURNS | CALL | CB | CCP | CROO | CSIZ | LDC | LOG | LOGD | MINT | MINTS
+                | RETD | RVRT | SMO | SCWQ | SRW | SRWQ | SWW | SWWQ | TIME | TR | TRO => false,
                 _ => true,
             };
             assert_eq!(should_allow, repr.is_predicate_allowed());
@@ -607,6 +607,124 @@ macro_rules! impl_instructions {
         }
     };
 
+    // Display implementations in the textual assembly format for each instruction.
+    (impl_op_display_fmt $Op:ident [RegId]) => {
+        fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let ra = self.unpack();
+            write!(f, "{} {}", Self::OPCODE.mnemonic(), ra)
+        }
+    };
+    (impl_op_display_fmt $Op:ident [RegId RegId]) => {
+        fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let (ra, rb) = self.unpack();
+            write!(f, "{} {} {}", Self::OPCODE.mnemonic(), ra, rb)
+        }
+    };
+    (impl_op_display_fmt $Op:ident [RegId RegId RegId]) => {
+        fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let (ra, rb, rc) = self.unpack();
+            write!(f, "{} {} {} {}", Self::OPCODE.mnemonic(), ra, rb, rc)
+        }
+    };
+    (impl_op_display_fmt $Op:ident [RegId RegId RegId RegId]) => {
+        fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let (ra, rb, rc, rd) = self.unpack();
+            write!(f, "{} {} {} {} {}", Self::OPCODE.mnemonic(), ra, rb, rc, rd)
+        }
+    };
+    (impl_op_display_fmt $Op:ident [RegId RegId Imm12]) => {
+        fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let (ra, rb, imm) = self.unpack();
+            write!(f, "{} {} {} {:#x}", Self::OPCODE.mnemonic(), ra, rb, u16::from(imm))
+        }
+    };
+    (impl_op_display_fmt $Op:ident [RegId Imm18]) => {
+        fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let (ra, imm) = self.unpack();
+            write!(f, "{} {} {:#x}", Self::OPCODE.mnemonic(), ra, u32::from(imm))
+        }
+    };
+    (impl_op_display_fmt $Op:ident [Imm24]) => {
+        fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let imm = self.unpack();
+            write!(f, "{} {:#x}", Self::OPCODE.mnemonic(), u32::from(imm))
+        }
+    };
+    (impl_op_display_fmt $Op:ident []) => {
+        fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            write!(f, "{}", Self::OPCODE.mnemonic())
+        }
+    };
+
+    // Generate a fn parsing the operands of the textual assembly format, based on the layout.
+    (impl_op_from_operands [RegId]) => {
+        pub(crate) fn from_operands<'a>(mut operands: impl Iterator<Item = &'a str>) -> Result<Self, ParseError> {
+            let ra = fasm::parse_reg(operands.next())?;
+            fasm::no_more_operands(operands)?;
+            Ok(Self::new(ra))
+        }
+    };
+    (impl_op_from_operands [RegId RegId]) => {
+        pub(crate) fn from_operands<'a>(mut operands: impl Iterator<Item = &'a str>) -> Result<Self, ParseError> {
+            let ra = fasm::parse_reg(operands.next())?;
+            let rb = fasm::parse_reg(operands.next())?;
+            fasm::no_more_operands(operands)?;
+            Ok(Self::new(ra, rb))
+        }
+    };
+    (impl_op_from_operands [RegId RegId RegId]) => {
+        pub(crate) fn from_operands<'a>(mut operands: impl Iterator<Item = &'a str>) -> Result<Self, ParseError> {
+            let ra = fasm::parse_reg(operands.next())?;
+            let rb = fasm::parse_reg(operands.next())?;
+            let rc = fasm::parse_reg(operands.next())?;
+            fasm::no_more_operands(operands)?;
+            Ok(Self::new(ra, rb, rc))
+        }
+    };
+    (impl_op_from_operands [RegId RegId RegId RegId]) => {
+        pub(crate) fn from_operands<'a>(mut operands: impl Iterator<Item = &'a str>) -> Result<Self, ParseError> {
+            let ra = fasm::parse_reg(operands.next())?;
+            let rb = fasm::parse_reg(operands.next())?;
+            let rc = fasm::parse_reg(operands.next())?;
+            let rd = fasm::parse_reg(operands.next())?;
+            fasm::no_more_operands(operands)?;
+            Ok(Self::new(ra, rb, rc, rd))
+        }
+    };
+    (impl_op_from_operands [RegId RegId Imm12]) => {
+        pub(crate) fn from_operands<'a>(mut operands: impl Iterator<Item = &'a str>) -> Result<Self, ParseError> {
+            let ra = fasm::parse_reg(operands.next())?;
+            let rb = fasm::parse_reg(operands.next())?;
+            let imm = fasm::parse_imm(operands.next())
+                .and_then(|imm| u16::try_from(imm).ok().and_then(Imm12::new_checked).ok_or(ParseError::ImmediateOutOfRange))?;
+            fasm::no_more_operands(operands)?;
+            Ok(Self::new(ra, rb, imm))
+        }
+    };
+    (impl_op_from_operands [RegId Imm18]) => {
+        pub(crate) fn from_operands<'a>(mut operands: impl Iterator<Item = &'a str>) -> Result<Self, ParseError> {
+            let ra = fasm::parse_reg(operands.next())?;
+            let imm = fasm::parse_imm(operands.next())
+                .and_then(|imm| u32::try_from(imm).ok().and_then(Imm18::new_checked).ok_or(ParseError::ImmediateOutOfRange))?;
+            fasm::no_more_operands(operands)?;
+            Ok(Self::new(ra, imm))
+        }
+    };
+    (impl_op_from_operands [Imm24]) => {
+        pub(crate) fn from_operands<'a>(mut operands: impl Iterator<Item = &'a str>) -> Result<Self, ParseError> {
+            let imm = fasm::parse_imm(operands.next())
+                .and_then(|imm| u32::try_from(imm).ok().and_then(Imm24::new_checked).ok_or(ParseError::ImmediateOutOfRange))?;
+            fasm::no_more_operands(operands)?;
+            Ok(Self::new(imm))
+        }
+    };
+    (impl_op_from_operands []) => {
+        pub(crate) fn from_operands<'a>(operands: impl Iterator<Item = &'a str>) -> Result<Self, ParseError> {
+            fasm::no_more_operands(operands)?;
+            Ok(Self::new())
+        }
+    };
+
     // Implement constructors and accessors for register and immediate values.
     (impl_op $doc:literal $ix:literal $Op:ident $op:ident [$($field:ident)*] $($rest:tt)*) => {
         impl $Op {
@@ -617,6 +735,7 @@ macro_rules! impl_instructions {
             impl_instructions!(impl_op_accessors [$($field)*]);
             impl_instructions!(impl_op_unpack [$($field)*]);
             impl_instructions!(impl_op_reg_ids [$($field)*]);
+            impl_instructions!(impl_op_from_operands [$($field)*]);
         }
 
         impl_instructions!(impl_op_constructor $doc $Op $op [$($field)*]);
@@ -649,6 +768,10 @@ macro_rules! impl_instructions {
             impl_instructions!(impl_op_debug_fmt $Op [$($field)*]);
         }
 
+        impl core::fmt::Display for $Op {
+            impl_instructions!(impl_op_display_fmt $Op [$($field)*]);
+        }
+
         impl_instructions!(impl_op $($rest)*);
     };
     (impl_op) => {};
@@ -668,6 +791,25 @@ macro_rules! impl_instructions {
         }
 
         impl Opcode {
+            /// The lowercase mnemonic of the opcode in the textual assembly format.
+            pub fn mnemonic(&self) -> &'static str {
+                match self {
+                    $(
+                        Self::$Op => stringify!($op).trim_end_matches('_'),
+                    )*
+                }
+            }
+
+            /// Find the opcode for a mnemonic of the textual assembly format, ignoring ASCII case.
+            pub fn from_mnemonic(mnemonic: &str) -> Option<Self> {
+                match mnemonic {
+                    $(
+                        m if m.eq_ignore_ascii_case(stringify!($op).trim_end_matches('_')) => Some(Self::$Op),
+                    )*
+                    _ => None,
+                }
+            }
+
             /// Construct the instruction from all possible raw fields, ignoring inapplicable ones.
             #[cfg(test)]
             pub fn test_construct(self, ra: RegId, rb: RegId, rc: RegId, rd: RegId, imm: u32) -> Instruction {
@@ -732,6 +874,30 @@ macro_rules! impl_instructions {
                 }
             }
         }
+
+        impl core::fmt::Display for Instruction {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                match self {
+                    $(
+                        Self::$Op(op) => core::fmt::Display::fmt(op, f),
+                    )*
+                }
+            }
+        }
+
+        impl Instruction {
+            /// Construct the instruction from the operands of the textual assembly format.
+            pub(crate) fn from_operands<'a>(
+                opcode: Opcode,
+                operands: impl Iterator<Item = &'a str>,
+            ) -> Result<Self, ParseError> {
+                match opcode {
+                    $(
+                        Opcode::$Op => op::$Op::from_operands(operands).map(Self::$Op),
+                    )*
+                }
+            }
+        }
     };
 
     // Entrypoint to the macro, generates structs, methods, opcode enum and instruction enum
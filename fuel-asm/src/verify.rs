@@ -0,0 +1,236 @@
+//! Static verification of bytecode before it is deployed or executed.
+
+use core::fmt;
+
+use crate::{Instruction, Opcode, RawInstruction, RegId};
+
+/// The context a program is executed in, which determines the instructions allowed in it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProgramKind {
+    /// The script of a transaction.
+    Script,
+    /// The predicate of an input, restricted to [`Opcode::is_predicate_allowed`].
+    Predicate,
+    /// The bytecode of a contract.
+    Contract,
+}
+
+/// A problem found in a program that would cause a panic if reached at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum IssueKind {
+    /// The instruction has a reserved or invalid opcode.
+    InvalidOpcode(RawInstruction),
+    /// The immediate jump lands outside of the program.
+    JumpOutOfBounds {
+        /// The target instruction index, relative to the start of the program.
+        target: u32,
+    },
+    /// The opcode is not allowed in predicates.
+    NotAllowedInPredicate(Opcode),
+    /// The instruction writes to a reserved register.
+    ReservedRegisterWrite(RegId),
+    /// The program length is not a multiple of the instruction size.
+    UnalignedLength {
+        /// The number of bytes after the last complete instruction.
+        trailing: usize,
+    },
+}
+
+/// An issue found in a program, along with its location.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VerificationIssue {
+    /// Offset of the offending instruction from the beginning of the program, in bytes.
+    pub offset: usize,
+    /// The kind of issue.
+    pub kind: IssueKind,
+}
+
+/// The result of statically verifying a program with [`verify`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VerificationReport {
+    kind: ProgramKind,
+    instructions: usize,
+    issues: Vec<VerificationIssue>,
+}
+
+impl VerificationReport {
+    /// The context the program was verified for.
+    pub const fn kind(&self) -> ProgramKind {
+        self.kind
+    }
+
+    /// The number of complete instructions in the program.
+    pub const fn instructions(&self) -> usize {
+        self.instructions
+    }
+
+    /// The issues found, ordered by offset.
+    pub fn issues(&self) -> &[VerificationIssue] {
+        &self.issues
+    }
+
+    /// Returns `true` if no issues were found.
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidOpcode(raw) => write!(f, "invalid opcode in instruction {raw:#010x}"),
+            Self::JumpOutOfBounds { target } => write!(f, "jump to instruction {target} outside of the program"),
+            Self::NotAllowedInPredicate(op) => write!(f, "`{}` is not allowed in predicates", op.mnemonic()),
+            Self::ReservedRegisterWrite(reg) => write!(f, "write to reserved register {reg}"),
+            Self::UnalignedLength { trailing } => write!(f, "{trailing} trailing bytes after the last instruction"),
+        }
+    }
+}
+
+impl fmt::Display for VerificationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:06x}: {}", self.offset, self.kind)
+    }
+}
+
+impl fmt::Display for VerificationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:?} of {} instructions, {} issues",
+            self.kind,
+            self.instructions,
+            self.issues.len()
+        )?;
+
+        self.issues.iter().try_for_each(|issue| writeln!(f, "  {issue}"))
+    }
+}
+
+/// Decode every instruction of a program and report the issues that would cause a panic when
+/// reached at runtime.
+///
+/// The analysis is purely static: every instruction is checked regardless of whether it is
+/// reachable, and jumps through registers (`jmp`, `jne`) are not followed.
+pub fn verify(bytecode: &[u8], kind: ProgramKind) -> VerificationReport {
+    let chunks = bytecode.chunks_exact(Instruction::SIZE);
+    let trailing = chunks.remainder().len();
+    let instructions = chunks.len();
+
+    let mut issues = vec![];
+
+    for (index, chunk) in chunks.enumerate() {
+        let mut report = |kind| {
+            issues.push(VerificationIssue {
+                offset: index * Instruction::SIZE,
+                kind,
+            })
+        };
+
+        let raw = [chunk[0], chunk[1], chunk[2], chunk[3]];
+        let instruction = match Instruction::try_from(raw) {
+            Ok(instruction) => instruction,
+            Err(_) => {
+                report(IssueKind::InvalidOpcode(RawInstruction::from_be_bytes(raw)));
+                continue;
+            }
+        };
+        let opcode = instruction.opcode();
+
+        if kind == ProgramKind::Predicate && !opcode.is_predicate_allowed() {
+            report(IssueKind::NotAllowedInPredicate(opcode));
+        }
+
+        if let Some(target) = instruction.jump_target().filter(|t| *t as usize >= instructions) {
+            report(IssueKind::JumpOutOfBounds { target });
+        }
+
+        let [ra, rb, ..] = instruction.reg_ids();
+        let written = [
+            ra.filter(|_| opcode.writes_to_ra()),
+            rb.filter(|_| opcode.writes_to_rb()),
+        ];
+        written
+            .into_iter()
+            .flatten()
+            .filter(|reg| *reg < RegId::WRITABLE)
+            .for_each(|reg| report(IssueKind::ReservedRegisterWrite(reg)));
+    }
+
+    if trailing != 0 {
+        issues.push(VerificationIssue {
+            offset: instructions * Instruction::SIZE,
+            kind: IssueKind::UnalignedLength { trailing },
+        });
+    }
+
+    VerificationReport {
+        kind,
+        instructions,
+        issues,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::op;
+
+    fn program(ops: impl IntoIterator<Item = Instruction>) -> Vec<u8> {
+        ops.into_iter().collect()
+    }
+
+    #[test]
+    fn valid_program_has_no_issues() {
+        let bytecode = program([
+            op::movi(0x10, 5),
+            op::subi(0x10, 0x10, 1),
+            op::jnzi(0x10, 1),
+            op::ret(RegId::ONE),
+        ]);
+
+        for kind in [ProgramKind::Script, ProgramKind::Predicate, ProgramKind::Contract] {
+            let report = verify(&bytecode, kind);
+            assert!(report.is_valid(), "{report}");
+            assert_eq!(report.instructions(), 4);
+        }
+    }
+
+    #[test]
+    fn reports_issues_by_offset() {
+        let mut bytecode = program([
+            op::ji(2),
+            op::jnei(0x10, RegId::ZERO, 5),
+            op::addi(RegId::ONE, 0x10, 1),
+            op::srw(0x10, RegId::ERR, 0x11),
+        ]);
+        bytecode.extend([0xff, 0, 0, 0, 0xaa]);
+
+        let report = verify(&bytecode, ProgramKind::Predicate);
+        let expected = [
+            (4, IssueKind::JumpOutOfBounds { target: 5 }),
+            (8, IssueKind::ReservedRegisterWrite(RegId::ONE)),
+            (12, IssueKind::NotAllowedInPredicate(Opcode::SRW)),
+            (12, IssueKind::ReservedRegisterWrite(RegId::ERR)),
+            (16, IssueKind::InvalidOpcode(0xff000000)),
+            (20, IssueKind::UnalignedLength { trailing: 1 }),
+        ]
+        .map(|(offset, kind)| VerificationIssue { offset, kind });
+
+        assert_eq!(report.instructions(), 5);
+        assert_eq!(report.issues(), &expected);
+    }
+
+    #[test]
+    fn contracts_may_use_contract_opcodes() {
+        let bytecode = program([op::srw(0x10, 0x11, 0x12), op::ret(RegId::ONE)]);
+
+        assert!(verify(&bytecode, ProgramKind::Contract).is_valid());
+        assert!(!verify(&bytecode, ProgramKind::Predicate).is_valid());
+    }
+}
@@ -2,6 +2,7 @@ mod hash;
 mod merkle_tree;
 mod node;
 mod primitive;
+mod verify;
 
 pub(crate) use hash::empty_sum;
 pub(crate) use hash::{leaf_sum, node_sum};
@@ -9,4 +10,5 @@ pub(crate) use node::Node;
 
 pub use merkle_tree::{MerkleTree, MerkleTreeError};
 pub use primitive::Primitive;
+pub use verify::verify;
 pub mod in_memory;
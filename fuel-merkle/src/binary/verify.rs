@@ -0,0 +1,105 @@
+use crate::binary::{leaf_sum, node_sum};
+use crate::common::{Bytes32, ProofSet};
+
+/// Verify that `data` is the leaf at `proof_index` of a binary Merkle tree of `num_leaves` leaves
+/// with the provided `root`.
+///
+/// The proof set is expected in the form returned by [`super::MerkleTree::prove`]: the leaf hash,
+/// followed by the side nodes from the leaf up to the root.
+pub fn verify<T: AsRef<[u8]>>(
+    root: &Bytes32,
+    data: &T,
+    proof_set: &ProofSet,
+    proof_index: u64,
+    num_leaves: u64,
+) -> bool {
+    if proof_index >= num_leaves {
+        return false;
+    }
+
+    let (leaf, side_nodes) = match proof_set.split_first() {
+        Some(split) => split,
+        None => return false,
+    };
+
+    if leaf != &leaf_sum(data.as_ref()) {
+        return false;
+    }
+
+    // Audit path verification as in RFC 9162, section 2.1.3.2
+    let mut index = proof_index;
+    let mut last_index = num_leaves - 1;
+    let mut sum = *leaf;
+
+    for side_node in side_nodes {
+        if last_index == 0 {
+            return false;
+        }
+
+        if index & 1 == 1 || index == last_index {
+            sum = node_sum(side_node, &sum);
+
+            while index & 1 == 0 && index != 0 {
+                index >>= 1;
+                last_index >>= 1;
+            }
+        } else {
+            sum = node_sum(&sum, side_node);
+        }
+
+        index >>= 1;
+        last_index >>= 1;
+    }
+
+    last_index == 0 && &sum == root
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::binary::in_memory::MerkleTree;
+    use fuel_merkle_test_helpers::TEST_DATA;
+
+    #[test]
+    fn verify_accepts_proofs_for_every_leaf() {
+        for num_leaves in 1..=TEST_DATA.len() {
+            let mut tree = MerkleTree::new();
+            let data = &TEST_DATA[0..num_leaves];
+            for datum in data.iter() {
+                tree.push(datum);
+            }
+
+            for (index, datum) in data.iter().enumerate() {
+                let (root, proof_set) = tree.prove(index as u64).unwrap();
+                assert!(verify(&root, datum, &proof_set, index as u64, num_leaves as u64));
+            }
+        }
+    }
+
+    #[test]
+    fn verify_rejects_tampered_proofs() {
+        let mut tree = MerkleTree::new();
+        let data = &TEST_DATA[0..7]; // 7 leaves
+        for datum in data.iter() {
+            tree.push(datum);
+        }
+
+        let (root, proof_set) = tree.prove(4).unwrap();
+
+        assert!(verify(&root, &data[4], &proof_set, 4, 7));
+        assert!(!verify(&root, &data[3], &proof_set, 4, 7));
+        assert!(!verify(&root, &data[4], &proof_set, 3, 7));
+        assert!(!verify(&root, &data[4], &proof_set, 4, 5));
+        assert!(!verify(&root, &data[4], &proof_set, 7, 7));
+        assert!(!verify(&[0; 32], &data[4], &proof_set, 4, 7));
+
+        let mut tampered = proof_set.clone();
+        tampered[1][0] ^= 1;
+        assert!(!verify(&root, &data[4], &tampered, 4, 7));
+
+        let mut truncated = proof_set;
+        truncated.pop();
+        assert!(!verify(&root, &data[4], &truncated, 4, 7));
+        assert!(!verify(&root, &data[4], &vec![], 4, 7));
+    }
+}
@@ -12,6 +12,17 @@ where
     map: HashMap<Type::OwnedKey, Type::OwnedValue>,
 }
 
+impl<Type> Clone for StorageMap<Type>
+where
+    Type: Mappable,
+    Type::OwnedKey: Clone,
+    Type::OwnedValue: Clone,
+{
+    fn clone(&self) -> Self {
+        Self { map: self.map.clone() }
+    }
+}
+
 impl<Type> Default for StorageMap<Type>
 where
     Type: Mappable,
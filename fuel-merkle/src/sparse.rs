@@ -2,10 +2,14 @@ mod hash;
 mod merkle_tree;
 mod node;
 mod primitive;
+mod proof;
+mod verify;
 
 pub(crate) use hash::zero_sum;
 pub(crate) use node::{Node, StorageNode, StorageNodeError};
 
 pub use merkle_tree::{MerkleTree, MerkleTreeError};
 pub use primitive::Primitive;
+pub use proof::Proof;
+pub use verify::verify;
 pub mod in_memory;
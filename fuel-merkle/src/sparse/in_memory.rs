@@ -1,12 +1,13 @@
 use crate::{
     common::{Bytes32, StorageMap},
-    sparse::{self, Primitive},
+    sparse::{self, Primitive, Proof},
     storage::Mappable,
 };
 
 /// The table of the Sparse Merkle tree's nodes. [`MerkleTree`] works with it as a sparse merkle
 /// tree, where the storage key is `Bytes32` and the value is the [`Buffer`](crate::sparse::Buffer)
 /// (raw presentation of the [`Node`](crate::sparse::Node)).
+#[derive(Debug, Clone)]
 pub struct NodesTable;
 
 impl Mappable for NodesTable {
@@ -19,6 +20,7 @@ impl Mappable for NodesTable {
 type Storage = StorageMap<NodesTable>;
 type SparseMerkleTree = sparse::MerkleTree<NodesTable, Storage>;
 
+#[derive(Debug, Clone)]
 pub struct MerkleTree {
     tree: SparseMerkleTree,
 }
@@ -41,6 +43,10 @@ impl MerkleTree {
     pub fn root(&self) -> Bytes32 {
         self.tree.root()
     }
+
+    pub fn prove(&self, key: &Bytes32) -> Option<Proof> {
+        self.tree.prove(key).ok()
+    }
 }
 
 impl Default for MerkleTree {
@@ -1,6 +1,6 @@
 use crate::{
     common::{error::DeserializeError, AsPathIterator, Bytes32, ChildError},
-    sparse::{primitive::Primitive, zero_sum, Node, StorageNode, StorageNodeError},
+    sparse::{primitive::Primitive, zero_sum, Node, Proof, StorageNode, StorageNodeError},
     storage::{Mappable, StorageInspect, StorageMutate},
 };
 
@@ -32,7 +32,7 @@ impl<StorageError> From<StorageError> for MerkleTreeError<StorageError> {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct MerkleTree<TableType, StorageType> {
     root_node: Node,
     storage: StorageType,
@@ -86,6 +86,22 @@ where
         Ok(tree)
     }
 
+    /// Build the proof of the data held by `key`, or of its absence.
+    pub fn prove(&self, key: &Bytes32) -> Result<Proof, MerkleTreeError<StorageError>> {
+        if self.root_node().is_placeholder() {
+            return Ok(Proof::default());
+        }
+
+        // Only the key of the leaf matters to follow its path
+        let (path_nodes, side_nodes) = self.path_set(Node::create_leaf(key, &[]))?;
+        let leaf = &path_nodes[0];
+
+        Ok(Proof {
+            leaf: (!leaf.is_placeholder()).then(|| (*leaf.leaf_key(), *leaf.leaf_data())),
+            side_nodes: side_nodes.iter().map(Node::hash).collect(),
+        })
+    }
+
     // PRIVATE
 
     fn path_set(&self, leaf_node: Node) -> Result<(Vec<Node>, Vec<Node>), MerkleTreeError<StorageError>> {
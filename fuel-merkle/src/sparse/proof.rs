@@ -0,0 +1,14 @@
+use crate::common::{Bytes32, ProofSet};
+
+/// Proof of the data of a key in a sparse Merkle tree, or of its absence.
+///
+/// The path of a key ends either on the leaf of this key, on the leaf of another key sharing the
+/// same path prefix, or on a placeholder. The proof holds this leaf along with the side nodes of
+/// the path, which is enough to rebuild the root.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Proof {
+    /// Key and hash of the data of the leaf ending the path, or `None` for a placeholder.
+    pub leaf: Option<(Bytes32, Bytes32)>,
+    /// Side nodes of the path, from the leaf up to the root.
+    pub side_nodes: ProofSet,
+}
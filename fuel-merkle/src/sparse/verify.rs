@@ -0,0 +1,109 @@
+use crate::common::{
+    path::{Instruction, Path},
+    Bytes32, Prefix,
+};
+use crate::sparse::{
+    hash::{sum, sum_all},
+    zero_sum, Proof,
+};
+
+fn node_sum(lhs: &Bytes32, rhs: &Bytes32) -> Bytes32 {
+    // A node joining two placeholders is itself a placeholder
+    if lhs == zero_sum() && rhs == zero_sum() {
+        *zero_sum()
+    } else {
+        sum_all([Prefix::Node.as_ref(), lhs.as_ref(), rhs.as_ref()])
+    }
+}
+
+/// Verify that `key` holds `data` in a sparse Merkle tree with the provided `root`, or that it is
+/// absent from the tree when `data` is `None`.
+///
+/// The proof is expected in the form returned by [`super::MerkleTree::prove`].
+pub fn verify(root: &Bytes32, key: &Bytes32, data: Option<&[u8]>, proof: &Proof) -> bool {
+    let depth = proof.side_nodes.len();
+    if depth > Bytes32::default().len() * 8 {
+        return false;
+    }
+
+    let matches = match (&proof.leaf, data) {
+        (Some((leaf_key, leaf_data)), Some(data)) => leaf_key == key && leaf_data == &sum(data),
+        (Some((leaf_key, _)), None) => leaf_key != key,
+        (None, data) => data.is_none(),
+    };
+    if !matches {
+        return false;
+    }
+
+    let mut sum = match &proof.leaf {
+        Some((leaf_key, leaf_data)) => sum_all([Prefix::Leaf.as_ref(), leaf_key.as_ref(), leaf_data.as_ref()]),
+        None => *zero_sum(),
+    };
+
+    // The side nodes are ordered from the leaf up, so the deepest bit of the path comes first
+    for (side_node, index) in proof.side_nodes.iter().zip((0..depth).rev()) {
+        sum = match key.get_instruction(index) {
+            Some(Instruction::Left) => node_sum(&sum, side_node),
+            Some(Instruction::Right) => node_sum(side_node, &sum),
+            None => return false,
+        };
+    }
+
+    &sum == root
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sparse::in_memory::MerkleTree;
+
+    #[test]
+    fn verify_accepts_proofs_for_present_and_absent_keys() {
+        let keys: Vec<Bytes32> = (0u32..16).map(|i| sum(i.to_be_bytes())).collect();
+
+        let mut tree = MerkleTree::new();
+        let proof = tree.prove(&keys[0]).unwrap();
+        assert!(verify(&tree.root(), &keys[0], None, &proof));
+
+        for (i, key) in keys.iter().enumerate() {
+            tree.update(key, b"DATA");
+
+            for (j, key) in keys.iter().enumerate() {
+                let proof = tree.prove(key).unwrap();
+                let data = (j <= i).then_some(b"DATA".as_ref());
+
+                assert!(verify(&tree.root(), key, data, &proof));
+            }
+        }
+    }
+
+    #[test]
+    fn verify_rejects_tampered_proofs() {
+        let keys: Vec<Bytes32> = (0u32..7).map(|i| sum(i.to_be_bytes())).collect();
+
+        let mut tree = MerkleTree::new();
+        for key in keys.iter() {
+            tree.update(key, key);
+        }
+        let root = tree.root();
+        let proof = tree.prove(&keys[4]).unwrap();
+
+        assert!(verify(&root, &keys[4], Some(&keys[4]), &proof));
+        assert!(!verify(&root, &keys[4], Some(&keys[3]), &proof));
+        assert!(!verify(&root, &keys[4], None, &proof));
+        assert!(!verify(&root, &keys[3], Some(&keys[4]), &proof));
+        assert!(!verify(&[0; 32], &keys[4], Some(&keys[4]), &proof));
+
+        let mut tampered = proof.clone();
+        tampered.side_nodes[0][0] ^= 1;
+        assert!(!verify(&root, &keys[4], Some(&keys[4]), &tampered));
+
+        let mut truncated = proof.clone();
+        truncated.side_nodes.pop();
+        assert!(!verify(&root, &keys[4], Some(&keys[4]), &truncated));
+
+        // The key is present, so its path can't end on a placeholder
+        let absent = Proof { leaf: None, ..proof };
+        assert!(!verify(&root, &keys[4], None, &absent));
+    }
+}
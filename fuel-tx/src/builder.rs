@@ -10,6 +10,10 @@ use fuel_types::{Salt, Word};
 
 use alloc::vec::Vec;
 
+mod coin_selection;
+
+pub use coin_selection::{select_coins, CoinSelectionError, SelectionStrategy, SpendableCoin};
+
 pub trait BuildableAloc
 where
     Self: Default
@@ -221,6 +225,47 @@ impl<Tx: Buildable> TransactionBuilder<Tx> {
         self
     }
 
+    /// Fund the coin outputs and the fee of the transaction with coins owned by `secret`, picked
+    /// from `coins` with `strategy`.
+    ///
+    /// A `Change` output to the owner is added for every spent asset without one. The gas price,
+    /// the gas limit and the outputs should be set beforehand since they determine the required
+    /// amounts, and the inputs already added are deducted from them.
+    #[cfg(feature = "std")]
+    pub fn select_coins(
+        &mut self,
+        secret: SecretKey,
+        coins: &[SpendableCoin],
+        strategy: SelectionStrategy,
+        params: &crate::ConsensusParameters,
+    ) -> Result<&mut Self, CoinSelectionError> {
+        let pk = secret.public_key();
+        let owner = Input::owner(&pk);
+
+        let (selected, change) = coin_selection::cover(&self.tx, owner, coins, strategy, params)?;
+
+        if !selected.is_empty() {
+            self.sign_keys.push(secret);
+        }
+
+        for coin in selected {
+            self.tx.add_unsigned_coin_input(
+                coin.utxo_id,
+                &pk,
+                coin.amount,
+                coin.asset_id,
+                coin.tx_pointer,
+                coin.maturity,
+            );
+        }
+
+        self.tx
+            .outputs_mut()
+            .extend(change.into_iter().map(|asset_id| Output::change(owner, 0, asset_id)));
+
+        Ok(self)
+    }
+
     #[cfg(feature = "std")]
     pub fn add_unsigned_message_input(
         &mut self,
@@ -363,3 +408,18 @@ where
         self.finalize_without_signature().into()
     }
 }
+
+#[cfg(feature = "std")]
+impl<Tx> TransactionBuilder<Tx>
+where
+    Self: Finalizable<Tx>,
+    Tx: crate::UniqueIdentifier + Cacheable + crate::FormatValidityChecks + field::Inputs + field::Witnesses,
+{
+    /// Finalize the transaction for a multi-party signing flow.
+    ///
+    /// The inputs owned by the keys of the builder are signed; the remaining signature slots are
+    /// left empty to be signed by the other parties.
+    pub fn finalize_partially_signed(&mut self) -> crate::PartiallySigned<Tx> {
+        crate::PartiallySigned::new(self.finalize())
+    }
+}
@@ -0,0 +1,568 @@
+//! Selection of the coins funding a transaction.
+
+use crate::{Input, TxPointer, UtxoId};
+
+use fuel_types::{Address, AssetId, Word};
+
+#[cfg(feature = "std")]
+use crate::{
+    transaction::field::{Inputs, Outputs, Witnesses},
+    Chargeable, ConsensusParameters, Output, TransactionFee, Witness,
+};
+#[cfg(feature = "std")]
+use fuel_crypto::Signature;
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Maximum number of steps of the branch and bound search before falling back to
+/// [`SelectionStrategy::LargestFirst`].
+const BNB_MAX_TRIES: usize = 100_000;
+
+/// A coin that can be spent by a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpendableCoin {
+    /// UTXO id of the coin.
+    pub utxo_id: UtxoId,
+    /// Owner of the coin.
+    pub owner: Address,
+    /// Amount of the coin.
+    pub amount: Word,
+    /// Asset of the coin.
+    pub asset_id: AssetId,
+    /// Pointer to the transaction that created the coin.
+    pub tx_pointer: TxPointer,
+    /// Maturity of the coin.
+    pub maturity: Word,
+}
+
+impl SpendableCoin {
+    /// Signed coin input spending this coin.
+    pub const fn input(&self, witness_index: u8) -> Input {
+        Input::coin_signed(
+            self.utxo_id,
+            self.owner,
+            self.amount,
+            self.asset_id,
+            self.tx_pointer,
+            witness_index,
+            self.maturity,
+        )
+    }
+}
+
+/// Strategy used to pick the coins covering the required amount of an asset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SelectionStrategy {
+    /// Pick the largest coins first, minimizing the number of inputs.
+    LargestFirst,
+    /// Search the coins with the smallest excess over the required amount, minimizing the change.
+    /// Falls back to [`Self::LargestFirst`] if the search takes too long.
+    BranchAndBound,
+    /// Pick random coins until the required amount is covered, then add random coins bringing the
+    /// total closer to twice the required amount, so the change can fund similar transactions
+    /// later. The coins are shuffled with a generator seeded by `seed`.
+    #[cfg(feature = "random")]
+    RandomImprove {
+        /// Seed of the random generator.
+        seed: u64,
+    },
+}
+
+/// Errors produced while selecting coins.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum CoinSelectionError {
+    /// The coins don't cover the required amount of an asset.
+    InsufficientFunds {
+        /// The asset that is lacking.
+        asset_id: AssetId,
+        /// The amount required from the coins.
+        required: Word,
+        /// The total amount of the coins.
+        available: Word,
+    },
+    /// The selected coins don't fit in the maximum number of inputs.
+    TransactionInputsMax,
+    /// The amounts or the fee of the transaction overflow.
+    ArithmeticOverflow,
+}
+
+impl fmt::Display for CoinSelectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CoinSelectionError {}
+
+/// Pick coins covering the `required` amount of every asset, using `strategy` for each asset.
+///
+/// Only the coins of the required assets are considered, in the order of `coins` when the
+/// strategy doesn't define one.
+pub fn select_coins(
+    coins: &[SpendableCoin],
+    required: &BTreeMap<AssetId, Word>,
+    strategy: SelectionStrategy,
+) -> Result<Vec<SpendableCoin>, CoinSelectionError> {
+    #[cfg(feature = "random")]
+    let mut rng = match strategy {
+        SelectionStrategy::RandomImprove { seed } => {
+            Some(<rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(seed))
+        }
+        _ => None,
+    };
+
+    let mut selected = Vec::new();
+
+    for (asset_id, target) in required.iter().filter(|(_, target)| **target > 0) {
+        let candidates: Vec<&SpendableCoin> = coins
+            .iter()
+            .filter(|coin| &coin.asset_id == asset_id && coin.amount > 0)
+            .collect();
+
+        let picked = match strategy {
+            SelectionStrategy::LargestFirst => largest_first(candidates.clone(), *target),
+            SelectionStrategy::BranchAndBound => {
+                branch_and_bound(&candidates, *target).or_else(|| largest_first(candidates.clone(), *target))
+            }
+            #[cfg(feature = "random")]
+            SelectionStrategy::RandomImprove { .. } => random_improve(
+                candidates.clone(),
+                *target,
+                rng.as_mut().expect("The generator is seeded for this strategy"),
+            ),
+        };
+
+        match picked {
+            Some(picked) => selected.extend(picked.into_iter().copied()),
+            None => {
+                let available = candidates.iter().map(|coin| coin.amount as u128).sum::<u128>();
+
+                return Err(CoinSelectionError::InsufficientFunds {
+                    asset_id: *asset_id,
+                    required: *target,
+                    available: available.try_into().unwrap_or(Word::MAX),
+                });
+            }
+        }
+    }
+
+    Ok(selected)
+}
+
+fn largest_first(mut coins: Vec<&SpendableCoin>, target: Word) -> Option<Vec<&SpendableCoin>> {
+    coins.sort_by_key(|coin| core::cmp::Reverse(coin.amount));
+
+    let mut picked = Vec::new();
+    let mut sum = 0u128;
+
+    for coin in coins {
+        if sum >= target as u128 {
+            break;
+        }
+
+        sum += coin.amount as u128;
+        picked.push(coin);
+    }
+
+    (sum >= target as u128).then_some(picked)
+}
+
+/// Depth-first search over the inclusion of every coin, largest first, keeping the set with the
+/// smallest excess and, for the same excess, the fewest coins.
+fn branch_and_bound<'a>(coins: &[&'a SpendableCoin], target: Word) -> Option<Vec<&'a SpendableCoin>> {
+    let target = target as u128;
+
+    let mut coins = coins.to_vec();
+    coins.sort_by_key(|coin| core::cmp::Reverse(coin.amount));
+
+    // Amount of the coins from each depth to the end
+    let mut remaining = alloc::vec![0u128; coins.len() + 1];
+    for (i, coin) in coins.iter().enumerate().rev() {
+        remaining[i] = remaining[i + 1] + coin.amount as u128;
+    }
+
+    let mut included = alloc::vec![false; coins.len()];
+    let mut best: Option<(u128, Vec<usize>)> = None;
+    let mut depth = 0;
+    let mut sum = 0u128;
+
+    for _ in 0..BNB_MAX_TRIES {
+        let backtrack = if sum >= target {
+            let picked: Vec<usize> = (0..depth).filter(|i| included[*i]).collect();
+            let excess = sum - target;
+
+            let better = match &best {
+                Some((best_excess, best_picked)) => {
+                    excess < *best_excess || (excess == *best_excess && picked.len() < best_picked.len())
+                }
+                None => true,
+            };
+
+            if better {
+                best.replace((excess, picked));
+            }
+
+            true
+        } else {
+            sum + remaining[depth] < target
+        };
+
+        if matches!(best, Some((0, _))) {
+            break;
+        }
+
+        if backtrack {
+            // Exclude the deepest included coin and continue with the next one
+            match (0..depth).rev().find(|i| included[*i]) {
+                Some(i) => {
+                    included[i] = false;
+                    sum -= coins[i].amount as u128;
+                    depth = i + 1;
+                }
+                None => break,
+            }
+        } else {
+            included[depth] = true;
+            sum += coins[depth].amount as u128;
+            depth += 1;
+        }
+    }
+
+    best.map(|(_, picked)| picked.into_iter().map(|i| coins[i]).collect())
+}
+
+#[cfg(feature = "random")]
+fn random_improve<'a, R>(mut coins: Vec<&'a SpendableCoin>, target: Word, rng: &mut R) -> Option<Vec<&'a SpendableCoin>>
+where
+    R: rand::Rng,
+{
+    use rand::seq::SliceRandom;
+
+    coins.shuffle(rng);
+
+    let target = target as u128;
+    let mut coins = coins.into_iter();
+    let mut picked = Vec::new();
+    let mut sum = 0u128;
+
+    while sum < target {
+        let coin = coins.next()?;
+
+        sum += coin.amount as u128;
+        picked.push(coin);
+    }
+
+    let ideal = target * 2;
+    let max = target * 3;
+
+    for coin in coins {
+        let improved = sum + coin.amount as u128;
+
+        if improved <= max && ideal.abs_diff(improved) < ideal.abs_diff(sum) {
+            sum = improved;
+            picked.push(coin);
+        }
+    }
+
+    Some(picked)
+}
+
+/// Coins of `owner` covering the coin outputs and the fee of `tx`, and the assets lacking a
+/// `Change` output once they are spent.
+///
+/// The empty witnesses of the transaction, including the ones of the selected coins, are
+/// accounted as signatures for the fee.
+#[cfg(feature = "std")]
+pub(super) fn cover<Tx>(
+    tx: &Tx,
+    owner: Address,
+    coins: &[SpendableCoin],
+    strategy: SelectionStrategy,
+    params: &ConsensusParameters,
+) -> Result<(Vec<SpendableCoin>, Vec<AssetId>), CoinSelectionError>
+where
+    Tx: Clone + Chargeable + Inputs + Outputs + Witnesses,
+{
+    let mut available = BTreeMap::<AssetId, u128>::new();
+    let mut required = BTreeMap::<AssetId, u128>::new();
+
+    for input in tx.inputs() {
+        match input {
+            Input::CoinSigned { asset_id, amount, .. } | Input::CoinPredicate { asset_id, amount, .. } => {
+                *available.entry(*asset_id).or_default() += *amount as u128;
+            }
+            Input::MessageSigned { amount, .. } | Input::MessagePredicate { amount, .. } => {
+                *available.entry(AssetId::BASE).or_default() += *amount as u128;
+            }
+            Input::Contract { .. } => {}
+        }
+    }
+
+    for output in tx.outputs() {
+        if let Output::Coin { asset_id, amount, .. } = output {
+            *required.entry(*asset_id).or_default() += *amount as u128;
+        }
+    }
+
+    let candidates: Vec<SpendableCoin> = coins
+        .iter()
+        .filter(|coin| coin.owner == owner && coin.amount > 0)
+        .filter(|coin| !tx.inputs().iter().any(|input| input.utxo_id() == Some(&coin.utxo_id)))
+        .copied()
+        .collect();
+
+    let mut fee = 0u128;
+
+    // The fee grows with the selected coins, so the selection is repeated until it covers the fee
+    // of the transaction spending it. The fee only takes values of the finite subsets of coins, and
+    // strictly grows between iterations, so the loop ends.
+    loop {
+        let mut targets = required.clone();
+        *targets.entry(AssetId::BASE).or_default() += fee;
+
+        let targets = targets
+            .into_iter()
+            .map(|(asset_id, target)| {
+                let target = target.saturating_sub(available.get(&asset_id).copied().unwrap_or_default());
+
+                Word::try_from(target)
+                    .map(|target| (asset_id, target))
+                    .map_err(|_| CoinSelectionError::ArithmeticOverflow)
+            })
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
+
+        let selected = select_coins(&candidates, &targets, strategy)?;
+
+        let mut change: Vec<AssetId> = available
+            .keys()
+            .chain(selected.iter().map(|coin| &coin.asset_id))
+            .copied()
+            .filter(|asset_id| {
+                !tx.outputs()
+                    .iter()
+                    .any(|output| matches!(output, Output::Change { asset_id: a, .. } if a == asset_id))
+            })
+            .collect();
+        change.sort();
+        change.dedup();
+
+        if tx.inputs().len() + selected.len() > params.max_inputs as usize {
+            return Err(CoinSelectionError::TransactionInputsMax);
+        }
+
+        let mut funded = tx.clone();
+        for coin in &selected {
+            let witness_index = funded.witnesses().len() as u8;
+
+            funded.inputs_mut().push(coin.input(witness_index));
+            funded.witnesses_mut().push(Witness::default());
+        }
+        funded
+            .outputs_mut()
+            .extend(change.iter().map(|asset_id| Output::change(owner, 0, *asset_id)));
+        funded
+            .witnesses_mut()
+            .iter_mut()
+            .filter(|witness| witness.as_ref().is_empty())
+            .for_each(|witness| *witness = Witness::from(alloc::vec![0u8; Signature::LEN]));
+
+        let funded_fee = TransactionFee::checked_from_tx(params, &funded)
+            .ok_or(CoinSelectionError::ArithmeticOverflow)?
+            .total() as u128;
+
+        if funded_fee <= fee {
+            return Ok((selected, change));
+        }
+
+        fee = funded_fee;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cacheable, Finalizable, FormatValidityChecks, Script, TransactionBuilder};
+
+    use fuel_crypto::SecretKey;
+    use quickcheck::TestResult;
+    use quickcheck_macros::quickcheck;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    const STRATEGIES: [SelectionStrategy; 3] = [
+        SelectionStrategy::LargestFirst,
+        SelectionStrategy::BranchAndBound,
+        SelectionStrategy::RandomImprove { seed: 2322 },
+    ];
+
+    fn coins(rng: &mut StdRng, owner: Address, assets: &[AssetId], amounts: &[u32]) -> Vec<SpendableCoin> {
+        amounts
+            .iter()
+            .enumerate()
+            .map(|(i, amount)| SpendableCoin {
+                utxo_id: rng.gen(),
+                owner,
+                amount: *amount as Word,
+                asset_id: assets[i % assets.len()],
+                tx_pointer: rng.gen(),
+                maturity: 0,
+            })
+            .collect()
+    }
+
+    fn total(coins: &[SpendableCoin], asset_id: &AssetId) -> u128 {
+        coins
+            .iter()
+            .filter(|coin| &coin.asset_id == asset_id)
+            .map(|coin| coin.amount as u128)
+            .sum()
+    }
+
+    #[quickcheck]
+    fn selection_covers_required_amounts(amounts: Vec<u32>, required: (u32, u32), seed: u64) -> bool {
+        let rng = &mut StdRng::seed_from_u64(seed);
+        let assets = [rng.gen(), rng.gen()];
+        let owner = rng.gen();
+        let coins = coins(rng, owner, &assets, &amounts);
+        let required: BTreeMap<AssetId, Word> = [(assets[0], required.0 as Word), (assets[1], required.1 as Word)]
+            .into_iter()
+            .collect();
+
+        STRATEGIES.into_iter().all(|strategy| {
+            let covered = required
+                .iter()
+                .all(|(asset_id, target)| total(&coins, asset_id) >= *target as u128);
+
+            match select_coins(&coins, &required, strategy) {
+                Ok(selected) => {
+                    let mut ids: Vec<_> = selected.iter().map(|coin| coin.utxo_id).collect();
+                    ids.sort();
+                    ids.dedup();
+
+                    covered
+                        && ids.len() == selected.len()
+                        && selected.iter().all(|coin| coins.contains(coin))
+                        && required
+                            .iter()
+                            .all(|(asset_id, target)| total(&selected, asset_id) >= *target as u128)
+                }
+                Err(CoinSelectionError::InsufficientFunds {
+                    asset_id,
+                    required: target,
+                    available,
+                }) => !covered && required[&asset_id] == target && (available as u128) < target as u128,
+                Err(_) => false,
+            }
+        })
+    }
+
+    #[quickcheck]
+    fn branch_and_bound_excess_is_minimal(amounts: Vec<u8>, target: u16) -> TestResult {
+        // Keep the exhaustive search cheap
+        if amounts.len() > 10 {
+            return TestResult::discard();
+        }
+
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let asset_id = rng.gen();
+        let amounts: Vec<u32> = amounts.into_iter().map(u32::from).collect();
+        let owner = rng.gen();
+        let coins = coins(rng, owner, &[asset_id], &amounts);
+        let required = [(asset_id, target as Word)].into_iter().collect();
+
+        let best = (0u32..1 << coins.len())
+            .map(|set| {
+                (0..coins.len())
+                    .filter(|i| set & (1 << i) != 0)
+                    .map(|i| coins[i].amount as u128)
+                    .sum::<u128>()
+            })
+            .filter(|sum| *sum >= target as u128)
+            .min();
+
+        match select_coins(&coins, &required, SelectionStrategy::BranchAndBound) {
+            Ok(selected) if target > 0 => TestResult::from_bool(Some(total(&selected, &asset_id)) == best),
+            Ok(selected) => TestResult::from_bool(selected.is_empty()),
+            Err(_) => TestResult::from_bool(best.is_none()),
+        }
+    }
+
+    #[quickcheck]
+    fn builder_covers_outputs_and_fee(amounts: Vec<u32>, spent: (u32, u32), gas_price: u8, seed: u64) -> bool {
+        let rng = &mut StdRng::seed_from_u64(seed);
+        let params = ConsensusParameters::DEFAULT.with_gas_price_factor(1);
+        let secret = SecretKey::random(rng);
+        let owner = Input::owner(&secret.public_key());
+        let asset_id: AssetId = rng.gen();
+        let coins = coins(rng, owner, &[AssetId::BASE, asset_id], &amounts);
+        let spent = (spent.0 as Word + 1, spent.1 as Word + 1);
+        let recipient = rng.gen();
+
+        let builder = || {
+            let mut builder = TransactionBuilder::script(alloc::vec![], alloc::vec![]);
+            builder
+                .gas_price(gas_price as Word)
+                .gas_limit(10_000)
+                .add_output(Output::coin(recipient, spent.0, AssetId::BASE))
+                .add_output(Output::coin(recipient, spent.1, asset_id));
+            builder
+        };
+
+        // The fee can't exceed the one of a transaction spending every coin
+        let mut spending_all = builder();
+        coins.iter().for_each(|coin| {
+            spending_all.add_unsigned_coin_input(
+                secret,
+                coin.utxo_id,
+                coin.amount,
+                coin.asset_id,
+                coin.tx_pointer,
+                coin.maturity,
+            );
+        });
+        spending_all
+            .add_output(Output::change(owner, 0, AssetId::BASE))
+            .add_output(Output::change(owner, 0, asset_id));
+        let max_fee = TransactionFee::checked_from_tx(&params, &spending_all.finalize()).expect("fee overflow");
+
+        STRATEGIES.into_iter().all(
+            |strategy| match builder().select_coins(secret, &coins, strategy, &params) {
+                Ok(builder) => {
+                    let mut tx: Script = builder.finalize();
+                    tx.precompute();
+
+                    let fee = TransactionFee::checked_from_tx(&params, &tx).expect("fee overflow");
+                    let inputs: Vec<SpendableCoin> = coins
+                        .iter()
+                        .filter(|coin| tx.inputs().iter().any(|input| input.utxo_id() == Some(&coin.utxo_id)))
+                        .copied()
+                        .collect();
+
+                    tx.check_without_signatures(0, &params).is_ok()
+                        && inputs.len() == tx.inputs().len()
+                        && total(&inputs, &AssetId::BASE) >= spent.0 as u128 + fee.total() as u128
+                        && total(&inputs, &asset_id) >= spent.1 as u128
+                        && tx
+                            .outputs()
+                            .iter()
+                            .filter(|output| matches!(output, Output::Change { to, .. } if *to == owner))
+                            .count()
+                            == 2
+                }
+                Err(CoinSelectionError::InsufficientFunds { asset_id: lacking, .. }) if lacking == AssetId::BASE => {
+                    total(&coins, &AssetId::BASE) < spent.0 as u128 + max_fee.total() as u128
+                }
+                Err(CoinSelectionError::InsufficientFunds { asset_id: lacking, .. }) => {
+                    lacking == asset_id && total(&coins, &asset_id) < spent.1 as u128
+                }
+                Err(_) => false,
+            },
+        )
+    }
+}
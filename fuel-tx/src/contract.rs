@@ -3,7 +3,7 @@ use crate::{CheckError, StorageSlot, Transaction};
 use fuel_crypto::Hasher;
 use fuel_merkle::binary::in_memory::MerkleTree as BinaryMerkleTree;
 use fuel_merkle::sparse::in_memory::MerkleTree as SparseMerkleTree;
-use fuel_types::{Bytes32, Bytes8, ContractId, Salt};
+use fuel_types::{AssetId, Bytes32, Bytes8, ContractId, Salt};
 
 use alloc::vec::Vec;
 use core::iter;
@@ -81,6 +81,17 @@ impl Contract {
 
         ContractId::from(*hasher.digest())
     }
+
+    /// Calculate the asset id minted by a contract under a sub id, `sha256(contract_id ||
+    /// sub_id)`.
+    pub fn asset_id(contract_id: &ContractId, sub_id: &Bytes32) -> AssetId {
+        let mut hasher = Hasher::default();
+
+        hasher.input(contract_id);
+        hasher.input(sub_id);
+
+        AssetId::from(*hasher.digest())
+    }
 }
 
 impl From<Vec<u8>> for Contract {
@@ -30,10 +30,12 @@ mod receipt;
 mod transaction;
 
 #[cfg(feature = "builder")]
-pub use builder::{Buildable, Finalizable, TransactionBuilder};
+pub use builder::{
+    select_coins, Buildable, CoinSelectionError, Finalizable, SelectionStrategy, SpendableCoin, TransactionBuilder,
+};
 
 #[cfg(feature = "alloc")]
-pub use receipt::{Receipt, ScriptExecutionResult};
+pub use receipt::{CallNode, CallTree, Receipt, ReceiptQuery, ReceiptRepr, ScriptExecutionResult};
 
 #[cfg(feature = "alloc")]
 pub use transaction::{
@@ -43,7 +45,13 @@ pub use transaction::{
 };
 
 #[cfg(feature = "std")]
-pub use transaction::{Signable, UniqueIdentifier};
+pub use receipt::{AbiDecode, AbiError, AbiRegistry, AbiType, AbiValue, DecodedEvent, EventSchema};
+
+#[cfg(feature = "std")]
+pub use transaction::{DecodedInput, DecodedInstruction, DecodedOutput, DecodedTransaction};
+
+#[cfg(feature = "std")]
+pub use transaction::{PartialSignError, PartiallySigned, Signable, SignatureSlot, UniqueIdentifier};
 
 #[cfg(feature = "alloc")]
 #[allow(deprecated)]
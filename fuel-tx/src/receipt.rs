@@ -4,16 +4,23 @@ use fuel_asm::InstructionResult;
 use fuel_types::bytes::{self, padded_len_usize, SizedBytes, WORD_SIZE};
 use fuel_types::{Address, AssetId, Bytes32, ContractId, MessageId, Word};
 
+#[cfg(feature = "std")]
+mod abi;
+
 #[cfg(feature = "std")]
 mod receipt_std;
 
+mod query;
 mod receipt_repr;
 mod script_result;
 
-use receipt_repr::ReceiptRepr;
-
+pub use query::{CallNode, CallTree, ReceiptQuery};
+pub use receipt_repr::ReceiptRepr;
 pub use script_result::ScriptExecutionResult;
 
+#[cfg(feature = "std")]
+pub use abi::{AbiDecode, AbiError, AbiRegistry, AbiType, AbiValue, DecodedEvent, EventSchema};
+
 use crate::Output;
 
 #[derive(Debug, Clone, Derivative)]
@@ -120,6 +127,22 @@ pub enum Receipt {
         digest: Bytes32,
         data: Vec<u8>,
     },
+
+    Mint {
+        contract_id: ContractId,
+        asset_id: AssetId,
+        amount: Word,
+        pc: Word,
+        is: Word,
+    },
+
+    Burn {
+        contract_id: ContractId,
+        asset_id: AssetId,
+        amount: Word,
+        pc: Word,
+        is: Word,
+    },
 }
 
 impl Receipt {
@@ -337,6 +360,26 @@ impl Receipt {
         }
     }
 
+    pub const fn mint(contract_id: ContractId, asset_id: AssetId, amount: Word, pc: Word, is: Word) -> Self {
+        Self::Mint {
+            contract_id,
+            asset_id,
+            amount,
+            pc,
+            is,
+        }
+    }
+
+    pub const fn burn(contract_id: ContractId, asset_id: AssetId, amount: Word, pc: Word, is: Word) -> Self {
+        Self::Burn {
+            contract_id,
+            asset_id,
+            amount,
+            pc,
+            is,
+        }
+    }
+
     #[inline(always)]
     pub fn id(&self) -> Option<&ContractId> {
         trim_contract_id(match self {
@@ -351,6 +394,8 @@ impl Receipt {
             Self::TransferOut { id, .. } => Some(id),
             Self::ScriptResult { .. } => None,
             Self::MessageOut { .. } => None,
+            Self::Mint { contract_id, .. } => Some(contract_id),
+            Self::Burn { contract_id, .. } => Some(contract_id),
         })
     }
 
@@ -367,6 +412,8 @@ impl Receipt {
             Self::TransferOut { pc, .. } => Some(*pc),
             Self::ScriptResult { .. } => None,
             Self::MessageOut { .. } => None,
+            Self::Mint { pc, .. } => Some(*pc),
+            Self::Burn { pc, .. } => Some(*pc),
         }
     }
 
@@ -383,6 +430,8 @@ impl Receipt {
             Self::TransferOut { is, .. } => Some(*is),
             Self::ScriptResult { .. } => None,
             Self::MessageOut { .. } => None,
+            Self::Mint { is, .. } => Some(*is),
+            Self::Burn { is, .. } => Some(*is),
         }
     }
 
@@ -408,6 +457,8 @@ impl Receipt {
             Self::Transfer { amount, .. } => Some(*amount),
             Self::TransferOut { amount, .. } => Some(*amount),
             Self::MessageOut { amount, .. } => Some(*amount),
+            Self::Mint { amount, .. } => Some(*amount),
+            Self::Burn { amount, .. } => Some(*amount),
             _ => None,
         }
     }
@@ -417,6 +468,8 @@ impl Receipt {
             Self::Call { asset_id, .. } => Some(asset_id),
             Self::Transfer { asset_id, .. } => Some(asset_id),
             Self::TransferOut { asset_id, .. } => Some(asset_id),
+            Self::Mint { asset_id, .. } => Some(asset_id),
+            Self::Burn { asset_id, .. } => Some(asset_id),
             _ => None,
         }
     }
@@ -575,6 +628,8 @@ impl Receipt {
     pub const fn contract_id(&self) -> Option<&ContractId> {
         match self {
             Self::Panic { contract_id, .. } => contract_id.as_ref(),
+            Self::Mint { contract_id, .. } => Some(contract_id),
+            Self::Burn { contract_id, .. } => Some(contract_id),
             _ => None,
         }
     }
@@ -645,6 +700,11 @@ impl Receipt {
                     + WORD_SIZE // len
                     + Bytes32::LEN // digest
             }
+
+            ReceiptRepr::Mint | ReceiptRepr::Burn => {
+                AssetId::LEN // asset_id
+                    + WORD_SIZE // amount
+            }
         }
     }
 }
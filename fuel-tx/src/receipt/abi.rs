@@ -0,0 +1,641 @@
+//! Decoding of the data logged and returned by contracts.
+//!
+//! Contracts emit structured events with `LOGD`, identifying the event type with the log id in
+//! `$rB`. An [`AbiRegistry`] maps log ids to an [`AbiType`] and decodes the data of
+//! [`Receipt::LogData`] into dynamic [`AbiValue`]s. Rust types implementing [`AbiDecode`],
+//! usually with [`crate::impl_abi_decode`], can be decoded directly with
+//! [`Receipt::decode_data`].
+//!
+//! The encoding follows the Fuel ABI: integers and booleans take a full big-endian word, `b256`
+//! takes 32 bytes, strings are padded to a word and aggregates concatenate their members.
+
+use crate::Receipt;
+
+use fuel_types::bytes::WORD_SIZE;
+use fuel_types::{Address, AssetId, Bytes32, ContractId, Word};
+
+use core::fmt;
+use std::collections::HashMap;
+use std::error;
+
+/// The type of an ABI encoded value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AbiType {
+    /// A boolean, encoded as a word holding `0` or `1`.
+    Bool,
+    /// An 8 bits unsigned integer, encoded as a word.
+    U8,
+    /// A 16 bits unsigned integer, encoded as a word.
+    U16,
+    /// A 32 bits unsigned integer, encoded as a word.
+    U32,
+    /// A 64 bits unsigned integer.
+    U64,
+    /// A 32 bytes value, such as an address or a hash.
+    B256,
+    /// A fixed length UTF-8 string, padded to a word.
+    Str(usize),
+    /// A fixed length array.
+    Array(Box<AbiType>, usize),
+    /// A tuple of values.
+    Tuple(Vec<AbiType>),
+    /// A struct with named fields.
+    Struct {
+        /// Name of the struct.
+        name: String,
+        /// Names and types of the fields, in declaration order.
+        fields: Vec<(String, AbiType)>,
+    },
+}
+
+impl AbiType {
+    /// The number of bytes of an encoded value of this type, or `None` if it overflows `usize`.
+    pub fn encoded_len(&self) -> Option<usize> {
+        match self {
+            Self::Bool | Self::U8 | Self::U16 | Self::U32 | Self::U64 => Some(WORD_SIZE),
+            Self::B256 => Some(Bytes32::LEN),
+            Self::Str(len) => len.checked_add((WORD_SIZE - len % WORD_SIZE) % WORD_SIZE),
+            Self::Array(ty, len) => ty.encoded_len()?.checked_mul(*len),
+            Self::Tuple(types) => Self::encoded_len_of(types),
+            Self::Struct { fields, .. } => Self::encoded_len_of(fields.iter().map(|(_, ty)| ty)),
+        }
+    }
+
+    fn encoded_len_of<'a, I>(types: I) -> Option<usize>
+    where
+        I: IntoIterator<Item = &'a AbiType>,
+    {
+        types
+            .into_iter()
+            .try_fold(0usize, |len, ty| len.checked_add(ty.encoded_len()?))
+    }
+
+    /// Decode a value of this type, which must span the whole `data`.
+    pub fn decode(&self, data: &[u8]) -> Result<AbiValue, AbiError> {
+        let expected = self.encoded_len().ok_or(AbiError::LengthOverflow)?;
+
+        if data.len() != expected {
+            return Err(AbiError::UnexpectedLength {
+                expected,
+                found: data.len(),
+            });
+        }
+
+        self.decode_at(data, 0)
+    }
+
+    /// Decode a value starting at `offset`, with the length of `data` already checked.
+    fn decode_at(&self, data: &[u8], offset: usize) -> Result<AbiValue, AbiError> {
+        let word = || {
+            let mut word = [0u8; WORD_SIZE];
+            word.copy_from_slice(&data[offset..offset + WORD_SIZE]);
+            Word::from_be_bytes(word)
+        };
+        let invalid = AbiError::InvalidValue { offset };
+
+        let value = match self {
+            Self::Bool => match word() {
+                0 => AbiValue::Bool(false),
+                1 => AbiValue::Bool(true),
+                _ => return Err(invalid),
+            },
+            Self::U8 => AbiValue::U8(word().try_into().map_err(|_| invalid)?),
+            Self::U16 => AbiValue::U16(word().try_into().map_err(|_| invalid)?),
+            Self::U32 => AbiValue::U32(word().try_into().map_err(|_| invalid)?),
+            Self::U64 => AbiValue::U64(word()),
+            Self::B256 => {
+                let mut bytes = [0u8; Bytes32::LEN];
+                bytes.copy_from_slice(&data[offset..offset + Bytes32::LEN]);
+                AbiValue::B256(bytes.into())
+            }
+            Self::Str(len) => {
+                let bytes = data[offset..offset + len].to_vec();
+                AbiValue::Str(String::from_utf8(bytes).map_err(|_| invalid)?)
+            }
+            Self::Array(ty, len) => AbiValue::Array(Self::decode_seq(data, offset, (0..*len).map(|_| &**ty))?),
+            Self::Tuple(types) => AbiValue::Tuple(Self::decode_seq(data, offset, types)?),
+            Self::Struct { name, fields } => {
+                let values = Self::decode_seq(data, offset, fields.iter().map(|(_, ty)| ty))?;
+
+                AbiValue::Struct {
+                    name: name.clone(),
+                    fields: fields.iter().map(|(name, _)| name.clone()).zip(values).collect(),
+                }
+            }
+        };
+
+        Ok(value)
+    }
+
+    fn decode_seq<'a, I>(data: &[u8], mut offset: usize, types: I) -> Result<Vec<AbiValue>, AbiError>
+    where
+        I: IntoIterator<Item = &'a AbiType>,
+    {
+        types
+            .into_iter()
+            .map(|ty| {
+                let value = ty.decode_at(data, offset)?;
+                offset += ty.encoded_len().ok_or(AbiError::LengthOverflow)?;
+                Ok(value)
+            })
+            .collect()
+    }
+}
+
+/// A dynamically typed ABI value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AbiValue {
+    /// A boolean.
+    Bool(bool),
+    /// An 8 bits unsigned integer.
+    U8(u8),
+    /// A 16 bits unsigned integer.
+    U16(u16),
+    /// A 32 bits unsigned integer.
+    U32(u32),
+    /// A 64 bits unsigned integer.
+    U64(u64),
+    /// A 32 bytes value.
+    B256(Bytes32),
+    /// A string.
+    Str(String),
+    /// The elements of an array.
+    Array(Vec<AbiValue>),
+    /// The members of a tuple.
+    Tuple(Vec<AbiValue>),
+    /// A struct with named fields.
+    Struct {
+        /// Name of the struct.
+        name: String,
+        /// Names and values of the fields, in declaration order.
+        fields: Vec<(String, AbiValue)>,
+    },
+}
+
+impl AbiValue {
+    /// Render the value as JSON.
+    ///
+    /// Structs are rendered as objects with sorted keys, arrays and tuples as arrays, and
+    /// `b256` values as `0x` prefixed hex strings.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        self.json_value().to_string()
+    }
+
+    #[cfg(feature = "serde")]
+    fn json_value(&self) -> serde_json::Value {
+        use serde_json::Value;
+
+        match self {
+            Self::Bool(b) => Value::from(*b),
+            Self::U8(n) => Value::from(*n),
+            Self::U16(n) => Value::from(*n),
+            Self::U32(n) => Value::from(*n),
+            Self::U64(n) => Value::from(*n),
+            Self::B256(b) => Value::from(format!("{b:#x}")),
+            Self::Str(s) => Value::from(s.as_str()),
+            Self::Array(values) | Self::Tuple(values) => values.iter().map(Self::json_value).collect(),
+            Self::Struct { fields, .. } => Value::Object(
+                fields
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.json_value()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Errors produced while decoding ABI encoded data.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum AbiError {
+    /// The data length doesn't match the encoded length of the type.
+    UnexpectedLength {
+        /// Encoded length of the type.
+        expected: usize,
+        /// Length of the data.
+        found: usize,
+    },
+    /// The encoded length of the type overflows `usize`.
+    LengthOverflow,
+    /// The bytes at the offset are not a valid encoding of their type.
+    InvalidValue {
+        /// Offset of the value in the data.
+        offset: usize,
+    },
+    /// A decoded value doesn't have the shape of the Rust type it is converted to.
+    TypeMismatch,
+    /// No event is registered for the log id.
+    UnknownLogId(Word),
+    /// The receipt doesn't carry any data.
+    UnexpectedReceipt,
+}
+
+impl fmt::Display for AbiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedLength { expected, found } => {
+                write!(f, "expected {expected} bytes of encoded data, found {found}")
+            }
+            Self::LengthOverflow => write!(f, "the encoded length of the type overflows"),
+            Self::InvalidValue { offset } => write!(f, "invalid encoded value at offset {offset}"),
+            Self::TypeMismatch => write!(f, "the decoded value doesn't match the requested type"),
+            Self::UnknownLogId(id) => write!(f, "no event registered for log id {id}"),
+            Self::UnexpectedReceipt => write!(f, "the receipt doesn't carry any data"),
+        }
+    }
+}
+
+impl error::Error for AbiError {}
+
+/// A Rust type that can be decoded from ABI encoded data.
+pub trait AbiDecode: Sized {
+    /// The ABI type of the encoded values.
+    fn abi_type() -> AbiType;
+
+    /// Convert a decoded value of [`Self::abi_type`].
+    fn from_abi(value: AbiValue) -> Result<Self, AbiError>;
+
+    /// Decode an instance from ABI encoded data.
+    fn abi_decode(data: &[u8]) -> Result<Self, AbiError> {
+        Self::from_abi(Self::abi_type().decode(data)?)
+    }
+}
+
+macro_rules! impl_abi_decode_primitive {
+    ($($t:ty => $variant:ident($v:ident) => $convert:expr),* $(,)?) => {
+        $(
+            impl AbiDecode for $t {
+                fn abi_type() -> AbiType {
+                    AbiType::$variant
+                }
+
+                fn from_abi(value: AbiValue) -> Result<Self, AbiError> {
+                    match value {
+                        AbiValue::$variant($v) => Ok($convert),
+                        _ => Err(AbiError::TypeMismatch),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_abi_decode_primitive!(
+    bool => Bool(v) => v,
+    u8 => U8(v) => v,
+    u16 => U16(v) => v,
+    u32 => U32(v) => v,
+    u64 => U64(v) => v,
+    Bytes32 => B256(v) => v,
+    Address => B256(v) => Self::from(*v),
+    AssetId => B256(v) => Self::from(*v),
+    ContractId => B256(v) => Self::from(*v),
+);
+
+impl<T, const N: usize> AbiDecode for [T; N]
+where
+    T: AbiDecode,
+{
+    fn abi_type() -> AbiType {
+        AbiType::Array(Box::new(T::abi_type()), N)
+    }
+
+    fn from_abi(value: AbiValue) -> Result<Self, AbiError> {
+        match value {
+            AbiValue::Array(values) => values
+                .into_iter()
+                .map(T::from_abi)
+                .collect::<Result<Vec<_>, _>>()?
+                .try_into()
+                .map_err(|_| AbiError::TypeMismatch),
+            _ => Err(AbiError::TypeMismatch),
+        }
+    }
+}
+
+macro_rules! impl_abi_decode_tuple {
+    ($($t:ident),+) => {
+        impl<$($t),+> AbiDecode for ($($t,)+)
+        where
+            $($t: AbiDecode),+
+        {
+            fn abi_type() -> AbiType {
+                AbiType::Tuple(vec![$($t::abi_type()),+])
+            }
+
+            fn from_abi(value: AbiValue) -> Result<Self, AbiError> {
+                match value {
+                    AbiValue::Tuple(values) => {
+                        let mut values = values.into_iter();
+                        let tuple = ($($t::from_abi(values.next().ok_or(AbiError::TypeMismatch)?)?,)+);
+
+                        values.next().map_or(Ok(tuple), |_| Err(AbiError::TypeMismatch))
+                    }
+                    _ => Err(AbiError::TypeMismatch),
+                }
+            }
+        }
+    };
+}
+
+impl_abi_decode_tuple!(A);
+impl_abi_decode_tuple!(A, B);
+impl_abi_decode_tuple!(A, B, C);
+impl_abi_decode_tuple!(A, B, C, D);
+
+/// Implement [`AbiDecode`] for a struct, decoding its fields in the provided order.
+///
+/// ```
+/// use fuel_tx::{impl_abi_decode, AbiDecode, Address};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Transfer {
+///     to: Address,
+///     amount: u64,
+/// }
+///
+/// impl_abi_decode!(Transfer { to: Address, amount: u64 });
+///
+/// let mut data = [0xaa; 40];
+/// data[32..].copy_from_slice(&10u64.to_be_bytes());
+///
+/// let transfer = Transfer::abi_decode(&data).unwrap();
+/// assert_eq!(transfer, Transfer { to: Address::from([0xaa; 32]), amount: 10 });
+/// ```
+#[macro_export]
+macro_rules! impl_abi_decode {
+    ($name:ident { $($field:ident: $ty:ty),* $(,)? }) => {
+        impl $crate::AbiDecode for $name {
+            fn abi_type() -> $crate::AbiType {
+                $crate::AbiType::Struct {
+                    name: stringify!($name).into(),
+                    fields: vec![$((stringify!($field).into(), <$ty as $crate::AbiDecode>::abi_type())),*],
+                }
+            }
+
+            fn from_abi(value: $crate::AbiValue) -> Result<Self, $crate::AbiError> {
+                match value {
+                    $crate::AbiValue::Struct { fields, .. } => {
+                        let mut fields = fields.into_iter().map(|(_, value)| value);
+
+                        Ok(Self {
+                            $($field: <$ty as $crate::AbiDecode>::from_abi(
+                                fields.next().ok_or($crate::AbiError::TypeMismatch)?,
+                            )?),*
+                        })
+                    }
+                    _ => Err($crate::AbiError::TypeMismatch),
+                }
+            }
+        }
+    };
+}
+
+/// The schema of an event logged with `LOGD`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EventSchema {
+    name: String,
+    ty: AbiType,
+}
+
+impl EventSchema {
+    /// Name of the event.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Type of the logged data.
+    pub const fn ty(&self) -> &AbiType {
+        &self.ty
+    }
+}
+
+/// An event decoded from a [`Receipt::LogData`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DecodedEvent {
+    /// Contract that logged the event, zeroed for scripts.
+    pub id: ContractId,
+    /// Log id of the event, from `$rB`.
+    pub log_id: Word,
+    /// Name of the event.
+    pub name: String,
+    /// Decoded data of the event.
+    pub value: AbiValue,
+}
+
+/// Event schemas of the data logged by contracts, keyed by log id.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AbiRegistry {
+    events: HashMap<Word, EventSchema>,
+}
+
+impl AbiRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the schema of the events logged with `log_id`, replacing any previous one.
+    pub fn register<N>(&mut self, log_id: Word, name: N, ty: AbiType) -> &mut Self
+    where
+        N: Into<String>,
+    {
+        self.events.insert(log_id, EventSchema { name: name.into(), ty });
+        self
+    }
+
+    /// Register the events logged with `log_id` as instances of `T`.
+    pub fn register_type<T, N>(&mut self, log_id: Word, name: N) -> &mut Self
+    where
+        T: AbiDecode,
+        N: Into<String>,
+    {
+        self.register(log_id, name, T::abi_type())
+    }
+
+    /// The schema registered for `log_id`.
+    pub fn schema(&self, log_id: Word) -> Option<&EventSchema> {
+        self.events.get(&log_id)
+    }
+
+    /// Decode a [`Receipt::LogData`] with the schema of its log id.
+    ///
+    /// Returns `None` for other receipts.
+    pub fn decode(&self, receipt: &Receipt) -> Result<Option<DecodedEvent>, AbiError> {
+        let (id, log_id, data) = match receipt {
+            Receipt::LogData { id, rb, data, .. } => (*id, *rb, data),
+            _ => return Ok(None),
+        };
+
+        let schema = self.schema(log_id).ok_or(AbiError::UnknownLogId(log_id))?;
+
+        Ok(Some(DecodedEvent {
+            id,
+            log_id,
+            name: schema.name.clone(),
+            value: schema.ty.decode(data)?,
+        }))
+    }
+
+    /// Decode every [`Receipt::LogData`] of `receipts`.
+    pub fn decode_all<'a, I>(&self, receipts: I) -> Result<Vec<DecodedEvent>, AbiError>
+    where
+        I: IntoIterator<Item = &'a Receipt>,
+    {
+        receipts
+            .into_iter()
+            .filter_map(|receipt| self.decode(receipt).transpose())
+            .collect()
+    }
+}
+
+impl Receipt {
+    /// Decode the data of a [`Receipt::LogData`] or [`Receipt::ReturnData`] as an instance of
+    /// `T`.
+    pub fn decode_data<T>(&self) -> Result<T, AbiError>
+    where
+        T: AbiDecode,
+    {
+        match self {
+            Self::LogData { data, .. } | Self::ReturnData { data, .. } => T::abi_decode(data),
+            _ => Err(AbiError::UnexpectedReceipt),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Swap {
+        pool: ContractId,
+        amounts: [u64; 2],
+        exact: bool,
+        fee: (u8, u32),
+    }
+
+    crate::impl_abi_decode!(Swap {
+        pool: ContractId,
+        amounts: [u64; 2],
+        exact: bool,
+        fee: (u8, u32),
+    });
+
+    fn word(n: Word) -> [u8; WORD_SIZE] {
+        n.to_be_bytes()
+    }
+
+    fn swap_data() -> Vec<u8> {
+        [&[3; 32][..], &word(10), &word(20), &word(1), &word(5), &word(300)].concat()
+    }
+
+    fn log_data(log_id: Word, data: Vec<u8>) -> Receipt {
+        Receipt::log_data(ContractId::from([1; 32]), 0, log_id, 0, Default::default(), data, 0, 0)
+    }
+
+    #[test]
+    fn decodes_typed_structs() {
+        let swap = Swap {
+            pool: ContractId::from([3; 32]),
+            amounts: [10, 20],
+            exact: true,
+            fee: (5, 300),
+        };
+
+        assert_eq!(Swap::abi_type().encoded_len(), Some(72));
+        assert_eq!(log_data(1, swap_data()).decode_data::<Swap>(), Ok(swap));
+        assert_eq!(
+            Receipt::ret(Default::default(), 0, 0, 0).decode_data::<Swap>(),
+            Err(AbiError::UnexpectedReceipt)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_encodings() {
+        let mut data = swap_data();
+        data[71] = 0;
+        data[64] = 1;
+
+        assert_eq!(Swap::abi_decode(&data), Err(AbiError::InvalidValue { offset: 64 }));
+        assert_eq!(
+            Swap::abi_decode(&data[1..]),
+            Err(AbiError::UnexpectedLength {
+                expected: 72,
+                found: 71
+            })
+        );
+        assert_eq!(
+            AbiType::Str(3).decode(&[0xff, 0, 0, 0, 0, 0, 0, 0]),
+            Err(AbiError::InvalidValue { offset: 0 })
+        );
+        assert_eq!(
+            Swap::abi_decode(&data[1..]).unwrap_err().to_string(),
+            "expected 72 bytes of encoded data, found 71"
+        );
+        assert_eq!(
+            Swap::abi_decode(&data).unwrap_err().to_string(),
+            "invalid encoded value at offset 64"
+        );
+    }
+
+    #[test]
+    fn rejects_types_overflowing_usize() {
+        let types = [
+            AbiType::Str(usize::MAX),
+            AbiType::Array(Box::new(AbiType::U64), usize::MAX / 4),
+            AbiType::Tuple(vec![
+                AbiType::Array(Box::new(AbiType::B256), usize::MAX / 32),
+                AbiType::B256,
+            ]),
+        ];
+
+        for ty in types {
+            assert_eq!(ty.encoded_len(), None);
+            assert_eq!(ty.decode(&[]), Err(AbiError::LengthOverflow));
+        }
+    }
+
+    #[test]
+    fn registry_decodes_log_data_by_log_id() {
+        let mut registry = AbiRegistry::new();
+        registry
+            .register_type::<Swap, _>(1, "Swap")
+            .register(2, "Memo", AbiType::Str(5));
+
+        let receipts = [
+            log_data(2, b"hello\0\0\0".to_vec()),
+            Receipt::ret(Default::default(), 0, 0, 0),
+            log_data(1, swap_data()),
+        ];
+
+        let events = registry.decode_all(&receipts).expect("known events");
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].name, "Memo");
+        assert_eq!(events[0].value, AbiValue::Str("hello".into()));
+        assert_eq!(events[1].value, Swap::abi_type().decode(&swap_data()).unwrap());
+
+        assert_eq!(registry.decode(&log_data(3, vec![])), Err(AbiError::UnknownLogId(3)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn renders_json() {
+        let value = Swap::abi_type().decode(&swap_data()).unwrap();
+
+        assert_eq!(
+            value.to_json(),
+            format!(
+                r#"{{"amounts":[10,20],"exact":true,"fee":[5,300],"pool":"0x{}"}}"#,
+                "03".repeat(32)
+            )
+        );
+    }
+}
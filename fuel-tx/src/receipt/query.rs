@@ -0,0 +1,357 @@
+use super::{Receipt, ReceiptRepr};
+
+use alloc::vec::Vec;
+use fuel_types::{AssetId, ContractId};
+
+impl Receipt {
+    /// Kind of the receipt, as identified in its serialized form.
+    pub fn kind(&self) -> ReceiptRepr {
+        self.into()
+    }
+}
+
+/// Filters over the receipts of an execution.
+///
+/// Filters are combined: a receipt is yielded only if it matches every filter set. Setting
+/// several kinds matches receipts of any of them.
+///
+/// ```
+/// use fuel_tx::{Receipt, ReceiptRepr, ReceiptQuery};
+/// use fuel_types::{AssetId, ContractId};
+///
+/// let contract = ContractId::from([1; 32]);
+/// let asset = AssetId::from([2; 32]);
+///
+/// let receipts = vec![
+///     Receipt::call(ContractId::zeroed(), contract, 10, asset, 1_000, 0, 0, 0, 0),
+///     Receipt::transfer(contract, ContractId::from([3; 32]), 5, asset, 0, 0),
+///     Receipt::mint(contract, AssetId::from([4; 32]), 1, 0, 0),
+///     Receipt::ret(contract, 0, 0, 0),
+/// ];
+///
+/// let transfers = ReceiptQuery::new(&receipts)
+///     .asset(asset)
+///     .kind(ReceiptRepr::Transfer)
+///     .kind(ReceiptRepr::TransferOut);
+///
+/// assert_eq!(transfers.iter().count(), 1);
+/// assert_eq!(ReceiptQuery::new(&receipts).contract(contract).iter().count(), 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReceiptQuery<'a> {
+    receipts: &'a [Receipt],
+    contract: Option<ContractId>,
+    asset: Option<AssetId>,
+    kinds: Vec<ReceiptRepr>,
+}
+
+impl<'a> ReceiptQuery<'a> {
+    /// Query over the receipts of an execution, in the order they were produced.
+    pub const fn new(receipts: &'a [Receipt]) -> Self {
+        Self {
+            receipts,
+            contract: None,
+            asset: None,
+            kinds: Vec::new(),
+        }
+    }
+
+    /// Keep only the receipts emitted by the contract, or calling or transferring to it.
+    pub fn contract(mut self, contract: ContractId) -> Self {
+        self.contract = Some(contract);
+        self
+    }
+
+    /// Keep only the receipts moving, minting or burning the asset.
+    pub fn asset(mut self, asset: AssetId) -> Self {
+        self.asset = Some(asset);
+        self
+    }
+
+    /// Keep only the receipts of the provided kind, or of any of the kinds set before.
+    pub fn kind(mut self, kind: ReceiptRepr) -> Self {
+        self.kinds.push(kind);
+        self
+    }
+
+    /// Check if a receipt matches the filters of the query.
+    pub fn matches(&self, receipt: &Receipt) -> bool {
+        let contract = self.contract.as_ref().map_or(true, |contract| {
+            receipt.id() == Some(contract) || receipt.to() == Some(contract)
+        });
+        let asset = self
+            .asset
+            .as_ref()
+            .map_or(true, |asset| receipt.asset_id() == Some(asset));
+        let kind = self.kinds.is_empty() || self.kinds.contains(&receipt.kind());
+
+        contract && asset && kind
+    }
+
+    /// Receipts matching the filters, in execution order.
+    pub fn iter(&self) -> impl Iterator<Item = &'a Receipt> + '_ {
+        self.indexed().map(|(_, receipt)| receipt)
+    }
+
+    /// Receipts matching the filters along with their index in the receipts of the execution.
+    pub fn indexed(&self) -> impl Iterator<Item = (usize, &'a Receipt)> + '_ {
+        self.receipts
+            .iter()
+            .enumerate()
+            .filter(move |(_, receipt)| self.matches(receipt))
+    }
+
+    /// Rebuild the call tree of the execution, ignoring the filters of the query.
+    pub fn call_tree(&self) -> CallTree {
+        CallTree::new(self.receipts)
+    }
+}
+
+/// A contract call, delimited by its `Call` receipt and the receipt that ended it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CallNode {
+    /// The called contract.
+    pub contract: ContractId,
+    /// Index of the `Call` receipt.
+    pub call: usize,
+    /// Index of the `Return`, `ReturnData`, `Panic` or `Revert` receipt ending the call, if any.
+    pub end: Option<usize>,
+    /// Index of the calling node in the tree, `None` for calls made by the script.
+    pub parent: Option<usize>,
+    /// Nesting depth of the call, starting at `1` for calls made by the script.
+    pub depth: usize,
+}
+
+impl CallNode {
+    /// Check if the receipt at `index` was produced while the call was executing, including the
+    /// receipts of nested calls.
+    pub fn contains(&self, index: usize) -> bool {
+        self.call < index && self.end.map_or(true, |end| index <= end)
+    }
+}
+
+/// Calls of an execution, rebuilt from its `Call`, `Return`, `ReturnData`, `Panic` and `Revert`
+/// receipts.
+///
+/// Nodes are stored in call order, so a parent always precedes its children. A `Panic` or
+/// `Revert` ends every open call, since it halts the whole execution. A `Return` or `ReturnData`
+/// ends the innermost open call even if it was emitted by another contract, which is reported by
+/// [`Self::mismatched_returns`].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct CallTree {
+    nodes: Vec<CallNode>,
+    mismatched: Vec<usize>,
+}
+
+impl CallTree {
+    /// Rebuild the call tree from the receipts of an execution.
+    pub fn new(receipts: &[Receipt]) -> Self {
+        let mut nodes: Vec<CallNode> = Vec::new();
+        let mut open: Vec<usize> = Vec::new();
+        let mut mismatched: Vec<usize> = Vec::new();
+
+        for (index, receipt) in receipts.iter().enumerate() {
+            match receipt {
+                Receipt::Call { to, .. } => {
+                    nodes.push(CallNode {
+                        contract: *to,
+                        call: index,
+                        end: None,
+                        parent: open.last().copied(),
+                        depth: open.len() + 1,
+                    });
+                    open.push(nodes.len() - 1);
+                }
+
+                Receipt::Return { id, .. } | Receipt::ReturnData { id, .. } => {
+                    if let Some(node) = open.pop() {
+                        if &nodes[node].contract != id {
+                            mismatched.push(index);
+                        }
+
+                        nodes[node].end = Some(index);
+                    }
+                }
+
+                Receipt::Panic { .. } | Receipt::Revert { .. } => {
+                    open.drain(..).for_each(|node| nodes[node].end = Some(index));
+                }
+
+                _ => (),
+            }
+        }
+
+        Self { nodes, mismatched }
+    }
+
+    /// All the nodes of the tree, in call order.
+    pub fn nodes(&self) -> &[CallNode] {
+        &self.nodes
+    }
+
+    /// Node at the provided index in the tree.
+    pub fn node(&self, node: usize) -> Option<&CallNode> {
+        self.nodes.get(node)
+    }
+
+    /// Indexes of the nodes called by the script.
+    pub fn roots(&self) -> impl Iterator<Item = usize> + '_ {
+        self.children_of(None)
+    }
+
+    /// Indexes of the nodes called from the provided node.
+    pub fn children(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.children_of(Some(node))
+    }
+
+    fn children_of(&self, parent: Option<usize>) -> impl Iterator<Item = usize> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(move |(_, f)| f.parent == parent)
+            .map(|(i, _)| i)
+    }
+
+    /// Index of the innermost node executing when the receipt at `index` was produced, or
+    /// `None` if it was produced by the script.
+    pub fn node_of(&self, index: usize) -> Option<usize> {
+        self.nodes.iter().rposition(|node| node.contains(index))
+    }
+
+    /// Indexes of the `Return` and `ReturnData` receipts that ended a call of another contract,
+    /// e.g. in receipts that were filtered or reordered. Always empty for the receipts of an
+    /// execution.
+    pub fn mismatched_returns(&self) -> &[usize] {
+        &self.mismatched
+    }
+
+    /// Maximum nesting depth of the calls, `0` if no contract was called.
+    pub fn max_depth(&self) -> usize {
+        self.nodes.iter().map(|node| node.depth).max().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_asm::{InstructionResult, PanicReason};
+
+    const A: ContractId = ContractId::new([1; 32]);
+    const B: ContractId = ContractId::new([2; 32]);
+    const C: ContractId = ContractId::new([3; 32]);
+
+    fn call(from: ContractId, to: ContractId) -> Receipt {
+        Receipt::call(from, to, 0, AssetId::zeroed(), 0, 0, 0, 0, 0)
+    }
+
+    #[test]
+    fn rebuilds_nested_calls() {
+        let receipts = vec![
+            call(ContractId::zeroed(), A),
+            call(A, B),
+            Receipt::log(B, 0, 0, 0, 0, 0, 0),
+            Receipt::ret(B, 0, 0, 0),
+            call(A, C),
+            Receipt::return_data(C, 0, Default::default(), vec![1], 0, 0),
+            Receipt::ret(A, 0, 0, 0),
+            call(ContractId::zeroed(), B),
+            Receipt::ret(B, 0, 0, 0),
+            Receipt::ret(ContractId::zeroed(), 0, 0, 0),
+        ];
+
+        let tree = CallTree::new(&receipts);
+        let nodes = tree.nodes();
+
+        assert_eq!(nodes.len(), 4);
+        assert_eq!(tree.roots().collect::<Vec<_>>(), vec![0, 3]);
+        assert_eq!(tree.children(0).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(tree.max_depth(), 2);
+
+        assert_eq!(
+            nodes[1],
+            CallNode {
+                contract: B,
+                call: 1,
+                end: Some(3),
+                parent: Some(0),
+                depth: 2,
+            }
+        );
+        assert_eq!(nodes[2].end, Some(5));
+        assert_eq!(nodes[0].end, Some(6));
+
+        assert_eq!(tree.node_of(2), Some(1));
+        assert_eq!(tree.node_of(6), Some(0));
+        assert_eq!(tree.node_of(9), None);
+    }
+
+    #[test]
+    fn panic_ends_open_calls() {
+        let receipts = vec![
+            call(ContractId::zeroed(), A),
+            call(A, B),
+            Receipt::panic(B, InstructionResult::error(PanicReason::ArithmeticOverflow, 0), 0, 0),
+        ];
+
+        let tree = CallTree::new(&receipts);
+
+        assert!(tree.nodes().iter().all(|node| node.end == Some(2)));
+        assert_eq!(tree.node_of(2), Some(1));
+    }
+
+    #[test]
+    fn mismatched_return_ends_the_innermost_call() {
+        let receipts = vec![
+            call(ContractId::zeroed(), A),
+            call(A, B),
+            Receipt::ret(A, 0, 0, 0),
+            Receipt::ret(A, 0, 0, 0),
+            Receipt::ret(ContractId::zeroed(), 0, 0, 0),
+        ];
+
+        let tree = CallTree::new(&receipts);
+
+        assert_eq!(tree.nodes()[1].end, Some(2));
+        assert_eq!(tree.nodes()[0].end, Some(3));
+        assert_eq!(tree.mismatched_returns(), &[2]);
+        assert_eq!(tree.node_of(4), None);
+        assert!(CallTree::new(&receipts[..2]).mismatched_returns().is_empty());
+    }
+
+    #[test]
+    fn filters_combine() {
+        let asset = AssetId::new([9; 32]);
+        let receipts = vec![
+            call(ContractId::zeroed(), A),
+            Receipt::transfer(A, B, 1, asset, 0, 0),
+            Receipt::transfer(A, C, 1, AssetId::zeroed(), 0, 0),
+            Receipt::mint(A, asset, 1, 0, 0),
+            Receipt::ret(A, 0, 0, 0),
+        ];
+
+        let query = ReceiptQuery::new(&receipts);
+
+        assert_eq!(query.clone().contract(A).iter().count(), 5);
+        assert_eq!(query.clone().contract(B).iter().count(), 1);
+        assert_eq!(query.clone().asset(asset).iter().count(), 2);
+        assert_eq!(
+            query
+                .clone()
+                .asset(asset)
+                .kind(ReceiptRepr::Transfer)
+                .indexed()
+                .map(|(i, _)| i)
+                .collect::<Vec<_>>(),
+            vec![1]
+        );
+        assert_eq!(
+            query
+                .kind(ReceiptRepr::Mint)
+                .kind(ReceiptRepr::Return)
+                .iter()
+                .map(Receipt::kind)
+                .collect::<Vec<_>>(),
+            vec![ReceiptRepr::Mint, ReceiptRepr::Return]
+        );
+    }
+}
@@ -1,6 +1,9 @@
 use crate::receipt::Receipt;
 
+/// Kind of a [`Receipt`], without its fields. The discriminant identifies the variant in the
+/// serialized receipt.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReceiptRepr {
     Call = 0x00,
     Return = 0x01,
@@ -13,6 +16,8 @@ pub enum ReceiptRepr {
     TransferOut = 0x08,
     ScriptResult = 0x09,
     MessageOut = 0x0A,
+    Mint = 0x0B,
+    Burn = 0x0C,
 }
 
 impl From<&Receipt> for ReceiptRepr {
@@ -29,6 +34,8 @@ impl From<&Receipt> for ReceiptRepr {
             Receipt::TransferOut { .. } => Self::TransferOut,
             Receipt::ScriptResult { .. } => Self::ScriptResult,
             Receipt::MessageOut { .. } => Self::MessageOut,
+            Receipt::Mint { .. } => Self::Mint,
+            Receipt::Burn { .. } => Self::Burn,
         }
     }
 }
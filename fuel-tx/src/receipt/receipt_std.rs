@@ -207,6 +207,40 @@ impl io::Read for Receipt {
 
                 bytes::store_bytes(buf, data)?;
             }
+
+            Self::Mint {
+                contract_id,
+                asset_id,
+                amount,
+                pc,
+                is,
+            } => {
+                let buf = bytes::store_number_unchecked(buf, ReceiptRepr::Mint as Word);
+
+                let buf = bytes::store_array_unchecked(buf, contract_id);
+                let buf = bytes::store_array_unchecked(buf, asset_id);
+                let buf = bytes::store_number_unchecked(buf, *amount);
+                let buf = bytes::store_number_unchecked(buf, *pc);
+
+                bytes::store_number_unchecked(buf, *is);
+            }
+
+            Self::Burn {
+                contract_id,
+                asset_id,
+                amount,
+                pc,
+                is,
+            } => {
+                let buf = bytes::store_number_unchecked(buf, ReceiptRepr::Burn as Word);
+
+                let buf = bytes::store_array_unchecked(buf, contract_id);
+                let buf = bytes::store_array_unchecked(buf, asset_id);
+                let buf = bytes::store_number_unchecked(buf, *amount);
+                let buf = bytes::store_number_unchecked(buf, *pc);
+
+                bytes::store_number_unchecked(buf, *is);
+            }
         }
 
         Ok(len)
@@ -405,6 +439,32 @@ impl io::Write for Receipt {
 
                 *self = Self::message_out_with_len(message_id, sender, recipient, amount, nonce, len, digest, data);
             }
+
+            ReceiptRepr::Mint => {
+                let (contract_id, buf) = unsafe { bytes::restore_array_unchecked(buf) };
+                let (asset_id, buf) = unsafe { bytes::restore_array_unchecked(buf) };
+                let (amount, buf) = unsafe { bytes::restore_word_unchecked(buf) };
+                let (pc, buf) = unsafe { bytes::restore_word_unchecked(buf) };
+                let (is, _) = unsafe { bytes::restore_word_unchecked(buf) };
+
+                let contract_id = contract_id.into();
+                let asset_id = asset_id.into();
+
+                *self = Self::mint(contract_id, asset_id, amount, pc, is);
+            }
+
+            ReceiptRepr::Burn => {
+                let (contract_id, buf) = unsafe { bytes::restore_array_unchecked(buf) };
+                let (asset_id, buf) = unsafe { bytes::restore_array_unchecked(buf) };
+                let (amount, buf) = unsafe { bytes::restore_word_unchecked(buf) };
+                let (pc, buf) = unsafe { bytes::restore_word_unchecked(buf) };
+                let (is, _) = unsafe { bytes::restore_word_unchecked(buf) };
+
+                let contract_id = contract_id.into();
+                let asset_id = asset_id.into();
+
+                *self = Self::burn(contract_id, asset_id, amount, pc, is);
+            }
         }
 
         Ok(used_len + WORD_SIZE)
@@ -443,6 +503,8 @@ impl TryFrom<Word> for ReceiptRepr {
             0x08 => Ok(Self::TransferOut),
             0x09 => Ok(Self::ScriptResult),
             0x0A => Ok(Self::MessageOut),
+            0x0B => Ok(Self::Mint),
+            0x0C => Ok(Self::Burn),
             _ => Err(io::Error::new(
                 io::ErrorKind::InvalidData,
                 "The provided identifier is invalid!",
@@ -11,9 +11,15 @@ mod repr;
 mod types;
 mod validity;
 
+#[cfg(feature = "std")]
+mod decoder;
+
 #[cfg(feature = "std")]
 mod id;
 
+#[cfg(feature = "std")]
+mod partially_signed;
+
 #[cfg(feature = "std")]
 mod txio;
 
@@ -28,9 +34,15 @@ pub use validity::{CheckError, FormatValidityChecks};
 
 use crate::TxPointer;
 
+#[cfg(feature = "std")]
+pub use decoder::{DecodedInput, DecodedInstruction, DecodedOutput, DecodedTransaction};
+
 #[cfg(feature = "std")]
 pub use id::{Signable, UniqueIdentifier};
 
+#[cfg(feature = "std")]
+pub use partially_signed::{PartialSignError, PartiallySigned, SignatureSlot};
+
 /// Identification of transaction (also called transaction hash)
 pub type TxId = Bytes32;
 
@@ -0,0 +1,633 @@
+//! Human readable representation of a transaction.
+//!
+//! [`DecodedTransaction`] collects the computed id, the fees, the disassembled script and
+//! predicates and the role of every input and output of a transaction. It can be rendered as
+//! text with its `Display` implementation, or as JSON with the `serde` feature.
+
+use crate::transaction::field::{
+    BytecodeWitnessIndex, GasLimit, GasPrice, Inputs, Maturity, Outputs, Salt as SaltField, Script as ScriptField,
+    ScriptData, StorageSlots, TxPointer as TxPointerField, Witnesses,
+};
+use crate::transaction::{ConsensusParameters, TransactionFee, TransactionRepr, UniqueIdentifier};
+use crate::{Input, Output, StorageSlot, Transaction, TxPointer, Witness};
+
+use fuel_asm::{Instruction, RawInstruction};
+use fuel_types::bytes::Deserializable;
+use fuel_types::{Bytes32, Salt, Word};
+
+use core::fmt;
+use std::io;
+
+/// A single disassembled instruction of a program.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DecodedInstruction {
+    /// Offset of the instruction from the beginning of the program, in bytes.
+    pub offset: usize,
+    /// The raw representation of the instruction.
+    pub raw: RawInstruction,
+    /// Number of bytes of the program covered: [`Instruction::SIZE`], or 1 for a trailing byte
+    /// that doesn't fill an instruction.
+    pub size: usize,
+    /// The decoded instruction, or `None` if the opcode is invalid or the entry is a trailing byte.
+    pub instruction: Option<String>,
+}
+
+impl DecodedInstruction {
+    /// Disassembles a program, one instruction per 4 bytes.
+    ///
+    /// Trailing bytes that don't form a full instruction are kept as raw bytes, one entry each,
+    /// like the `.byte` directives of [`fuel_asm::disassemble`].
+    pub fn disassemble(program: &[u8]) -> Vec<Self> {
+        let chunks = program.chunks_exact(Instruction::SIZE);
+        let trailing = chunks.remainder();
+        let instructions = chunks.len() * Instruction::SIZE;
+
+        chunks
+            .enumerate()
+            .map(|(i, chunk)| {
+                let bytes = [chunk[0], chunk[1], chunk[2], chunk[3]];
+
+                Self {
+                    offset: i * Instruction::SIZE,
+                    raw: RawInstruction::from_be_bytes(bytes),
+                    size: Instruction::SIZE,
+                    instruction: Instruction::try_from(bytes).ok().map(|i| i.to_string()),
+                }
+            })
+            .chain(trailing.iter().enumerate().map(|(i, byte)| Self {
+                offset: instructions + i,
+                raw: RawInstruction::from(*byte),
+                size: 1,
+                instruction: None,
+            }))
+            .collect()
+    }
+}
+
+impl fmt::Display for DecodedInstruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.size < Instruction::SIZE {
+            let raw = format!("{:02x}", self.raw);
+            return write!(f, "{:06x}: {raw:<8}  .byte {:#04x}", self.offset, self.raw);
+        }
+
+        write!(f, "{:06x}: {:08x}  ", self.offset, self.raw)?;
+
+        match &self.instruction {
+            Some(instruction) => write!(f, "{instruction}"),
+            None => write!(f, "<invalid opcode 0x{:02x}>", self.raw >> 24),
+        }
+    }
+}
+
+/// An input of the transaction with its role and disassembled predicate.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DecodedInput {
+    /// Index of the input in the transaction.
+    pub index: usize,
+    /// Short description of the role of the input.
+    pub role: String,
+    /// The input itself.
+    pub input: Input,
+    /// The disassembled predicate of the input, if any.
+    pub predicate: Option<Vec<DecodedInstruction>>,
+}
+
+impl DecodedInput {
+    fn new(index: usize, input: &Input) -> Self {
+        let role = match input {
+            Input::CoinSigned { witness_index, .. } => format!("coin signed by witness #{witness_index}"),
+            Input::CoinPredicate { .. } => "coin owned by a predicate".into(),
+            Input::Contract { .. } => "contract".into(),
+            Input::MessageSigned { witness_index, .. } => format!("message signed by witness #{witness_index}"),
+            Input::MessagePredicate { .. } => "message owned by a predicate".into(),
+        };
+
+        let predicate = input.input_predicate().map(DecodedInstruction::disassemble);
+
+        Self {
+            index,
+            role,
+            input: input.clone(),
+            predicate,
+        }
+    }
+}
+
+impl fmt::Display for DecodedInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "  [{}] {}", self.index, self.role)?;
+
+        match &self.input {
+            Input::CoinSigned {
+                utxo_id,
+                owner,
+                amount,
+                asset_id,
+                tx_pointer,
+                maturity,
+                ..
+            }
+            | Input::CoinPredicate {
+                utxo_id,
+                owner,
+                amount,
+                asset_id,
+                tx_pointer,
+                maturity,
+                ..
+            } => {
+                writeln!(f, "      utxo_id:    {utxo_id:#x}")?;
+                writeln!(f, "      owner:      {owner:#x}")?;
+                writeln!(f, "      amount:     {amount}")?;
+                writeln!(f, "      asset_id:   {asset_id:#x}")?;
+                writeln!(f, "      tx_pointer: {tx_pointer}")?;
+                writeln!(f, "      maturity:   {maturity}")?;
+            }
+
+            Input::Contract {
+                utxo_id,
+                balance_root,
+                state_root,
+                tx_pointer,
+                contract_id,
+            } => {
+                writeln!(f, "      contract_id:  {contract_id:#x}")?;
+                writeln!(f, "      utxo_id:      {utxo_id:#x}")?;
+                writeln!(f, "      balance_root: {balance_root:#x}")?;
+                writeln!(f, "      state_root:   {state_root:#x}")?;
+                writeln!(f, "      tx_pointer:   {tx_pointer}")?;
+            }
+
+            Input::MessageSigned {
+                message_id,
+                sender,
+                recipient,
+                amount,
+                nonce,
+                data,
+                ..
+            }
+            | Input::MessagePredicate {
+                message_id,
+                sender,
+                recipient,
+                amount,
+                nonce,
+                data,
+                ..
+            } => {
+                writeln!(f, "      message_id: {message_id:#x}")?;
+                writeln!(f, "      sender:     {sender:#x}")?;
+                writeln!(f, "      recipient:  {recipient:#x}")?;
+                writeln!(f, "      amount:     {amount}")?;
+                writeln!(f, "      nonce:      {nonce}")?;
+                writeln!(f, "      data:       0x{}", Hex(data))?;
+            }
+        }
+
+        if let Some(predicate_data) = self.input.input_predicate_data() {
+            writeln!(f, "      predicate_data: 0x{}", Hex(predicate_data))?;
+        }
+
+        if let Some(predicate) = &self.predicate {
+            writeln!(f, "      predicate:")?;
+            predicate.iter().try_for_each(|i| writeln!(f, "        {i}"))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An output of the transaction with its role.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DecodedOutput {
+    /// Index of the output in the transaction.
+    pub index: usize,
+    /// Short description of the role of the output.
+    pub role: String,
+    /// The output itself.
+    pub output: Output,
+}
+
+impl DecodedOutput {
+    fn new(index: usize, output: &Output) -> Self {
+        let role = match output {
+            Output::Coin { .. } => "coin".into(),
+            Output::Contract { input_index, .. } => format!("contract of input #{input_index}"),
+            Output::Message { .. } => "message".into(),
+            Output::Change { .. } => "change".into(),
+            Output::Variable { .. } => "variable".into(),
+            Output::ContractCreated { .. } => "contract created".into(),
+        };
+
+        Self {
+            index,
+            role,
+            output: *output,
+        }
+    }
+}
+
+impl fmt::Display for DecodedOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "  [{}] {}", self.index, self.role)?;
+
+        match &self.output {
+            Output::Coin { to, amount, asset_id }
+            | Output::Change { to, amount, asset_id }
+            | Output::Variable { to, amount, asset_id } => {
+                writeln!(f, "      to:       {to:#x}")?;
+                writeln!(f, "      amount:   {amount}")?;
+                writeln!(f, "      asset_id: {asset_id:#x}")?;
+            }
+
+            Output::Contract {
+                balance_root,
+                state_root,
+                ..
+            } => {
+                writeln!(f, "      balance_root: {balance_root:#x}")?;
+                writeln!(f, "      state_root:   {state_root:#x}")?;
+            }
+
+            Output::Message { recipient, amount } => {
+                writeln!(f, "      recipient: {recipient:#x}")?;
+                writeln!(f, "      amount:    {amount}")?;
+            }
+
+            Output::ContractCreated {
+                contract_id,
+                state_root,
+            } => {
+                writeln!(f, "      contract_id: {contract_id:#x}")?;
+                writeln!(f, "      state_root:  {state_root:#x}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Human readable representation of a [`Transaction`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DecodedTransaction {
+    /// The type of the transaction.
+    pub kind: TransactionRepr,
+    /// The computed id of the transaction.
+    pub id: Bytes32,
+    /// Gas price of the transaction; `None` for `Mint`.
+    pub gas_price: Option<Word>,
+    /// Gas limit of the transaction; `None` for `Mint`.
+    pub gas_limit: Option<Word>,
+    /// Maturity of the transaction; `None` for `Mint`.
+    pub maturity: Option<Word>,
+    /// Fees charged for the transaction; `None` for `Mint` or if the fee computation overflows.
+    pub fee: Option<TransactionFee>,
+    /// The disassembled script of a `Script` transaction.
+    pub script: Option<Vec<DecodedInstruction>>,
+    /// The script data of a `Script` transaction.
+    pub script_data: Option<Vec<u8>>,
+    /// Index of the bytecode witness of a `Create` transaction.
+    pub bytecode_witness_index: Option<u8>,
+    /// Salt of a `Create` transaction.
+    pub salt: Option<Salt>,
+    /// Storage slots of a `Create` transaction.
+    pub storage_slots: Vec<StorageSlot>,
+    /// Location of a `Mint` transaction in the block.
+    pub tx_pointer: Option<TxPointer>,
+    /// The decoded inputs.
+    pub inputs: Vec<DecodedInput>,
+    /// The decoded outputs.
+    pub outputs: Vec<DecodedOutput>,
+    /// The witnesses of the transaction.
+    pub witnesses: Vec<Witness>,
+}
+
+impl DecodedTransaction {
+    /// Decodes a transaction, computing its fees with the provided parameters.
+    pub fn new(tx: &Transaction, params: &ConsensusParameters) -> Self {
+        let mut decoded = Self {
+            kind: tx.into(),
+            id: tx.id(),
+            gas_price: None,
+            gas_limit: None,
+            maturity: None,
+            fee: None,
+            script: None,
+            script_data: None,
+            bytecode_witness_index: None,
+            salt: None,
+            storage_slots: vec![],
+            tx_pointer: None,
+            inputs: vec![],
+            outputs: vec![],
+            witnesses: vec![],
+        };
+
+        let (inputs, outputs, witnesses) = match tx {
+            Transaction::Script(script) => {
+                decoded.gas_price = Some(*script.gas_price());
+                decoded.gas_limit = Some(*script.gas_limit());
+                decoded.maturity = Some(*script.maturity());
+                decoded.fee = TransactionFee::checked_from_tx(params, script);
+                decoded.script = Some(DecodedInstruction::disassemble(script.script()));
+                decoded.script_data = Some(script.script_data().clone());
+
+                (
+                    script.inputs().as_slice(),
+                    script.outputs(),
+                    script.witnesses().as_slice(),
+                )
+            }
+
+            Transaction::Create(create) => {
+                decoded.gas_price = Some(*create.gas_price());
+                decoded.gas_limit = Some(*create.gas_limit());
+                decoded.maturity = Some(*create.maturity());
+                decoded.fee = TransactionFee::checked_from_tx(params, create);
+                decoded.bytecode_witness_index = Some(*create.bytecode_witness_index());
+                decoded.salt = Some(*create.salt());
+                decoded.storage_slots = create.storage_slots().clone();
+
+                (
+                    create.inputs().as_slice(),
+                    create.outputs(),
+                    create.witnesses().as_slice(),
+                )
+            }
+
+            Transaction::Mint(mint) => {
+                decoded.tx_pointer = Some(*mint.tx_pointer());
+
+                (&[][..], mint.outputs(), &[][..])
+            }
+        };
+
+        decoded.inputs = inputs
+            .iter()
+            .enumerate()
+            .map(|(index, input)| DecodedInput::new(index, input))
+            .collect();
+
+        decoded.outputs = outputs
+            .iter()
+            .enumerate()
+            .map(|(index, output)| DecodedOutput::new(index, output))
+            .collect();
+
+        decoded.witnesses = witnesses.to_vec();
+
+        decoded
+    }
+
+    /// Decodes a transaction from its canonical binary representation.
+    pub fn from_bytes(bytes: &[u8], params: &ConsensusParameters) -> io::Result<Self> {
+        Transaction::from_bytes(bytes).map(|tx| Self::new(&tx, params))
+    }
+
+    /// Decodes a transaction from the hex encoding of its canonical binary representation.
+    ///
+    /// The `0x` prefix and surrounding whitespace are optional.
+    pub fn from_hex<S>(hex: S, params: &ConsensusParameters) -> io::Result<Self>
+    where
+        S: AsRef<str>,
+    {
+        let hex = hex.as_ref().trim();
+        let hex = hex.strip_prefix("0x").unwrap_or(hex);
+
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, "The provided hex string is invalid!");
+
+        if hex.len() % 2 != 0 {
+            return Err(invalid());
+        }
+
+        let bytes = (0..hex.len())
+            .step_by(2)
+            .map(|i| hex.get(i..i + 2).and_then(|b| u8::from_str_radix(b, 16).ok()))
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(invalid)?;
+
+        Self::from_bytes(&bytes, params)
+    }
+
+    /// Convert the decoded transaction into a JSON string.
+    ///
+    /// If an error happens, a JSON string with the error description will be returned.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|e| format!(r#"{{"error": "{e}"}}"#))
+    }
+}
+
+impl fmt::Display for DecodedTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:?} transaction {:#x}", self.kind, self.id)?;
+
+        if let Some(gas_price) = self.gas_price {
+            writeln!(f, "gas_price: {gas_price}")?;
+        }
+
+        if let Some(gas_limit) = self.gas_limit {
+            writeln!(f, "gas_limit: {gas_limit}")?;
+        }
+
+        if let Some(maturity) = self.maturity {
+            writeln!(f, "maturity:  {maturity}")?;
+        }
+
+        if let Some(tx_pointer) = self.tx_pointer {
+            writeln!(f, "tx_pointer: {tx_pointer}")?;
+        }
+
+        match &self.fee {
+            Some(fee) => writeln!(
+                f,
+                "fee: bytes {}, total {} (min gas {}, max gas {})",
+                fee.bytes(),
+                fee.total(),
+                fee.min_gas(),
+                fee.max_gas()
+            )?,
+            None if self.gas_price.is_some() => writeln!(f, "fee: overflow")?,
+            None => (),
+        }
+
+        if let Some(salt) = &self.salt {
+            writeln!(f, "salt: {salt:#x}")?;
+        }
+
+        if let Some(index) = self.bytecode_witness_index {
+            writeln!(f, "bytecode witness: #{index}")?;
+        }
+
+        if let Some(script) = &self.script {
+            writeln!(f, "script ({} instructions):", script.len())?;
+            script.iter().try_for_each(|i| writeln!(f, "  {i}"))?;
+        }
+
+        if let Some(script_data) = &self.script_data {
+            writeln!(f, "script_data: 0x{}", Hex(script_data))?;
+        }
+
+        if !self.storage_slots.is_empty() {
+            writeln!(f, "storage slots ({}):", self.storage_slots.len())?;
+            self.storage_slots
+                .iter()
+                .try_for_each(|slot| writeln!(f, "  {:#x} = {:#x}", slot.key(), slot.value()))?;
+        }
+
+        writeln!(f, "inputs ({}):", self.inputs.len())?;
+        self.inputs.iter().try_for_each(|input| write!(f, "{input}"))?;
+
+        writeln!(f, "outputs ({}):", self.outputs.len())?;
+        self.outputs.iter().try_for_each(|output| write!(f, "{output}"))?;
+
+        writeln!(f, "witnesses ({}):", self.witnesses.len())?;
+        self.witnesses
+            .iter()
+            .enumerate()
+            .try_for_each(|(i, w)| writeln!(f, "  [{i}] 0x{}", Hex(w.as_ref())))
+    }
+}
+
+impl Transaction {
+    /// Decodes the transaction into its human readable representation.
+    pub fn decode(&self, params: &ConsensusParameters) -> DecodedTransaction {
+        DecodedTransaction::new(self, params)
+    }
+}
+
+/// Lower hex representation of a slice of bytes.
+struct Hex<'a>(&'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|b| write!(f, "{b:02x}"))
+    }
+}
+
+#[cfg(all(test, feature = "builder", feature = "random"))]
+mod tests {
+    use super::*;
+    use crate::TransactionBuilder;
+    use fuel_asm::op;
+    use fuel_crypto::SecretKey;
+    use fuel_types::bytes::SerializableVec;
+
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn sample_tx(rng: &mut StdRng) -> Transaction {
+        let script = [op::addi(0x10, 0x00, 0x10), op::ret(0x01)].into_iter().collect();
+        let predicate: Vec<u8> = [op::ret(0x01)].into_iter().collect();
+        let owner = Input::predicate_owner(&predicate);
+
+        TransactionBuilder::script(script, vec![0xde, 0xad])
+            .gas_price(1)
+            .gas_limit(100)
+            .add_unsigned_coin_input(SecretKey::random(rng), rng.gen(), 100, rng.gen(), rng.gen(), 0)
+            .add_input(Input::coin_predicate(
+                rng.gen(),
+                owner,
+                50,
+                rng.gen(),
+                rng.gen(),
+                0,
+                predicate,
+                vec![],
+            ))
+            .add_output(Output::change(rng.gen(), 0, rng.gen()))
+            .finalize_as_transaction()
+    }
+
+    #[test]
+    fn decodes_script_transaction() {
+        let rng = &mut StdRng::seed_from_u64(8586);
+        let params = ConsensusParameters::DEFAULT;
+        let tx = sample_tx(rng);
+
+        let decoded = tx.decode(&params);
+
+        assert_eq!(decoded.id, tx.id());
+        assert_eq!(
+            decoded.fee,
+            TransactionFee::checked_from_tx(&params, tx.as_script().unwrap())
+        );
+
+        let script = decoded.script.as_ref().expect("Script expected");
+        assert_eq!(2, script.len());
+        assert_eq!(Some("addi $r16 $zero 0x10".to_string()), script[0].instruction);
+        assert_eq!(4, script[1].offset);
+
+        assert_eq!(2, decoded.inputs.len());
+        assert!(decoded.inputs[0].predicate.is_none());
+        assert_eq!(1, decoded.inputs[1].predicate.as_ref().unwrap().len());
+        assert_eq!("change", decoded.outputs[0].role);
+
+        let text = decoded.to_string();
+        assert!(text.contains(&format!("{:#x}", tx.id())));
+        assert!(text.contains("coin signed by witness #0"));
+        assert!(text.contains("coin owned by a predicate"));
+    }
+
+    #[test]
+    fn decodes_from_hex() {
+        let rng = &mut StdRng::seed_from_u64(8586);
+        let params = ConsensusParameters::DEFAULT;
+        let mut tx = sample_tx(rng);
+
+        let hex: String = tx.to_bytes().iter().map(|b| format!("{b:02x}")).collect();
+
+        let decoded = DecodedTransaction::from_hex(format!(" 0x{hex}\n"), &params).expect("Failed to decode");
+        assert_eq!(tx.decode(&params), decoded);
+
+        DecodedTransaction::from_hex("0xabc", &params).expect_err("Odd length should fail");
+        DecodedTransaction::from_hex("zz", &params).expect_err("Invalid hex should fail");
+    }
+
+    #[test]
+    fn disassemble_reports_invalid_opcodes() {
+        let decoded = DecodedInstruction::disassemble(&[0x00, 0x00, 0x00, 0x00, 0x24, 0x04, 0x00, 0x01]);
+
+        assert_eq!(2, decoded.len());
+        assert_eq!(None, decoded[0].instruction);
+        assert_eq!(Some("ret $one".to_string()), decoded[1].instruction);
+        assert_eq!("000000: 00000000  <invalid opcode 0x00>", decoded[0].to_string());
+    }
+
+    #[test]
+    fn disassemble_keeps_trailing_bytes_raw() {
+        let decoded = DecodedInstruction::disassemble(&[0x24, 0x04, 0x00, 0x01, 0x24, 0x04]);
+
+        assert_eq!(3, decoded.len());
+        assert_eq!(Some("ret $one".to_string()), decoded[0].instruction);
+        assert_eq!(
+            (4, 0x24, 1, None),
+            (
+                decoded[1].offset,
+                decoded[1].raw,
+                decoded[1].size,
+                decoded[1].instruction.clone()
+            )
+        );
+        assert_eq!((5, 0x04), (decoded[2].offset, decoded[2].raw));
+        assert_eq!("000004: 24        .byte 0x24", decoded[1].to_string());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn decoded_transaction_to_json() {
+        let rng = &mut StdRng::seed_from_u64(8586);
+        let tx = sample_tx(rng);
+
+        let json = tx.decode(&ConsensusParameters::DEFAULT).to_json();
+        let decoded: DecodedTransaction = serde_json::from_str(&json).expect("Failed to parse json");
+
+        assert_eq!(tx.decode(&ConsensusParameters::DEFAULT), decoded);
+    }
+}
@@ -0,0 +1,485 @@
+//! Multi-party signing of a transaction.
+//!
+//! A [`PartiallySigned`] transaction wraps an unsigned (or partly signed) transaction and
+//! describes the signatures it still requires as a set of [`SignatureSlot`]s. It can be
+//! serialized, handed to the owners of the inputs, signed by each of them independently and
+//! merged back before being finalized.
+
+use crate::transaction::{field, Cacheable, CheckError, FormatValidityChecks, UniqueIdentifier};
+use crate::{Input, Witness};
+
+use fuel_crypto::{Message, PublicKey, SecretKey, Signature};
+use fuel_types::bytes::{Deserializable, SerializableVec};
+use fuel_types::{Address, Bytes32};
+use itertools::Itertools;
+
+use core::fmt;
+use std::{error, io};
+
+/// A witness that must contain the signature of `owner` over the transaction id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SignatureSlot {
+    witness_index: u8,
+    owner: Address,
+}
+
+impl SignatureSlot {
+    /// Index of the witness that will hold the signature.
+    pub const fn witness_index(&self) -> u8 {
+        self.witness_index
+    }
+
+    /// Owner of the inputs signed by this slot.
+    pub const fn owner(&self) -> &Address {
+        &self.owner
+    }
+}
+
+/// Errors produced while assembling a [`PartiallySigned`] transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum PartialSignError {
+    /// The merged transactions differ in fields other than their signatures.
+    TransactionMismatch {
+        /// Id of the local transaction.
+        expected: Bytes32,
+        /// Id of the merged transaction.
+        found: Bytes32,
+    },
+    /// The merged transactions have a different number of witnesses.
+    WitnessCountMismatch {
+        /// Number of local witnesses.
+        expected: usize,
+        /// Number of merged witnesses.
+        found: usize,
+    },
+    /// Both parties provided a different value for the same witness.
+    ConflictingWitness {
+        /// Index of the conflicting witness.
+        witness_index: u8,
+    },
+    /// No signature slot exists for the witness index.
+    UnknownSlot {
+        /// Index of the witness.
+        witness_index: u8,
+    },
+    /// The signature doesn't belong to the owner of the slot.
+    InvalidSignature {
+        /// Index of the witness.
+        witness_index: u8,
+    },
+    /// The slot is not signed yet.
+    MissingSignature {
+        /// Index of the witness.
+        witness_index: u8,
+    },
+    /// The assembled transaction didn't pass the signature checks.
+    Check(CheckError),
+}
+
+impl fmt::Display for PartialSignError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl error::Error for PartialSignError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Check(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<CheckError> for PartialSignError {
+    fn from(e: CheckError) -> Self {
+        Self::Check(e)
+    }
+}
+
+/// A transaction that is being signed by several parties.
+///
+/// The signature slots are derived from the `CoinSigned` and `MessageSigned` inputs of the
+/// transaction, so the serialized form is the transaction itself. The fields of the transaction
+/// can't be modified through this type; only the witnesses of the slots are filled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PartiallySigned<Tx> {
+    tx: Tx,
+}
+
+impl<Tx> PartiallySigned<Tx>
+where
+    Tx: UniqueIdentifier + Cacheable + FormatValidityChecks + field::Inputs + field::Witnesses,
+{
+    /// Wraps the transaction, usually produced by
+    /// [`crate::Finalizable::finalize_without_signature`].
+    pub fn new(mut tx: Tx) -> Self {
+        tx.precompute();
+
+        Self { tx }
+    }
+
+    /// Returns the inner transaction.
+    pub const fn transaction(&self) -> &Tx {
+        &self.tx
+    }
+
+    /// Id of the transaction that must be signed by every slot.
+    pub fn id(&self) -> Bytes32 {
+        self.tx.id()
+    }
+
+    /// All signature slots of the transaction, ordered by witness index.
+    pub fn slots(&self) -> Vec<SignatureSlot> {
+        self.tx
+            .inputs()
+            .iter()
+            .filter_map(|input| match input {
+                Input::CoinSigned {
+                    witness_index, owner, ..
+                }
+                | Input::MessageSigned {
+                    witness_index,
+                    recipient: owner,
+                    ..
+                } => Some(SignatureSlot {
+                    witness_index: *witness_index,
+                    owner: *owner,
+                }),
+                _ => None,
+            })
+            .sorted()
+            .dedup()
+            .collect()
+    }
+
+    /// Signature slots that don't contain a signature yet.
+    pub fn unsigned_slots(&self) -> Vec<SignatureSlot> {
+        self.slots()
+            .into_iter()
+            .filter(|slot| !self.is_filled(slot.witness_index))
+            .collect()
+    }
+
+    /// Returns `true` if every signature slot contains a signature.
+    pub fn is_complete(&self) -> bool {
+        self.unsigned_slots().is_empty()
+    }
+
+    /// Signs every unsigned slot owned by `secret`, returning the number of signed slots.
+    pub fn sign(&mut self, secret: &SecretKey) -> usize {
+        let owner = Input::owner(&PublicKey::from(secret));
+        let slots = self
+            .unsigned_slots()
+            .into_iter()
+            .filter(|slot| slot.owner == owner)
+            .filter(|slot| (slot.witness_index as usize) < self.tx.witnesses().len())
+            .collect_vec();
+
+        if slots.is_empty() {
+            return 0;
+        }
+
+        let id = self.id();
+
+        // Safety: checked length
+        let message = unsafe { Message::as_ref_unchecked(id.as_ref()) };
+        let signature = Signature::sign(secret, message);
+
+        for slot in slots.iter() {
+            self.tx.witnesses_mut()[slot.witness_index as usize] = signature.as_ref().into();
+        }
+
+        slots.len()
+    }
+
+    /// Sets the signature of a slot produced by an external signer.
+    ///
+    /// The signature is verified against the owner of the slot.
+    pub fn add_signature(&mut self, witness_index: u8, signature: Signature) -> Result<(), PartialSignError> {
+        self.verify_slot_signature(witness_index, signature.as_ref())?;
+
+        let witness = self
+            .tx
+            .witnesses_mut()
+            .get_mut(witness_index as usize)
+            .ok_or(PartialSignError::UnknownSlot { witness_index })?;
+
+        match witness.as_ref() {
+            [] => *witness = signature.as_ref().into(),
+            w if w == signature.as_ref() => (),
+            _ => return Err(PartialSignError::ConflictingWitness { witness_index }),
+        }
+
+        Ok(())
+    }
+
+    /// Merges the signatures collected by another party into `self`.
+    ///
+    /// Both transactions must be identical except for the witnesses of their signature slots.
+    /// The merge is atomic: in case of error `self` is not modified.
+    pub fn merge(&mut self, other: &Self) -> Result<(), PartialSignError> {
+        let (expected, found) = (self.id(), other.id());
+        if expected != found {
+            return Err(PartialSignError::TransactionMismatch { expected, found });
+        }
+
+        let (ours, theirs) = (self.tx.witnesses(), other.tx.witnesses());
+        if ours.len() != theirs.len() {
+            return Err(PartialSignError::WitnessCountMismatch {
+                expected: ours.len(),
+                found: theirs.len(),
+            });
+        }
+
+        let slots = self.slots();
+        let updates = ours
+            .iter()
+            .zip(theirs.iter())
+            .enumerate()
+            .filter(|(_, (ours, theirs))| ours != theirs)
+            .filter_map(|(index, (ours, theirs))| {
+                let witness_index = index as u8;
+                let is_slot = slots.iter().any(|slot| slot.witness_index == witness_index);
+
+                match (ours.as_ref().is_empty(), theirs.as_ref().is_empty()) {
+                    // The other party didn't sign this slot yet
+                    (false, true) if is_slot => None,
+                    (true, false) if is_slot => Some(
+                        self.verify_slot_signature(witness_index, theirs.as_ref())
+                            .map(|_| (index, theirs.clone())),
+                    ),
+                    _ => Some(Err(PartialSignError::ConflictingWitness { witness_index })),
+                }
+            })
+            .collect::<Result<Vec<(usize, Witness)>, _>>()?;
+
+        for (index, witness) in updates {
+            self.tx.witnesses_mut()[index] = witness;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the signed transaction if all slots are filled with valid signatures.
+    pub fn finalize(self) -> Result<Tx, PartialSignError> {
+        if let Some(slot) = self.unsigned_slots().first() {
+            return Err(PartialSignError::MissingSignature {
+                witness_index: slot.witness_index,
+            });
+        }
+
+        self.tx.check_signatures()?;
+
+        Ok(self.tx)
+    }
+
+    /// Serializes the transaction to be sent to another party.
+    pub fn to_bytes(&self) -> Vec<u8>
+    where
+        Tx: SerializableVec + Clone,
+    {
+        self.tx.clone().to_bytes()
+    }
+
+    /// Restores a transaction serialized with [`PartiallySigned::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self>
+    where
+        Tx: Deserializable,
+    {
+        Tx::from_bytes(bytes).map(Self::new)
+    }
+
+    fn is_filled(&self, witness_index: u8) -> bool {
+        self.tx
+            .witnesses()
+            .get(witness_index as usize)
+            .map(|w| !w.as_ref().is_empty())
+            .unwrap_or(false)
+    }
+
+    fn verify_slot_signature(&self, witness_index: u8, signature: &[u8]) -> Result<(), PartialSignError> {
+        let owners = self
+            .slots()
+            .into_iter()
+            .filter(|slot| slot.witness_index == witness_index)
+            .map(|slot| slot.owner)
+            .collect_vec();
+
+        if owners.is_empty() {
+            return Err(PartialSignError::UnknownSlot { witness_index });
+        }
+
+        if signature.len() != Signature::LEN {
+            return Err(PartialSignError::InvalidSignature { witness_index });
+        }
+
+        let id = self.id();
+
+        // Safety: checked length
+        let signature = unsafe { Signature::as_ref_unchecked(signature) };
+
+        // Safety: checked length
+        let message = unsafe { Message::as_ref_unchecked(id.as_ref()) };
+
+        let recovered = signature
+            .recover(message)
+            .map(|pk| Input::owner(&pk))
+            .map_err(|_| PartialSignError::InvalidSignature { witness_index })?;
+
+        if owners.iter().any(|owner| owner != &recovered) {
+            return Err(PartialSignError::InvalidSignature { witness_index });
+        }
+
+        Ok(())
+    }
+}
+
+impl<Tx> From<Tx> for PartiallySigned<Tx>
+where
+    Tx: UniqueIdentifier + Cacheable + FormatValidityChecks + field::Inputs + field::Witnesses,
+{
+    fn from(tx: Tx) -> Self {
+        Self::new(tx)
+    }
+}
+
+#[cfg(all(test, feature = "builder", feature = "random"))]
+mod tests {
+    use super::*;
+    use crate::field::Witnesses;
+    use crate::{Buildable, Executable, Finalizable, Script, TransactionBuilder};
+
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn two_party_tx(rng: &mut StdRng) -> (Script, SecretKey, SecretKey) {
+        let alice = SecretKey::random(rng);
+        let bob = SecretKey::random(rng);
+
+        let mut tx = TransactionBuilder::script(vec![], vec![]).finalize_without_signature();
+        tx.add_unsigned_coin_input(rng.gen(), &alice.public_key(), 10, rng.gen(), rng.gen(), 0);
+        tx.add_unsigned_coin_input(rng.gen(), &bob.public_key(), 20, rng.gen(), rng.gen(), 0);
+        tx.add_unsigned_message_input(rng.gen(), Input::owner(&alice.public_key()), 0, 30, vec![]);
+
+        (tx, alice, bob)
+    }
+
+    #[test]
+    fn parties_sign_independently_and_merge() {
+        let rng = &mut StdRng::seed_from_u64(8586);
+        let (tx, alice, bob) = two_party_tx(rng);
+
+        let psbt = PartiallySigned::new(tx);
+        assert_eq!(3, psbt.slots().len());
+        assert!(!psbt.is_complete());
+
+        let bytes = psbt.to_bytes();
+
+        let mut alice_copy = PartiallySigned::<Script>::from_bytes(&bytes).unwrap();
+        assert_eq!(2, alice_copy.sign(&alice));
+
+        let mut bob_copy = PartiallySigned::<Script>::from_bytes(&bytes).unwrap();
+        assert_eq!(1, bob_copy.sign(&bob));
+        assert_eq!(
+            Err(PartialSignError::MissingSignature { witness_index: 0 }),
+            bob_copy.clone().finalize()
+        );
+
+        alice_copy.merge(&bob_copy).expect("Failed to merge");
+        assert!(alice_copy.is_complete());
+
+        let signed = alice_copy.finalize().expect("Failed to finalize");
+        signed.check_signatures().expect("Invalid signatures");
+    }
+
+    #[test]
+    fn builder_signs_own_slots() {
+        let rng = &mut StdRng::seed_from_u64(8586);
+        let alice = SecretKey::random(rng);
+        let bob = SecretKey::random(rng);
+        let bob_owner = Input::owner(&bob.public_key());
+
+        let mut psbt = TransactionBuilder::script(vec![], vec![])
+            .add_unsigned_coin_input(alice, rng.gen(), 10, rng.gen(), rng.gen(), 0)
+            .add_input(Input::coin_signed(rng.gen(), bob_owner, 20, rng.gen(), rng.gen(), 1, 0))
+            .add_witness(Witness::default())
+            .finalize_partially_signed();
+
+        let unsigned = psbt.unsigned_slots();
+        assert_eq!(1, unsigned.len());
+        assert_eq!(1, unsigned[0].witness_index());
+        assert_eq!(&bob_owner, unsigned[0].owner());
+
+        assert_eq!(0, psbt.sign(&alice));
+        assert_eq!(1, psbt.sign(&bob));
+        psbt.finalize().expect("Failed to finalize");
+    }
+
+    #[test]
+    fn add_signature_from_external_signer() {
+        let rng = &mut StdRng::seed_from_u64(8586);
+        let (tx, alice, bob) = two_party_tx(rng);
+
+        let mut psbt = PartiallySigned::new(tx);
+        let id = psbt.id();
+        let message = unsafe { Message::as_ref_unchecked(id.as_ref()) };
+
+        let signature = Signature::sign(&bob, message);
+        assert_eq!(
+            Err(PartialSignError::InvalidSignature { witness_index: 0 }),
+            psbt.add_signature(0, signature)
+        );
+        psbt.add_signature(1, signature).expect("Failed to add signature");
+
+        let signature = Signature::sign(&alice, message);
+        psbt.add_signature(0, signature).expect("Failed to add signature");
+        psbt.add_signature(2, signature).expect("Failed to add signature");
+
+        psbt.finalize().expect("Failed to finalize");
+    }
+
+    #[test]
+    fn merge_detects_conflicts() {
+        let rng = &mut StdRng::seed_from_u64(8586);
+        let (tx, alice, bob) = two_party_tx(rng);
+
+        let mut ours = PartiallySigned::new(tx.clone());
+        ours.sign(&alice);
+
+        // The other party changed the gas price
+        let mut changed = tx.clone();
+        changed.set_gas_price(100);
+        let mut theirs = PartiallySigned::new(changed);
+        theirs.sign(&bob);
+
+        let err = ours.merge(&theirs).expect_err("Expected conflict");
+        assert!(matches!(err, PartialSignError::TransactionMismatch { .. }));
+
+        // The other party forged a signature for the slot of bob
+        let mut theirs = PartiallySigned::new(tx.clone());
+        theirs.tx.witnesses_mut()[1] =
+            Signature::sign(&alice, unsafe { Message::as_ref_unchecked(theirs.id().as_ref()) })
+                .as_ref()
+                .into();
+
+        assert_eq!(
+            Err(PartialSignError::InvalidSignature { witness_index: 1 }),
+            ours.merge(&theirs)
+        );
+
+        // The other party replaced an existing signature
+        let mut theirs = PartiallySigned::new(tx);
+        theirs.tx.witnesses_mut()[0] = vec![0xaa; Signature::LEN].into();
+
+        let before = ours.clone();
+        assert_eq!(
+            Err(PartialSignError::ConflictingWitness { witness_index: 0 }),
+            ours.merge(&theirs)
+        );
+        assert_eq!(before, ours);
+    }
+}
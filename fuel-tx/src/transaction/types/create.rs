@@ -14,6 +14,9 @@ use fuel_types::{bytes, AssetId, Salt, Word};
 #[cfg(feature = "std")]
 use std::io;
 
+#[cfg(feature = "std")]
+use crate::transaction::validity::check_signatures_batched;
+
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
@@ -90,6 +93,15 @@ impl FormatValidityChecks for Create {
         Ok(())
     }
 
+    #[cfg(feature = "std")]
+    fn check_signatures_batched(&self, threads: usize) -> Result<(), CheckError> {
+        use crate::UniqueIdentifier;
+
+        let id = self.id();
+
+        check_signatures_batched(self.inputs(), &self.witnesses, &id, threads)
+    }
+
     fn check_without_signatures(&self, block_height: Word, parameters: &ConsensusParameters) -> Result<(), CheckError> {
         check_common_part(self, block_height, parameters)?;
 
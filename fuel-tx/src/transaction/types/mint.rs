@@ -94,6 +94,11 @@ impl FormatValidityChecks for Mint {
         Ok(())
     }
 
+    #[cfg(feature = "std")]
+    fn check_signatures_batched(&self, _threads: usize) -> Result<(), CheckError> {
+        Ok(())
+    }
+
     fn check_without_signatures(&self, block_height: Word, parameters: &ConsensusParameters) -> Result<(), CheckError> {
         if self.outputs().len() > parameters.max_outputs as usize {
             return Err(CheckError::TransactionOutputsMax);
@@ -175,6 +175,20 @@ pub trait FormatValidityChecks {
     /// Validates that all required signatures are set in the transaction and that they are valid.
     fn check_signatures(&self) -> Result<(), CheckError>;
 
+    #[cfg(feature = "std")]
+    /// Same as [`FormatValidityChecks::check_signatures`], but recovers the public key of every
+    /// distinct witness only once, even if it is shared by several inputs. The recovery is split
+    /// across up to `threads` threads; `0` and `1` both run on the calling thread.
+    ///
+    /// The first reported error is the same as the one of [`FormatValidityChecks::check_signatures`].
+    ///
+    /// The default implementation falls back to [`FormatValidityChecks::check_signatures`].
+    fn check_signatures_batched(&self, threads: usize) -> Result<(), CheckError> {
+        let _ = threads;
+
+        self.check_signatures()
+    }
+
     /// Validates the transactions according to rules from the specification:
     /// https://github.com/FuelLabs/fuel-specs/blob/master/src/protocol/tx_format/transaction.md#transaction
     fn check_without_signatures(&self, block_height: Word, parameters: &ConsensusParameters) -> Result<(), CheckError>;
@@ -190,6 +204,15 @@ impl FormatValidityChecks for Transaction {
         }
     }
 
+    #[cfg(feature = "std")]
+    fn check_signatures_batched(&self, threads: usize) -> Result<(), CheckError> {
+        match self {
+            Transaction::Script(script) => script.check_signatures_batched(threads),
+            Transaction::Create(create) => create.check_signatures_batched(threads),
+            Transaction::Mint(mint) => mint.check_signatures_batched(threads),
+        }
+    }
+
     fn check_without_signatures(&self, block_height: Word, parameters: &ConsensusParameters) -> Result<(), CheckError> {
         match self {
             Transaction::Script(script) => script.check_without_signatures(block_height, parameters),
@@ -199,6 +222,89 @@ impl FormatValidityChecks for Transaction {
     }
 }
 
+/// Checks the signatures of `inputs` against `txhash`, recovering every distinct witness once.
+#[cfg(feature = "std")]
+pub(crate) fn check_signatures_batched(
+    inputs: &[Input],
+    witnesses: &[Witness],
+    txhash: &Bytes32,
+    threads: usize,
+) -> Result<(), CheckError> {
+    use fuel_types::Address;
+    use std::collections::HashMap;
+
+    // Safety: checked length
+    let message = unsafe { Message::as_ref_unchecked(txhash.as_ref()) };
+
+    let recover = |witness_index: u8| -> (u8, Option<Address>) {
+        let owner = witnesses
+            .get(witness_index as usize)
+            .map(Witness::as_ref)
+            .filter(|witness| witness.len() == Signature::LEN)
+            .and_then(|witness| {
+                // Safety: checked length
+                let signature = unsafe { Signature::as_ref_unchecked(witness) };
+
+                signature.recover(message).ok()
+            })
+            .map(|pk| Input::owner(&pk));
+
+        (witness_index, owner)
+    };
+
+    let distinct = inputs
+        .iter()
+        .filter_map(|input| match input {
+            Input::CoinSigned { witness_index, .. } | Input::MessageSigned { witness_index, .. } => {
+                Some(*witness_index)
+            }
+            _ => None,
+        })
+        .unique()
+        .collect_vec();
+
+    let threads = threads.clamp(1, distinct.len().max(1));
+    let recovered: HashMap<u8, Option<Address>> = if threads == 1 {
+        distinct.into_iter().map(recover).collect()
+    } else {
+        let chunk_size = num_integer::div_ceil(distinct.len(), threads);
+
+        std::thread::scope(|scope| {
+            let handles = distinct
+                .chunks(chunk_size)
+                .map(|chunk| scope.spawn(|| chunk.iter().copied().map(recover).collect_vec()))
+                .collect_vec();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("signature recovery doesn't panic"))
+                .collect()
+        })
+    };
+
+    inputs.iter().enumerate().try_for_each(|(index, input)| match input {
+        Input::CoinSigned {
+            witness_index, owner, ..
+        }
+        | Input::MessageSigned {
+            witness_index,
+            recipient: owner,
+            ..
+        } => {
+            if *witness_index as usize >= witnesses.len() {
+                return Err(CheckError::InputWitnessIndexBounds { index });
+            }
+
+            match recovered.get(witness_index) {
+                Some(Some(recovered)) if recovered == owner => Ok(()),
+                _ => Err(CheckError::InputInvalidSignature { index }),
+            }
+        }
+
+        _ => input.check_signature(index, txhash, witnesses),
+    })
+}
+
 pub(crate) fn check_common_part<T>(
     tx: &T,
     block_height: Word,
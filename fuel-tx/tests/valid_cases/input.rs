@@ -101,6 +101,61 @@ fn coin_signed() {
     assert_eq!(CheckError::InputWitnessIndexBounds { index: 0 }, err);
 }
 
+#[test]
+fn coin_signed_batched() {
+    use fuel_tx::field::{Inputs, Witnesses};
+
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    let a = SecretKey::random(rng);
+    let b = SecretKey::random(rng);
+
+    let mut builder = TransactionBuilder::script(vec![], vec![]);
+    for i in 0..16 {
+        let secret = if i % 3 == 0 { b } else { a };
+        builder.add_unsigned_coin_input(secret, rng.gen(), rng.gen(), rng.gen(), rng.gen(), 0);
+    }
+    builder.add_unsigned_message_input(a, rng.gen(), rng.gen(), rng.gen(), vec![]);
+    let tx = builder.finalize();
+
+    for threads in [0, 1, 2, 8] {
+        assert_eq!(Ok(()), tx.check_signatures_batched(threads));
+    }
+
+    // Replace the owner of one of the inputs sharing a witness
+    let mut invalid_owner = tx.clone();
+    if let Input::CoinSigned { owner, .. } = &mut invalid_owner.inputs_mut()[4] {
+        *owner = rng.gen();
+    }
+
+    // Corrupt a witness shared by several inputs
+    let mut invalid_witness = tx.clone();
+    invalid_witness.witnesses_mut()[1] = rng.gen::<Bytes64>().to_vec().into();
+
+    // Truncate a witness
+    let mut invalid_witness_len = tx.clone();
+    invalid_witness_len.witnesses_mut()[0] = vec![0xaa; 32].into();
+
+    // Point an input to a witness that doesn't exist
+    let mut invalid_witness_index = tx;
+    if let Input::CoinSigned { witness_index, .. } = &mut invalid_witness_index.inputs_mut()[7] {
+        *witness_index = 0xff;
+    }
+
+    for tx in [
+        invalid_owner,
+        invalid_witness,
+        invalid_witness_len,
+        invalid_witness_index,
+    ] {
+        let expected = tx.check_signatures().expect_err("Expected failure");
+
+        for threads in [0, 1, 2, 8] {
+            assert_eq!(Err(expected.clone()), tx.check_signatures_batched(threads));
+        }
+    }
+}
+
 #[test]
 fn coin_predicate() {
     let rng = &mut StdRng::seed_from_u64(8586);
@@ -27,7 +27,7 @@ impl Backtrace {
     pub fn from_vm_error<S, Tx>(vm: &Interpreter<S, Tx>, result: ScriptExecutionResult) -> Self {
         let call_stack = vm.call_stack().to_owned();
         let contract = vm.internal_contract_or_default();
-        let memory = vm.memory().to_owned();
+        let memory = vm.memory().to_vec();
         let initial_balances = vm.initial_balances().clone();
         let mut registers = [0; VM_REGISTER_COUNT];
 
@@ -21,6 +21,9 @@ pub struct Call {
 }
 
 impl Call {
+    /// Size of the serialized call structure.
+    pub const LEN: usize = ContractId::LEN + 2 * WORD_SIZE;
+
     /// Create a new call structure representation.
     pub const fn new(to: ContractId, a: Word, b: Word) -> Self {
         Self { to, a, b }
@@ -49,7 +52,7 @@ impl Call {
 
 impl SizedBytes for Call {
     fn serialized_size(&self) -> usize {
-        ContractId::LEN + 2 * WORD_SIZE
+        Self::LEN
     }
 }
 
@@ -98,6 +98,18 @@ impl<Tx: IntoChecked> Checked<Tx> {
         }
         Ok(self)
     }
+
+    /// Performs check of signatures, if not yet done, recovering every distinct witness only
+    /// once and splitting the recovery across up to `threads` threads.
+    ///
+    /// Produces the same result as [`Checked::check_signatures`].
+    pub fn check_signatures_batched(mut self, threads: usize) -> Result<Self, CheckError> {
+        if !self.checks_bitmask.contains(Checks::Signatures) {
+            self.transaction.check_signatures_batched(threads)?;
+            self.checks_bitmask.insert(Checks::Signatures);
+        }
+        Ok(self)
+    }
 }
 
 #[cfg(feature = "test-helpers")]
@@ -719,6 +731,44 @@ mod tests {
         assert!(checked.checks().contains(Checks::Basic | Checks::Signatures));
     }
 
+    #[test]
+    fn batched_signatures_check_marks_signatures_flag() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let block_height = 1;
+        let params = ConsensusParameters::default();
+
+        let tx = valid_coin_tx(&mut rng, 1, 100000, 1000000, 10);
+        let checked = tx
+            // Sets Checks::Basic
+            .into_checked_basic(block_height, &params)
+            .unwrap()
+            // Sets Checks::Signatures
+            .check_signatures_batched(4)
+            .unwrap();
+
+        assert!(checked.checks().contains(Checks::Basic | Checks::Signatures));
+    }
+
+    #[test]
+    fn batched_signatures_check_rejects_invalid_signature() {
+        use fuel_tx::field::Witnesses;
+
+        let mut rng = StdRng::seed_from_u64(1);
+        let block_height = 1;
+        let params = ConsensusParameters::default();
+
+        let mut tx = valid_coin_tx(&mut rng, 1, 100000, 1000000, 10);
+        tx.witnesses_mut()[0] = vec![0u8; 64].into();
+
+        let err = tx
+            .into_checked_basic(block_height, &params)
+            .unwrap()
+            .check_signatures_batched(4)
+            .expect_err("Expected invalid signature");
+
+        assert_eq!(CheckError::InputInvalidSignature { index: 0 }, err);
+    }
+
     #[test]
     fn predicates_check_marks_predicate_flag() {
         let mut rng = StdRng::seed_from_u64(1);
@@ -21,6 +21,9 @@ pub const VM_MAX_RAM: u64 = 1024 * 1024 * FUEL_MAX_MEMORY_SIZE;
 /// Maximum memory access size, in bytes.
 pub const MEM_MAX_ACCESS_SIZE: u64 = VM_MAX_RAM;
 
+/// Granularity of the VM memory dirty tracking, in bytes.
+pub const VM_MEMORY_PAGE_SIZE: usize = 4096;
+
 /// Tighter of the two bounds for VM_MAX_RAM and usize::MAX
 pub const MIN_VM_MAX_RAM_USIZE_MAX: u64 = if VM_MAX_RAM < usize::MAX as u64 {
     VM_MAX_RAM
@@ -1,7 +1,9 @@
 //! Crypto implementations for the instructions
 
-use fuel_merkle::binary::in_memory::MerkleTree;
-use fuel_types::Bytes32;
+use fuel_merkle::binary::{self, in_memory::MerkleTree};
+use fuel_tx::{Output, Receipt};
+use fuel_types::bytes::SerializableVec;
+use fuel_types::{Bytes32, MessageId};
 
 /// Calculate a binary merkle root with in-memory storage
 pub fn ephemeral_merkle_root<L, I>(leaves: I) -> Bytes32
@@ -14,6 +16,140 @@ where
     tree.root().into()
 }
 
+/// Calculate a binary merkle root with in-memory storage, along with the proof set of the leaf at
+/// `index`.
+///
+/// The proof set contains the leaf hash, followed by the side nodes from the leaf to the root.
+/// Returns `None` if `index` is out of bounds.
+pub fn ephemeral_merkle_prove<L, I>(leaves: I, index: u64) -> Option<(Bytes32, Vec<Bytes32>)>
+where
+    L: AsRef<[u8]>,
+    I: Iterator<Item = L> + ExactSizeIterator,
+{
+    let mut tree = MerkleTree::new();
+    leaves.for_each(|l| tree.push(l.as_ref()));
+    tree.prove(index)
+        .map(|(root, proof_set)| (root.into(), proof_set.into_iter().map(Bytes32::from).collect()))
+}
+
+/// Verify a proof set produced by [`ephemeral_merkle_prove`] for the leaf `data` at `index`, out of
+/// `leaves_count` leaves.
+pub fn verify_merkle_proof<L>(root: &Bytes32, data: &L, proof_set: &[Bytes32], index: u64, leaves_count: u64) -> bool
+where
+    L: AsRef<[u8]>,
+{
+    let proof_set = proof_set.iter().map(|node| **node).collect();
+
+    binary::verify(root, data, &proof_set, index, leaves_count)
+}
+
+/// Calculate the receipts root of a transaction, along with the inclusion proof set of the
+/// receipt at `index`.
+///
+/// The root is the one set as receipts root of a script transaction after its execution.
+pub fn receipt_proof(receipts: &[Receipt], index: usize) -> Option<(Bytes32, Vec<Bytes32>)> {
+    ephemeral_merkle_prove(receipts.iter().map(|r| r.clone().to_bytes()), index as u64)
+}
+
+/// Verify that `receipt` is the receipt at `index` of a transaction with `receipts_count`
+/// receipts and receipts root `root`.
+pub fn verify_receipt_proof(
+    root: &Bytes32,
+    receipt: &Receipt,
+    proof_set: &[Bytes32],
+    index: usize,
+    receipts_count: usize,
+) -> bool {
+    verify_merkle_proof(
+        root,
+        &receipt.clone().to_bytes(),
+        proof_set,
+        index as u64,
+        receipts_count as u64,
+    )
+}
+
+/// Inclusion proof of a `MessageOut` receipt in the message outputs tree of a block.
+///
+/// The leaves of the tree are the message ids of the `MessageOut` receipts of the block, in
+/// execution order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MessageProof {
+    /// Root of the message outputs tree.
+    pub root: Bytes32,
+    /// Message id hash, followed by the side nodes from the message to the root.
+    pub proof_set: Vec<Bytes32>,
+    /// Index of the message in the tree.
+    pub index: u64,
+    /// Number of messages in the tree.
+    pub messages_count: u64,
+}
+
+fn message_ids<'a, I>(receipts: I) -> Vec<MessageId>
+where
+    I: IntoIterator<Item = &'a Receipt>,
+{
+    receipts.into_iter().filter_map(Receipt::message_id).copied().collect()
+}
+
+/// Calculate the root of the message outputs tree over the receipts of a block.
+///
+/// `receipts` are the receipts of every transaction of the block, in execution order. Only
+/// `MessageOut` receipts are part of the tree.
+pub fn message_outs_root<'a, I>(receipts: I) -> Bytes32
+where
+    I: IntoIterator<Item = &'a Receipt>,
+{
+    ephemeral_merkle_root(message_ids(receipts).iter())
+}
+
+/// Produce the inclusion proof of the message `message_id` in the message outputs tree over the
+/// receipts of a block.
+///
+/// Returns `None` if no `MessageOut` receipt of the block has this id.
+pub fn message_out_proof<'a, I>(receipts: I, message_id: &MessageId) -> Option<MessageProof>
+where
+    I: IntoIterator<Item = &'a Receipt>,
+{
+    let ids = message_ids(receipts);
+    let index = ids.iter().position(|id| id == message_id)? as u64;
+    let (root, proof_set) = ephemeral_merkle_prove(ids.iter(), index)?;
+
+    Some(MessageProof {
+        root,
+        proof_set,
+        index,
+        messages_count: ids.len() as u64,
+    })
+}
+
+/// Verify that a `MessageOut` receipt is included in the message outputs tree of root `root`.
+///
+/// `root` must come from a trusted source, e.g. the header of the block; the root carried by
+/// `proof` has to match it. The message id and data digest are recomputed from the receipt
+/// fields, so a receipt with altered sender, recipient, nonce, amount or data is rejected.
+pub fn verify_message_out_proof(root: &Bytes32, receipt: &Receipt, proof: &MessageProof) -> bool {
+    let (message_id, sender, recipient, amount, nonce, digest, data) = match receipt {
+        Receipt::MessageOut {
+            message_id,
+            sender,
+            recipient,
+            amount,
+            nonce,
+            digest,
+            data,
+            ..
+        } => (message_id, sender, recipient, *amount, nonce, digest, data),
+        _ => return false,
+    };
+
+    &proof.root == root
+        && &Output::message_id(sender, recipient, nonce, amount, data) == message_id
+        && &Output::message_digest(data) == digest
+        && verify_merkle_proof(root, message_id, &proof.proof_set, proof.index, proof.messages_count)
+}
+
 #[test]
 #[cfg(feature = "random")]
 fn ephemeral_merkle_root_returns_the_expected_root() {
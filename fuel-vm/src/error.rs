@@ -264,6 +264,7 @@ pub enum BugId {
     ID005,
     ID006,
     ID007,
+    ID008,
 }
 
 /// Traceable bug variants
@@ -280,6 +281,9 @@ pub enum BugVariant {
 
     /// The stack point has overflow
     StackPointerOverflow,
+
+    /// An executed script didn't produce a `ScriptResult` receipt
+    ScriptResultMissing,
 }
 
 impl fmt::Display for BugVariant {
@@ -312,6 +316,13 @@ impl fmt::Display for BugVariant {
 
                 This overflow means the registers are corrupted."#
             ),
+
+            Self::ScriptResultMissing => write!(
+                f,
+                r#"The execution of a script always ends with a `ScriptResult` receipt.
+
+                This means the receipts of the script are corrupted."#
+            ),
         }
     }
 }
@@ -180,6 +180,8 @@ pub struct GasCostsValues {
     pub bhei: Word,
     pub bhsh: Word,
     pub burn: Word,
+    #[cfg_attr(feature = "serde", serde(default = "GasCostsValues::default_burns"))]
+    pub burns: Word,
     pub cb: Word,
     pub cfei: Word,
     pub cfsi: Word,
@@ -206,6 +208,8 @@ pub struct GasCostsValues {
     pub lw: Word,
     pub mcpi: Word,
     pub mint: Word,
+    #[cfg_attr(feature = "serde", serde(default = "GasCostsValues::default_mints"))]
+    pub mints: Word,
     pub mlog: Word,
     #[cfg_attr(feature = "serde", serde(rename = "mod"))]
     pub mod_op: Word,
@@ -287,6 +291,18 @@ impl GasCosts {
 }
 
 impl GasCostsValues {
+    /// Default cost of `MINTS`, for gas cost configs serialized before the opcode existed.
+    #[cfg(feature = "serde")]
+    fn default_mints() -> Word {
+        default_gas_costs::default_gas_costs().mints
+    }
+
+    /// Default cost of `BURNS`, for gas cost configs serialized before the opcode existed.
+    #[cfg(feature = "serde")]
+    fn default_burns() -> Word {
+        default_gas_costs::default_gas_costs().burns
+    }
+
     /// Create costs that are all set to zero.
     pub fn free() -> Self {
         Self {
@@ -299,6 +315,7 @@ impl GasCostsValues {
             bhei: 0,
             bhsh: 0,
             burn: 0,
+            burns: 0,
             cb: 0,
             cfei: 0,
             cfsi: 0,
@@ -325,6 +342,7 @@ impl GasCostsValues {
             lw: 0,
             mcpi: 0,
             mint: 0,
+            mints: 0,
             mlog: 0,
             mod_op: 0,
             modi: 0,
@@ -384,6 +402,7 @@ impl GasCostsValues {
             bhei: 1,
             bhsh: 1,
             burn: 1,
+            burns: 1,
             cb: 1,
             cfei: 1,
             cfsi: 1,
@@ -410,6 +429,7 @@ impl GasCostsValues {
             lw: 1,
             mcpi: 1,
             mint: 1,
+            mints: 1,
             mlog: 1,
             mod_op: 1,
             modi: 1,
@@ -495,3 +515,19 @@ impl From<GasCosts> for GasCostsValues {
         (*i.0).clone()
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn costs_serialized_without_sub_id_mint_and_burn_deserialize() {
+        let mut json = serde_json::to_value(GasCostsValues::default()).expect("failed to serialize");
+        let fields = json.as_object_mut().expect("costs are serialized as a map");
+        fields.remove("mints");
+        fields.remove("burns");
+
+        let costs: GasCostsValues = serde_json::from_value(json).expect("failed to deserialize");
+        assert_eq!(costs, GasCostsValues::default());
+    }
+}
@@ -12,6 +12,7 @@ pub fn default_gas_costs() -> GasCostsValues {
         bhei: 1,
         bhsh: 1,
         burn: 35,
+        burns: 35,
         cb: 2,
         cfei: 1,
         cfsi: 1,
@@ -38,6 +39,7 @@ pub fn default_gas_costs() -> GasCostsValues {
         lw: 1,
         mcpi: 3,
         mint: 35,
+        mints: 35,
         mlog: 1,
         srwq: DependentCost {
             base: 54,
@@ -24,6 +24,7 @@ mod blockchain;
 mod constructors;
 mod contract;
 mod crypto;
+mod decode_cache;
 pub mod diff;
 mod executors;
 mod flow;
@@ -39,14 +40,23 @@ mod post_execution;
 #[cfg(feature = "debug")]
 mod debug;
 
+#[cfg(feature = "trace")]
+mod trace;
+
 #[cfg(feature = "profile-any")]
 use crate::profiler::Profiler;
 
 #[cfg(feature = "profile-gas")]
 use crate::profiler::InstructionLocation;
 
+#[cfg(feature = "trace")]
+use crate::trace::Trace;
+
 pub use balances::RuntimeBalances;
-pub use memory::MemoryRange;
+pub use decode_cache::DecodeCache;
+pub use memory::{Memory, MemoryRange};
+
+use decode_cache::ActiveCode;
 
 use crate::checked_transaction::{CreateCheckedMetadata, IntoChecked, ScriptCheckedMetadata};
 
@@ -61,7 +71,7 @@ use crate::checked_transaction::{CreateCheckedMetadata, IntoChecked, ScriptCheck
 #[derive(Debug, Clone)]
 pub struct Interpreter<S, Tx = ()> {
     registers: [Word; VM_REGISTER_COUNT],
-    memory: Vec<u8>,
+    memory: Memory,
     frames: Vec<CallFrame>,
     receipts: Vec<Receipt>,
     tx: Tx,
@@ -73,10 +83,15 @@ pub struct Interpreter<S, Tx = ()> {
     gas_costs: GasCosts,
     #[cfg(feature = "profile-any")]
     profiler: Profiler,
+    #[cfg(feature = "trace")]
+    trace: Trace,
     params: ConsensusParameters,
     /// `PanicContext` after the latest execution. It is consumed by `append_panic_receipt`
     /// and is `PanicContext::None` after consumption.
     panic_context: PanicContext,
+    decode_cache: Option<DecodeCache>,
+    /// Decoded code of each call frame, if it was found in the decode cache.
+    active_code: Vec<Option<ActiveCode>>,
 }
 
 /// Sometimes it is possible to add some additional context information
@@ -93,8 +108,11 @@ pub(crate) enum PanicContext {
 
 impl<S, Tx> Interpreter<S, Tx> {
     /// Returns the current state of the VM memory
-    pub fn memory(&self) -> &[u8] {
-        self.memory.as_slice()
+    ///
+    /// The memory is paged, so it can't be borrowed as a single slice: use [`Memory::read`] for
+    /// the bytes of a range, and [`Memory::to_vec`] for a contiguous copy of the whole memory.
+    pub const fn memory(&self) -> &Memory {
+        &self.memory
     }
 
     /// Returns the current state of the registers
@@ -106,6 +124,11 @@ impl<S, Tx> Interpreter<S, Tx> {
         self.frames.as_slice()
     }
 
+    /// The cache of decoded contract code, if enabled with [`Self::with_decode_cache`].
+    pub const fn decode_cache(&self) -> Option<&DecodeCache> {
+        self.decode_cache.as_ref()
+    }
+
     /// Debug handler
     pub const fn debugger(&self) -> &Debugger {
         &self.debugger
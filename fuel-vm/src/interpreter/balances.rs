@@ -1,5 +1,5 @@
 use crate::consts::*;
-use crate::interpreter::{ExecutableTransaction, InitialBalances, Interpreter};
+use crate::interpreter::{ExecutableTransaction, InitialBalances, Interpreter, Memory};
 
 use fuel_asm::{RegId, Word};
 use fuel_tx::CheckError;
@@ -85,14 +85,11 @@ impl RuntimeBalances {
         self.state.get(asset).map(Balance::value)
     }
 
-    fn _set_memory_balance(balance: &Balance, memory: &mut [u8]) -> Word {
+    fn _set_memory_balance(balance: &Balance, memory: &mut Memory) -> Word {
         let value = balance.value();
         let offset = balance.offset();
 
-        let offset = offset + AssetId::LEN;
-        let memory = &mut memory[offset..offset + WORD_SIZE];
-
-        memory.copy_from_slice(&value.to_be_bytes());
+        memory.write(offset + AssetId::LEN, &value.to_be_bytes());
 
         value
     }
@@ -103,7 +100,7 @@ impl RuntimeBalances {
     /// Note: This will not append a new asset into the set since all the assets must be created
     /// during VM initialization and any additional asset would imply reordering the memory
     /// representation of the balances since they must always be ordered, as in the protocol.
-    pub fn checked_balance_add(&mut self, memory: &mut [u8], asset: &AssetId, value: Word) -> Option<Word> {
+    pub fn checked_balance_add(&mut self, memory: &mut Memory, asset: &AssetId, value: Word) -> Option<Word> {
         self.state
             .get_mut(asset)
             .and_then(|b| b.checked_add(value))
@@ -113,7 +110,7 @@ impl RuntimeBalances {
 
     /// Attempt to subtract the balance of an asset, updating the VM memory in the appropriate
     /// offset
-    pub fn checked_balance_sub(&mut self, memory: &mut [u8], asset: &AssetId, value: Word) -> Option<Word> {
+    pub fn checked_balance_sub(&mut self, memory: &mut Memory, asset: &AssetId, value: Word) -> Option<Word> {
         self.state
             .get_mut(asset)
             .and_then(|b| b.checked_sub(value))
@@ -136,8 +133,8 @@ impl RuntimeBalances {
             let value = balance.value();
             let ofs = balance.offset();
 
-            vm.memory[ofs..ofs + AssetId::LEN].copy_from_slice(asset.as_ref());
-            vm.memory[ofs + AssetId::LEN..ofs + AssetId::LEN + WORD_SIZE].copy_from_slice(&value.to_be_bytes());
+            vm.memory.write(ofs, asset.as_ref());
+            vm.memory.write(ofs + AssetId::LEN, &value.to_be_bytes());
         });
 
         vm.balances = self;
@@ -204,10 +201,10 @@ fn writes_to_memory_correctly() {
     assets_sorted
         .iter()
         .fold(VM_MEMORY_BALANCES_OFFSET, |ofs, (asset, value)| {
-            assert_eq!(asset.as_ref(), &memory[ofs..ofs + AssetId::LEN]);
+            assert_eq!(asset.as_ref(), &*memory.read(ofs..ofs + AssetId::LEN));
             assert_eq!(
                 &value.to_be_bytes(),
-                &memory[ofs + AssetId::LEN..ofs + AssetId::LEN + WORD_SIZE]
+                &*memory.read(ofs + AssetId::LEN..ofs + AssetId::LEN + WORD_SIZE)
             );
 
             ofs + AssetId::LEN + WORD_SIZE
@@ -1,20 +1,23 @@
 use super::memory::OwnershipRegisters;
-use super::{ExecutableTransaction, Interpreter, MemoryRange};
+use super::{ExecutableTransaction, Interpreter, Memory, MemoryRange};
 use crate::call::CallFrame;
 use crate::consts::*;
 use crate::error::{Bug, BugId, BugVariant, RuntimeError};
 use crate::storage::InterpreterStorage;
 
 use fuel_asm::{PanicReason, RegId};
-use fuel_tx::{Output, Receipt};
+use fuel_tx::{Contract, Output, Receipt};
 use fuel_types::bytes::{self, Deserializable};
 use fuel_types::{Address, AssetId, Bytes32, Bytes8, ContractId, RegisterId, Word};
 
 use crate::arith::{add_usize, checked_add_usize, checked_add_word, checked_sub_word};
 use crate::interpreter::PanicContext;
-use core::slice;
+use std::borrow::Cow;
 use std::ops::Range;
 
+#[cfg(feature = "trace")]
+use crate::trace::StorageAccess;
+
 #[cfg(test)]
 mod test;
 
@@ -60,25 +63,23 @@ where
             return Err(PanicReason::MemoryOverflow.into());
         }
 
-        // compiler will optimize to memset
-        self.memory[memory_offset..memory_offset_end]
-            .iter_mut()
-            .for_each(|m| *m = 0);
+        self.memory.fill(memory_offset..memory_offset_end, 0);
 
         // fetch the contract id
-        let contract_id = &self.memory[contract_id..contract_id_end];
-
-        // Safety: Memory bounds are checked and consistent
-        let contract_id = unsafe { ContractId::as_ref_unchecked(contract_id) };
+        let contract_id = ContractId::new(self.memory.read_bytes(contract_id));
 
         // the contract must be declared in the transaction inputs
-        if !self.transaction().input_contracts().any(|id| id == contract_id) {
-            self.panic_context = PanicContext::ContractId(*contract_id);
+        if !self.transaction().input_contracts().any(|id| id == &contract_id) {
+            self.panic_context = PanicContext::ContractId(contract_id);
             return Err(PanicReason::ContractNotInInputs.into());
         };
 
         // fetch the storage contract
-        let contract = self.contract(contract_id)?;
+        let Self { storage, memory, .. } = self;
+        let contract = storage
+            .storage_contract(&contract_id)
+            .map_err(RuntimeError::from_io)?
+            .ok_or(PanicReason::ContractNotFound)?;
         let contract = contract.as_ref().as_ref();
 
         if contract_offset > contract.len() {
@@ -88,14 +89,8 @@ where
         let contract = &contract[contract_offset..];
         let len = contract.len().min(length);
 
-        let code = &contract[..len];
-
-        // Safety: all bounds are checked
-        let memory = &self.memory[memory_offset] as *const u8;
-        let memory = unsafe { slice::from_raw_parts_mut(memory as *mut u8, len) };
-
         // perform the code copy
-        memory.copy_from_slice(code);
+        memory.write(memory_offset, &contract[..len]);
 
         self.registers[RegId::SP]
             //TODO this is looser than the compare against [RegId::HP,RegId::SSP+length]
@@ -109,56 +104,103 @@ where
         // update frame pointer, if we have a stack frame (e.g. fp > 0)
         if fp > 0 {
             let fp_code_size = add_usize(fp, CallFrame::code_size_offset());
-            let fp_code_size_end = add_usize(fp_code_size, WORD_SIZE);
 
-            let length = Word::from_be_bytes(
-                self.memory[fp_code_size..fp_code_size_end]
-                    .try_into()
-                    .map_err(|_| PanicReason::MemoryOverflow)?,
-            )
-            .checked_add(length as Word)
-            .ok_or(PanicReason::MemoryOverflow)?;
+            let length = Word::from_be_bytes(self.memory.read_bytes(fp_code_size))
+                .checked_add(length as Word)
+                .ok_or(PanicReason::MemoryOverflow)?;
 
-            self.memory[fp_code_size..fp_code_size_end].copy_from_slice(&length.to_be_bytes());
+            self.memory.write(fp_code_size, &length.to_be_bytes());
         }
 
         self.inc_pc()
     }
 
     pub(crate) fn burn(&mut self, a: Word) -> Result<(), RuntimeError> {
-        let (c, cx) = self.internal_contract_bounds()?;
+        let contract = self.internal_contract()?;
 
-        // Safety: Memory bounds logically verified by the interpreter
-        let contract = unsafe { ContractId::as_ref_unchecked(&self.memory[c..cx]) };
-        let asset_id = unsafe { AssetId::as_ref_unchecked(&self.memory[c..cx]) };
+        self.burn_asset(&contract, &AssetId::from(*contract), a)?;
 
-        let balance = self.balance(contract, asset_id)?;
-        let balance = balance.checked_sub(a).ok_or(PanicReason::NotEnoughBalance)?;
+        self.inc_pc()
+    }
 
-        self.storage
-            .merkle_contract_asset_id_balance_insert(contract, asset_id, balance)
-            .map_err(RuntimeError::from_io)?;
+    /// Burn `a` coins of the asset `sha256(contract_id || mem[$rB, 32])`.
+    pub(crate) fn burn_sub_id(&mut self, a: Word, b: Word) -> Result<(), RuntimeError> {
+        let contract = self.internal_contract()?;
+        let asset_id = Contract::asset_id(&contract, &self.sub_id(b)?);
+
+        self.burn_asset(&contract, &asset_id, a)?;
 
         self.inc_pc()
     }
 
     pub(crate) fn mint(&mut self, a: Word) -> Result<(), RuntimeError> {
-        let (c, cx) = self.internal_contract_bounds()?;
+        let contract = self.internal_contract()?;
 
-        // Safety: Memory bounds logically verified by the interpreter
-        let contract = unsafe { ContractId::as_ref_unchecked(&self.memory[c..cx]) };
-        let asset_id = unsafe { AssetId::as_ref_unchecked(&self.memory[c..cx]) };
+        self.mint_asset(&contract, &AssetId::from(*contract), a)?;
 
-        let balance = self.balance(contract, asset_id)?;
-        let balance = checked_add_word(balance, a)?;
+        self.inc_pc()
+    }
 
-        self.storage
-            .merkle_contract_asset_id_balance_insert(contract, asset_id, balance)
-            .map_err(RuntimeError::from_io)?;
+    /// Mint `a` coins of the asset `sha256(contract_id || mem[$rB, 32])`.
+    pub(crate) fn mint_sub_id(&mut self, a: Word, b: Word) -> Result<(), RuntimeError> {
+        let contract = self.internal_contract()?;
+        let asset_id = Contract::asset_id(&contract, &self.sub_id(b)?);
+
+        self.mint_asset(&contract, &asset_id, a)?;
 
         self.inc_pc()
     }
 
+    fn sub_id(&self, b: Word) -> Result<Bytes32, RuntimeError> {
+        let bx = checked_add_word(b, Bytes32::LEN as Word)?;
+
+        if bx > VM_MAX_RAM {
+            return Err(PanicReason::MemoryOverflow.into());
+        }
+
+        Ok(Bytes32::new(self.memory.read_bytes(b as usize)))
+    }
+
+    /// Update the balance of the contract and record the supply change with a
+    /// [`Receipt::Burn`].
+    fn burn_asset(&mut self, contract: &ContractId, asset_id: &AssetId, a: Word) -> Result<(), RuntimeError> {
+        let balance = self.traced_balance(contract, asset_id)?;
+        let balance = balance.checked_sub(a).ok_or(PanicReason::NotEnoughBalance)?;
+
+        self.balance_insert(contract, asset_id, balance)?;
+
+        let receipt = Receipt::burn(
+            *contract,
+            *asset_id,
+            a,
+            self.registers[RegId::PC],
+            self.registers[RegId::IS],
+        );
+        self.append_receipt(receipt);
+
+        Ok(())
+    }
+
+    /// Update the balance of the contract and record the supply change with a
+    /// [`Receipt::Mint`].
+    fn mint_asset(&mut self, contract: &ContractId, asset_id: &AssetId, a: Word) -> Result<(), RuntimeError> {
+        let balance = self.traced_balance(contract, asset_id)?;
+        let balance = checked_add_word(balance, a)?;
+
+        self.balance_insert(contract, asset_id, balance)?;
+
+        let receipt = Receipt::mint(
+            *contract,
+            *asset_id,
+            a,
+            self.registers[RegId::PC],
+            self.registers[RegId::IS],
+        );
+        self.append_receipt(receipt);
+
+        Ok(())
+    }
+
     pub(crate) fn code_copy(&mut self, a: Word, b: Word, c: Word, d: Word) -> Result<(), RuntimeError> {
         let bx = checked_add_word(b, ContractId::LEN as Word)?;
         let cd = checked_add_word(c, d)?;
@@ -168,17 +210,16 @@ where
         }
 
         let (a, b, c, d) = (a as usize, b as usize, c as usize, d as usize);
-        let (bx, cd) = (bx as usize, cd as usize);
+        let cd = cd as usize;
 
-        // Safety: Memory bounds are checked by the interpreter
-        let contract = unsafe { ContractId::as_ref_unchecked(&self.memory[b..bx]) };
+        let contract = ContractId::new(self.memory.read_bytes(b));
 
-        if !self.transaction().input_contracts().any(|input| input == contract) {
-            self.panic_context = PanicContext::ContractId(*contract);
+        if !self.transaction().input_contracts().any(|input| input == &contract) {
+            self.panic_context = PanicContext::ContractId(contract);
             return Err(PanicReason::ContractNotInInputs.into());
         }
 
-        let contract = self.contract(contract)?.into_owned();
+        let contract = self.contract(&contract)?.into_owned();
 
         if contract.as_ref().len() < d {
             self.try_zeroize(a, d)?;
@@ -225,14 +266,12 @@ where
         }
 
         let (a, b) = (a as usize, b as usize);
-        let bx = bx as usize;
 
-        // Safety: Memory bounds are checked by the interpreter
-        let contract_id = unsafe { ContractId::as_ref_unchecked(&self.memory[b..bx]) };
+        let contract_id = ContractId::new(self.memory.read_bytes(b));
 
         let (_, root) = self
             .storage
-            .storage_contract_root(contract_id)
+            .storage_contract_root(&contract_id)
             .transpose()
             .ok_or(PanicReason::ContractNotFound)?
             .map_err(RuntimeError::from_io)?
@@ -252,12 +291,9 @@ where
             return Err(PanicReason::MemoryOverflow.into());
         }
 
-        let (b, bx) = (b as usize, bx as usize);
-
-        // Safety: Memory bounds are checked by the interpreter
-        let contract_id = unsafe { ContractId::as_ref_unchecked(&self.memory[b..bx]) };
+        let contract_id = ContractId::new(self.memory.read_bytes(b as usize));
 
-        let len = self.contract_size(contract_id)?;
+        let len = self.contract_size(&contract_id)?;
         self.dependent_gas_charge(self.gas_costs.csiz, len)?;
         self.registers[ra] = len;
 
@@ -267,8 +303,19 @@ where
     pub(crate) fn state_clear_qword(&mut self, a: Word, rb: RegisterId, c: Word) -> Result<(), RuntimeError> {
         Self::is_register_writable(rb)?;
 
-        let contract_id = *self.internal_contract()?;
+        let contract_id = self.internal_contract()?;
         let input = StateClearQWord::new(a, c)?;
+
+        #[cfg(feature = "trace")]
+        {
+            let key = Bytes32::new(self.memory.read_bytes(input.start_storage_key_memory_range.start));
+            self.trace_storage(|| StorageAccess::StateClear {
+                contract: contract_id,
+                key,
+                slots: c,
+            });
+        }
+
         let Self {
             ref mut storage,
             ref memory,
@@ -291,18 +338,26 @@ where
             return Err(PanicReason::MemoryOverflow.into());
         }
 
-        let (c, cx) = (c as usize, cx as usize);
-
         let contract = self.internal_contract()?;
+        let key = Bytes32::new(self.memory.read_bytes(c as usize));
 
-        // Safety: Memory bounds are checked by the interpreter
-        let key = unsafe { Bytes32::as_ref_unchecked(&self.memory[c..cx]) };
-
-        let result = self
+        let value = self
             .storage
-            .merkle_contract_state(contract, key)
+            .merkle_contract_state(&contract, &key)
             .map_err(RuntimeError::from_io)?
-            .map(|state| unsafe { Bytes8::from_slice_unchecked(state.as_ref().as_ref()).into() })
+            .map(Cow::into_owned);
+
+        #[cfg(feature = "trace")]
+        {
+            self.trace_storage(|| StorageAccess::StateRead {
+                contract,
+                key,
+                values: vec![value],
+            });
+        }
+
+        let result = value
+            .map(|state| unsafe { Bytes8::from_slice_unchecked(state.as_ref()).into() })
             .map(Word::from_be_bytes);
 
         self.registers[ra] = result.unwrap_or(0);
@@ -313,8 +368,13 @@ where
 
     pub(crate) fn state_read_qword(&mut self, a: Word, rb: RegisterId, c: Word, d: Word) -> Result<(), RuntimeError> {
         Self::is_register_writable(rb)?;
-        let contract_id = *self.internal_contract()?;
+        let contract_id = self.internal_contract()?;
         let input = StateReadQWord::new(a, c, d, self.ownership_registers())?;
+
+        // The destination may overlap the key, so read it before the instruction does.
+        #[cfg(feature = "trace")]
+        let key = Bytes32::new(self.memory.read_bytes(input.origin_key_memory_range.start));
+
         let Self {
             ref storage,
             ref mut memory,
@@ -322,7 +382,18 @@ where
             ..
         } = self;
 
-        state_read_qword(&contract_id, storage, memory, &mut registers[rb], input)?;
+        #[cfg_attr(not(feature = "trace"), allow(unused_variables))]
+        let values = state_read_qword(&contract_id, storage, memory, &mut registers[rb], input)?;
+
+        #[cfg(feature = "trace")]
+        if self.trace.is_enabled() {
+            self.trace.record_storage(StorageAccess::StateRead {
+                contract: contract_id,
+                key,
+                values: values.into_iter().map(|value| value.map(Cow::into_owned)).collect(),
+            });
+        }
+
         self.inc_pc()
     }
 
@@ -335,12 +406,10 @@ where
             return Err(PanicReason::MemoryOverflow.into());
         }
 
-        let (a, ax) = (a as usize, ax as usize);
-        let (d, dx) = self.internal_contract_bounds()?;
+        let (d, _) = self.internal_contract_bounds()?;
 
-        // Safety: Memory bounds logically verified by the interpreter
-        let contract = unsafe { ContractId::as_ref_unchecked(&self.memory[d..dx]) };
-        let key = unsafe { Bytes32::as_ref_unchecked(&self.memory[a..ax]) };
+        let contract = ContractId::new(self.memory.read_bytes(d));
+        let key = Bytes32::new(self.memory.read_bytes(a as usize));
 
         let mut value = Bytes32::default();
 
@@ -348,9 +417,18 @@ where
 
         let result = self
             .storage
-            .merkle_contract_state_insert(contract, key, &value)
+            .merkle_contract_state_insert(&contract, &key, &value)
             .map_err(RuntimeError::from_io)?;
 
+        #[cfg(feature = "trace")]
+        {
+            self.trace_storage(|| StorageAccess::StateWrite {
+                contract,
+                key,
+                values: vec![value],
+            });
+        }
+
         self.registers[rb] = result.is_some() as Word;
 
         self.inc_pc()
@@ -358,8 +436,25 @@ where
 
     pub(crate) fn state_write_qword(&mut self, a: Word, rb: RegisterId, c: Word, d: Word) -> Result<(), RuntimeError> {
         Self::is_register_writable(rb)?;
-        let contract_id = *self.internal_contract()?;
+        let contract_id = self.internal_contract()?;
         let input = StateWriteQWord::new(a, c, d)?;
+
+        #[cfg(feature = "trace")]
+        if self.is_tracing() {
+            let key = Bytes32::new(self.memory.read_bytes(input.starting_storage_key_memory_range.start));
+            let values = self
+                .memory
+                .read(input.source_address_memory_range.clone())
+                .chunks_exact(Bytes32::LEN)
+                .map(|chunk| unsafe { Bytes32::from_slice_unchecked(chunk) })
+                .collect();
+            self.trace_storage(|| StorageAccess::StateWrite {
+                contract: contract_id,
+                key,
+                values,
+            });
+        }
+
         let Self {
             ref mut storage,
             ref mut memory,
@@ -398,8 +493,7 @@ where
         let idx = c;
         let amount = d;
 
-        // Safety: checked len
-        let recipient = unsafe { Address::from_slice_unchecked(&self.memory[a..ax]) };
+        let recipient = Address::new(self.memory.read_bytes(a));
         if recipient == Address::zeroed() {
             return Err(PanicReason::ZeroedMessageOutputRecipient.into());
         }
@@ -409,9 +503,15 @@ where
             .outputs_offset_at(c as usize)
             .and_then(|ofs| ofs.checked_add(self.tx_offset()))
             .ok_or(PanicReason::OutputNotFound)?;
+        let size = self
+            .transaction()
+            .outputs()
+            .get(c as usize)
+            .map(bytes::SizedBytes::serialized_size)
+            .ok_or(PanicReason::OutputNotFound)?;
 
         // halt with I/O error because tx should be serialized correctly into vm memory
-        let output = Output::from_bytes(&self.memory[offset..])?;
+        let output = Output::from_bytes(&self.memory.read(offset..offset + size))?;
 
         // amount isn't checked because we are allowed to send zero balances with a message
         if !matches!(output, Output::Message { recipient, .. } if recipient == Address::zeroed()) {
@@ -424,17 +524,13 @@ where
 
         let fp = self.registers[RegId::FP] as usize;
         let txid = self.tx_id();
-        let data = ax;
-        let data = &self.memory[data..bx];
-        let data = data.to_vec();
-
-        let fpx = checked_add_usize(fp, Address::LEN)?;
+        let data = self.memory.read(ax..bx).into_owned();
 
-        // Safety: $fp is guaranteed to contain enough bytes
-        let sender = unsafe { Address::from_slice_unchecked(&self.memory[fp..fpx]) };
+        // $fp is guaranteed to contain enough bytes
+        let sender = Address::new(self.memory.read_bytes(fp));
 
         let message = Output::message(recipient, amount);
-        let receipt = Receipt::message_out_from_tx_output(txid, idx, sender, recipient, amount, data);
+        let receipt = Receipt::message_out_from_tx_output(&txid, idx, sender, recipient, amount, data);
 
         self.set_message_output(idx as usize, message)?;
         self.append_receipt(receipt);
@@ -487,23 +583,25 @@ impl StateReadQWord {
     }
 }
 
-fn state_read_qword(
+/// Read the slots into memory and return them as read from the storage.
+fn state_read_qword<'s, S: InterpreterStorage>(
     contract_id: &ContractId,
-    storage: &impl InterpreterStorage,
-    memory: &mut [u8],
+    storage: &'s S,
+    memory: &mut Memory,
     result_register: &mut Word,
     input: StateReadQWord,
-) -> Result<(), RuntimeError> {
-    // Safety: Memory bounds are checked by the interpreter
-    let origin_key = unsafe { Bytes32::as_ref_unchecked(&memory[input.origin_key_memory_range]) };
+) -> Result<Vec<Option<Cow<'s, Bytes32>>>, RuntimeError> {
+    let origin_key = Bytes32::new(memory.read_bytes(input.origin_key_memory_range.start));
+
+    let values = storage
+        .merkle_contract_state_range(contract_id, &origin_key, input.num_slots)
+        .map_err(RuntimeError::from_io)?;
 
     let mut all_set = true;
-    let result: Vec<u8> = storage
-        .merkle_contract_state_range(contract_id, origin_key, input.num_slots)
-        .map_err(RuntimeError::from_io)?
-        .into_iter()
+    let result: Vec<u8> = values
+        .iter()
         .flat_map(|bytes| match bytes {
-            Some(bytes) => **bytes,
+            Some(bytes) => ***bytes,
             None => {
                 all_set = false;
                 *Bytes32::zeroed()
@@ -513,9 +611,9 @@ fn state_read_qword(
 
     *result_register = all_set as Word;
 
-    memory[input.destination_address_memory_range].copy_from_slice(&result);
+    memory.write(input.destination_address_memory_range.start, &result);
 
-    Ok(())
+    Ok(values)
 }
 
 struct StateWriteQWord {
@@ -552,20 +650,20 @@ impl StateWriteQWord {
 fn state_write_qword(
     contract_id: &ContractId,
     storage: &mut impl InterpreterStorage,
-    memory: &[u8],
+    memory: &Memory,
     result_register: &mut Word,
     input: StateWriteQWord,
 ) -> Result<(), RuntimeError> {
-    // Safety: Memory bounds are checked by the interpreter
-    let destination_key = unsafe { Bytes32::as_ref_unchecked(&memory[input.starting_storage_key_memory_range]) };
+    let destination_key = Bytes32::new(memory.read_bytes(input.starting_storage_key_memory_range.start));
 
-    let values: Vec<_> = memory[input.source_address_memory_range]
+    let values: Vec<_> = memory
+        .read(input.source_address_memory_range)
         .chunks_exact(Bytes32::LEN)
         .flat_map(|chunk| Some(Bytes32::from(<[u8; 32]>::try_from(chunk).ok()?)))
         .collect();
 
     let any_none = storage
-        .merkle_contract_state_insert_range(contract_id, destination_key, &values)
+        .merkle_contract_state_insert_range(contract_id, &destination_key, &values)
         .map_err(RuntimeError::from_io)?
         .is_some();
     *result_register = any_none as Word;
@@ -597,15 +695,14 @@ impl StateClearQWord {
 fn state_clear_qword(
     contract_id: &ContractId,
     storage: &mut impl InterpreterStorage,
-    memory: &[u8],
+    memory: &Memory,
     result_register: &mut Word,
     input: StateClearQWord,
 ) -> Result<(), RuntimeError> {
-    // Safety: Memory bounds are checked by the interpreter
-    let start_key = unsafe { Bytes32::as_ref_unchecked(&memory[input.start_storage_key_memory_range]) };
+    let start_key = Bytes32::new(memory.read_bytes(input.start_storage_key_memory_range.start));
 
     let all_previously_set = storage
-        .merkle_contract_state_remove_range(contract_id, start_key, input.num_slots)
+        .merkle_contract_state_remove_range(contract_id, &start_key, input.num_slots)
         .map_err(RuntimeError::from_io)?
         .is_some();
 
@@ -77,7 +77,14 @@ fn test_state_clear_qword(input: SCWQInput) -> (Vec<([u8; 32], [u8; 32])>, bool)
     }
 
     let mut result_register = 0u64;
-    state_clear_qword(&Default::default(), &mut storage, &memory, &mut result_register, input).unwrap();
+    state_clear_qword(
+        &Default::default(),
+        &mut storage,
+        &memory.into(),
+        &mut result_register,
+        input,
+    )
+    .unwrap();
 
     let results = storage
         .all_contract_state()
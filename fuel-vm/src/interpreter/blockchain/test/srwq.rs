@@ -89,7 +89,7 @@ fn test_state_read_qword(input: SRWQInput) -> (Vec<u8>, bool) {
     let SRWQInput {
         input,
         storage_slots,
-        mut memory,
+        memory,
     } = input;
     let mut storage = MemoryStorage::new(0, Default::default());
     for (k, v) in storage_slots {
@@ -99,8 +99,9 @@ fn test_state_read_qword(input: SRWQInput) -> (Vec<u8>, bool) {
             .unwrap();
     }
     let mut result_register = 0u64;
+    let mut memory = memory.into();
     state_read_qword(&Default::default(), &storage, &mut memory, &mut result_register, input).unwrap();
-    (memory, result_register != 0)
+    (memory.to_vec(), result_register != 0)
 }
 
 #[test_case(
@@ -101,7 +101,14 @@ fn test_state_write_qword(input: SWWQInput) -> (Vec<([u8; 32], [u8; 32])>, bool)
     }
 
     let mut result_register = 0u64;
-    state_write_qword(&Default::default(), &mut storage, &memory, &mut result_register, input).unwrap();
+    state_write_qword(
+        &Default::default(),
+        &mut storage,
+        &memory.into(),
+        &mut result_register,
+        input,
+    )
+    .unwrap();
 
     let results = storage
         .all_contract_state()
@@ -1,6 +1,6 @@
 //! Exposed constructors API for the [`Interpreter`]
 
-use super::{ExecutableTransaction, Interpreter, RuntimeBalances};
+use super::{DecodeCache, ExecutableTransaction, Interpreter, Memory, RuntimeBalances};
 use crate::context::Context;
 use crate::interpreter::PanicContext;
 use crate::state::Debugger;
@@ -10,6 +10,9 @@ use crate::{consts::*, gas::GasCosts};
 #[cfg(feature = "profile-any")]
 use crate::profiler::{ProfileReceiver, Profiler};
 
+#[cfg(feature = "trace")]
+use crate::trace::Tracer;
+
 use fuel_tx::ConsensusParameters;
 
 impl<S, Tx> Interpreter<S, Tx>
@@ -24,7 +27,7 @@ where
     pub fn with_storage(storage: S, params: ConsensusParameters, gas_costs: GasCosts) -> Self {
         Self {
             registers: [0; VM_REGISTER_COUNT],
-            memory: vec![0; VM_MAX_RAM as usize],
+            memory: Memory::new(),
             frames: vec![],
             receipts: vec![],
             tx: Default::default(),
@@ -36,17 +39,50 @@ where
             gas_costs,
             #[cfg(feature = "profile-any")]
             profiler: Profiler::default(),
+            #[cfg(feature = "trace")]
+            trace: Default::default(),
             params,
             panic_context: PanicContext::None,
+            decode_cache: None,
+            active_code: vec![],
         }
     }
 
+    /// Restore the interpreter to the state of a freshly constructed one, so it can be reused for
+    /// another transaction.
+    ///
+    /// Registers, call frames, receipts, balances, the transaction and the execution context are
+    /// cleared, and every memory page is released, at a cost proportional to the number of pages.
+    /// The storage, consensus parameters, gas costs, decode cache, breakpoints, profiler and tracer
+    /// are kept.
+    pub fn reset(&mut self) -> &mut Self {
+        self.registers = [0; VM_REGISTER_COUNT];
+        self.memory.reset();
+        self.frames.clear();
+        self.active_code.clear();
+        self.receipts.clear();
+        self.tx = Default::default();
+        self.initial_balances = Default::default();
+        self.context = Context::default();
+        self.balances = RuntimeBalances::default();
+        self.panic_context = PanicContext::None;
+        #[cfg(feature = "debug")]
+        self.debugger.reset_last_state();
+        self
+    }
+
     /// Set the consensus parameters for the interpreter
     pub fn with_params(&mut self, params: ConsensusParameters) -> &mut Self {
         self.params = params;
         self
     }
 
+    /// Enable the cache of decoded contract code, reused across calls and transactions.
+    pub fn with_decode_cache(&mut self, cache: DecodeCache) -> &mut Self {
+        self.decode_cache.replace(cache);
+        self
+    }
+
     /// Sets a profiler for the VM
     #[cfg(feature = "profile-any")]
     pub fn with_profiler<P>(&mut self, receiver: P) -> &mut Self
@@ -56,6 +92,23 @@ where
         self.profiler.set_receiver(Box::new(receiver));
         self
     }
+
+    /// Sets a tracer receiving every executed instruction, see [`crate::trace`].
+    #[cfg(feature = "trace")]
+    pub fn with_tracer<T>(&mut self, tracer: T) -> &mut Self
+    where
+        T: Tracer + Send + Sync + 'static,
+    {
+        self.trace.set_tracer(Box::new(tracer));
+        self
+    }
+
+    /// Remove the tracer set with [`Self::with_tracer`].
+    #[cfg(feature = "trace")]
+    pub fn without_tracer(&mut self) -> &mut Self {
+        self.trace.remove_tracer();
+        self
+    }
 }
 
 impl<S, Tx> Interpreter<S, Tx>
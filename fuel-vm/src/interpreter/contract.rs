@@ -10,6 +10,9 @@ use fuel_types::{Address, AssetId, ContractId};
 
 use std::borrow::Cow;
 
+#[cfg(feature = "trace")]
+use crate::trace::StorageAccess;
+
 impl<S, Tx> Interpreter<S, Tx>
 where
     S: InterpreterStorage,
@@ -47,18 +50,15 @@ where
             return Err(PanicReason::MemoryOverflow.into());
         }
 
-        let (b, c, bx, cx) = (b as usize, c as usize, bx as usize, cx as usize);
-
-        // Safety: memory bounds checked
-        let asset_id = unsafe { AssetId::as_ref_unchecked(&self.memory[b..bx]) };
-        let contract = unsafe { ContractId::as_ref_unchecked(&self.memory[c..cx]) };
+        let asset_id = AssetId::new(self.memory.read_bytes(b as usize));
+        let contract = ContractId::new(self.memory.read_bytes(c as usize));
 
-        if !self.transaction().input_contracts().any(|input| contract == input) {
-            self.panic_context = PanicContext::ContractId(*contract);
+        if !self.transaction().input_contracts().any(|input| &contract == input) {
+            self.panic_context = PanicContext::ContractId(contract);
             return Err(PanicReason::ContractNotInInputs.into());
         }
 
-        let balance = self.balance(contract, asset_id)?;
+        let balance = self.traced_balance(&contract, &asset_id)?;
 
         self.registers[ra] = balance;
 
@@ -81,10 +81,8 @@ where
         }
 
         let amount = b;
-        let destination =
-            ContractId::try_from(&self.memory[a as usize..ax as usize]).expect("Unreachable! Checked memory range");
-        let asset_id =
-            AssetId::try_from(&self.memory[c as usize..cx as usize]).expect("Unreachable! Checked memory range");
+        let destination = ContractId::new(self.memory.read_bytes(a as usize));
+        let asset_id = AssetId::new(self.memory.read_bytes(c as usize));
 
         if !self
             .transaction()
@@ -101,7 +99,7 @@ where
 
         let internal_context = match self.internal_contract() {
             // optimistically attempt to load the internal contract id
-            Ok(source_contract) => Some(*source_contract),
+            Ok(source_contract) => Some(source_contract),
             // revert to external context if no internal contract is set
             Err(RuntimeError::Recoverable(PanicReason::ExpectedInternalContext)) => None,
             // bubble up any other kind of errors
@@ -148,14 +146,13 @@ where
         }
 
         let out_idx = b as usize;
-        let to = Address::try_from(&self.memory[a as usize..ax as usize]).expect("Unreachable! Checked memory range");
-        let asset_id =
-            AssetId::try_from(&self.memory[d as usize..dx as usize]).expect("Unreachable! Checked memory range");
+        let to = Address::new(self.memory.read_bytes(a as usize));
+        let asset_id = AssetId::new(self.memory.read_bytes(d as usize));
         let amount = c;
 
         let internal_context = match self.internal_contract() {
             // optimistically attempt to load the internal contract id
-            Ok(source_contract) => Some(*source_contract),
+            Ok(source_contract) => Some(source_contract),
             // revert to external context if no internal contract is set
             Err(RuntimeError::Recoverable(PanicReason::ExpectedInternalContext)) => None,
             // bubble up any other kind of errors
@@ -203,6 +200,41 @@ where
             .unwrap_or_default())
     }
 
+    /// Read the asset balance for a contract and report it to the tracer
+    pub(crate) fn traced_balance(&mut self, contract: &ContractId, asset_id: &AssetId) -> Result<Word, RuntimeError> {
+        let balance = self.balance(contract, asset_id)?;
+
+        #[cfg(feature = "trace")]
+        self.trace_storage(|| StorageAccess::BalanceRead {
+            contract: *contract,
+            asset_id: *asset_id,
+            value: balance,
+        });
+
+        Ok(balance)
+    }
+
+    /// Set the asset balance for a contract
+    pub(crate) fn balance_insert(
+        &mut self,
+        contract: &ContractId,
+        asset_id: &AssetId,
+        balance: Word,
+    ) -> Result<(), RuntimeError> {
+        self.storage
+            .merkle_contract_asset_id_balance_insert(contract, asset_id, balance)
+            .map_err(RuntimeError::from_io)?;
+
+        #[cfg(feature = "trace")]
+        self.trace_storage(|| StorageAccess::BalanceWrite {
+            contract: *contract,
+            asset_id: *asset_id,
+            value: balance,
+        });
+
+        Ok(())
+    }
+
     /// Increase the asset balance for a contract
     pub(crate) fn balance_increase(
         &mut self,
@@ -210,11 +242,9 @@ where
         asset_id: &AssetId,
         amount: Word,
     ) -> Result<Word, RuntimeError> {
-        let balance = self.balance(contract, asset_id)?;
+        let balance = self.traced_balance(contract, asset_id)?;
         let balance = balance.checked_add(amount).ok_or(PanicReason::ArithmeticOverflow)?;
-        self.storage
-            .merkle_contract_asset_id_balance_insert(contract, asset_id, balance)
-            .map_err(RuntimeError::from_io)?;
+        self.balance_insert(contract, asset_id, balance)?;
         Ok(balance)
     }
 
@@ -225,11 +255,9 @@ where
         asset_id: &AssetId,
         amount: Word,
     ) -> Result<Word, RuntimeError> {
-        let balance = self.balance(contract, asset_id)?;
+        let balance = self.traced_balance(contract, asset_id)?;
         let balance = balance.checked_sub(amount).ok_or(PanicReason::NotEnoughBalance)?;
-        self.storage
-            .merkle_contract_asset_id_balance_insert(contract, asset_id, balance)
-            .map_err(RuntimeError::from_io)?;
+        self.balance_insert(contract, asset_id, balance)?;
         Ok(balance)
     }
 }
@@ -22,13 +22,13 @@ where
             return Err(PanicReason::MemoryOverflow.into());
         }
 
-        let (a, b, bx, c, cx) = (a as usize, b as usize, bx as usize, c as usize, cx as usize);
+        let a = a as usize;
 
-        // Safety: memory bounds are checked
-        let signature = unsafe { Signature::as_ref_unchecked(&self.memory[b..bx]) };
-        let message = unsafe { Message::as_ref_unchecked(&self.memory[c..cx]) };
+        // Safety: the bytes are only used to recover a public key
+        let signature = unsafe { Signature::from_bytes_unchecked(self.memory.read_bytes(b as usize)) };
+        let message = unsafe { Message::from_bytes_unchecked(self.memory.read_bytes(c as usize)) };
 
-        match signature.recover(message) {
+        match signature.recover(&message) {
             Ok(pub_key) => {
                 self.try_mem_write(a, pub_key.as_ref())?;
                 self.clear_err();
@@ -58,7 +58,7 @@ where
 
         let mut h = Keccak256::new();
 
-        h.update(&self.memory[b..bc]);
+        self.memory.chunks(b..bc).for_each(|chunk| h.update(chunk));
 
         self.try_mem_write(a, h.finalize().as_slice())?;
 
@@ -77,7 +77,7 @@ where
 
         let (a, b, bc) = (a as usize, b as usize, bc as usize);
 
-        self.try_mem_write(a, Hasher::hash(&self.memory[b..bc]).as_ref())?;
+        self.try_mem_write(a, self.memory.chunks(b..bc).collect::<Hasher>().finalize().as_ref())?;
 
         self.inc_pc()
     }
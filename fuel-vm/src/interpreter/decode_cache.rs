@@ -0,0 +1,285 @@
+use super::Interpreter;
+use crate::error::RuntimeError;
+use crate::storage::InterpreterStorage;
+
+use fuel_asm::{Instruction, RegId};
+use fuel_types::{Bytes32, ContractId};
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Decoded instructions of a contract code, `None` for words that don't decode to a valid
+/// instruction.
+type DecodedCode = Arc<[Option<Instruction>]>;
+
+/// Cache of pre-decoded contract code, keyed by the code root stored in
+/// [`crate::storage::ContractsInfo`].
+///
+/// When set with [`super::Interpreter::with_decode_cache`], the code of a called contract is
+/// decoded once and reused for every step executed within it, and for every later call into
+/// contracts with the same code root. The decoded code of a call is discarded if its memory is
+/// written to, falling back to decoding from memory.
+#[derive(Debug, Clone, Default)]
+pub struct DecodeCache {
+    code: HashMap<Bytes32, DecodedCode>,
+    roots: HashMap<ContractId, Bytes32>,
+    capacity: Option<usize>,
+    hits: u64,
+    misses: u64,
+}
+
+impl DecodeCache {
+    /// Create an unbounded cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a cache holding the code of at most `capacity` contracts.
+    ///
+    /// The cache is cleared when a contract is decoded while it is full.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity: Some(capacity),
+            ..Default::default()
+        }
+    }
+
+    /// Number of distinct contract codes cached.
+    pub fn len(&self) -> usize {
+        self.code.len()
+    }
+
+    /// Return `true` if no contract code is cached.
+    pub fn is_empty(&self) -> bool {
+        self.code.is_empty()
+    }
+
+    /// Check if the code with the provided root is cached.
+    pub fn contains(&self, root: &Bytes32) -> bool {
+        self.code.contains_key(root)
+    }
+
+    /// Number of calls served from the cache.
+    pub const fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Number of calls that required decoding the contract code.
+    pub const fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Remove all cached code.
+    pub fn clear(&mut self) {
+        self.code.clear();
+        self.roots.clear();
+    }
+
+    pub(crate) fn root(&self, contract: &ContractId) -> Option<Bytes32> {
+        self.roots.get(contract).copied()
+    }
+
+    /// Fetch the decoded code for `root`, decoding `code` if it isn't cached yet.
+    pub(crate) fn get_or_decode(&mut self, contract: &ContractId, root: Bytes32, code: &[u8]) -> DecodedCode {
+        let len = code.len() / Instruction::SIZE;
+
+        if let Some(decoded) = self.code.get(&root).filter(|decoded| decoded.len() == len) {
+            self.hits += 1;
+            return decoded.clone();
+        }
+
+        self.misses += 1;
+
+        if self.capacity.map_or(false, |capacity| self.code.len() >= capacity) {
+            self.clear();
+        }
+
+        let decoded: DecodedCode = fuel_asm::from_bytes(code.iter().copied()).map(Result::ok).collect();
+
+        self.roots.insert(*contract, root);
+        self.code.insert(root, decoded.clone());
+
+        decoded
+    }
+}
+
+/// Decoded code of a call frame, loaded in memory at `range`.
+#[derive(Debug, Clone)]
+pub(crate) struct ActiveCode {
+    pub(crate) range: Range<usize>,
+    pub(crate) instructions: DecodedCode,
+}
+
+impl ActiveCode {
+    /// The instruction pair starting at the memory address `pc`, if both were decoded.
+    pub(crate) fn instructions_at(&self, pc: usize) -> Option<[Instruction; 2]> {
+        let offset = pc.checked_sub(self.range.start)?;
+
+        if offset % Instruction::SIZE != 0 {
+            return None;
+        }
+
+        let index = offset / Instruction::SIZE;
+
+        match self.instructions.get(index..index + 2)? {
+            [Some(hi), Some(lo)] => Some([*hi, *lo]),
+            _ => None,
+        }
+    }
+}
+
+impl<S, Tx> Interpreter<S, Tx>
+where
+    S: InterpreterStorage,
+{
+    /// Fetch the decoded code of a contract called with its code loaded at `code`, and make it
+    /// active for the new call frame.
+    pub(crate) fn activate_code(&mut self, contract: &ContractId, code: Range<usize>) -> Result<(), RuntimeError> {
+        let active = match self.decode_cache.as_mut() {
+            Some(cache) => {
+                let root = match cache.root(contract) {
+                    Some(root) => Some(root),
+                    None => self
+                        .storage
+                        .storage_contract_root(contract)
+                        .map_err(RuntimeError::from_io)?
+                        .map(|info| info.1),
+                };
+
+                root.map(|root| ActiveCode {
+                    instructions: cache.get_or_decode(contract, root, &self.memory.read(code.clone())),
+                    range: code,
+                })
+            }
+            None => None,
+        };
+
+        if let Some(active) = &active {
+            self.memory.watch_code(active.range.clone());
+        }
+        self.active_code.push(active);
+
+        Ok(())
+    }
+}
+
+impl<S, Tx> Interpreter<S, Tx> {
+    /// Drop the decoded code of the call frame being returned from.
+    pub(crate) fn deactivate_code(&mut self) {
+        if let Some(Some(_)) = self.active_code.pop() {
+            self.memory.unwatch_code();
+        }
+    }
+
+    /// Drop the decoded code of every call frame, so execution falls back to decoding memory.
+    pub(crate) fn drop_active_code(&mut self) {
+        self.active_code.iter_mut().for_each(|active| *active = None);
+        self.memory.unwatch_all_code();
+    }
+
+    /// The decoded instruction pair at `$pc`, if it belongs to the active code of the current
+    /// call frame and that code wasn't written to since the call.
+    pub(crate) fn active_instructions(&mut self) -> Option<[Instruction; 2]> {
+        if self.memory.take_code_written() {
+            self.drop_active_code();
+            return None;
+        }
+
+        self.active_code
+            .last()?
+            .as_ref()?
+            .instructions_at(self.registers[RegId::PC] as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_asm::{op, RegId};
+
+    #[test]
+    fn decodes_once_per_root() {
+        let code: Vec<u8> = [op::noop(), op::ret(RegId::ONE)].into_iter().collect();
+        let root = Bytes32::from([1; 32]);
+        let mut cache = DecodeCache::new();
+
+        let a = cache.get_or_decode(&ContractId::from([2; 32]), root, &code);
+        let b = cache.get_or_decode(&ContractId::from([3; 32]), root, &code);
+
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!((cache.hits(), cache.misses(), cache.len()), (1, 1, 1));
+        assert_eq!(cache.root(&ContractId::from([2; 32])), Some(root));
+    }
+
+    #[test]
+    fn clears_when_full() {
+        let code: Vec<u8> = [op::noop()].into_iter().collect();
+        let mut cache = DecodeCache::with_capacity(1);
+
+        cache.get_or_decode(&Default::default(), [1; 32].into(), &code);
+        cache.get_or_decode(&Default::default(), [2; 32].into(), &code);
+
+        assert_eq!(cache.len(), 1);
+        assert!(cache.contains(&[2; 32].into()));
+    }
+
+    #[test]
+    fn active_code_skips_invalid_and_unaligned() {
+        let mut code: Vec<u8> = [op::noop(), op::ret(RegId::ONE)].into_iter().collect();
+        code.extend([0xff; 4]);
+
+        let active = ActiveCode {
+            range: 100..100 + code.len(),
+            instructions: DecodeCache::new().get_or_decode(&Default::default(), Default::default(), &code),
+        };
+
+        assert_eq!(active.instructions_at(100), Some([op::noop(), op::ret(RegId::ONE)]));
+        assert_eq!(active.instructions_at(102), None);
+        assert_eq!(active.instructions_at(104), None);
+        assert_eq!(active.instructions_at(96), None);
+    }
+
+    #[test]
+    fn pair_reloads_code_overwritten_by_hi() {
+        use crate::checked_transaction::IntoChecked;
+        use crate::prelude::*;
+
+        let mut vm = Interpreter::with_memory_storage();
+        let tx = Transaction::script(
+            0,
+            1_000_000,
+            0,
+            op::ret(RegId::ONE).to_bytes().to_vec(),
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+        )
+        .into_checked(0, vm.params(), vm.gas_costs())
+        .expect("failed to check tx");
+        vm.init_script(tx).expect("failed to init VM");
+
+        // `hi` replaces the pair with `noop; movi 0x20 2` before `lo` runs
+        let code: Vec<u8> = [op::sw(0x10, 0x11, 0), op::movi(0x20, 1)].into_iter().collect();
+        let patch: Vec<u8> = [op::noop(), op::movi(0x20, 2)].into_iter().collect();
+
+        let start = vm.registers[RegId::SSP] as usize;
+        let range = start..start + code.len();
+        vm.registers[RegId::SP] += code.len() as Word;
+        vm.registers[RegId::PC] = start as Word;
+        vm.registers[0x10] = start as Word;
+        vm.registers[0x11] = Word::from_be_bytes(patch.try_into().expect("patch is one word"));
+        vm.memory.write(start, &code);
+
+        let instructions = DecodeCache::new().get_or_decode(&Default::default(), Default::default(), &code);
+        vm.memory.watch_code(range.clone());
+        vm.active_code.push(Some(ActiveCode { range, instructions }));
+
+        vm.execute().expect("failed to execute the pair");
+
+        assert_eq!(vm.registers[0x20], 2);
+        assert_eq!(vm.registers[RegId::PC], (start + code.len()) as Word);
+        assert!(matches!(vm.active_code.last(), Some(None)));
+    }
+}
@@ -361,12 +361,13 @@ impl<S, Tx> Interpreter<S, Tx> {
     {
         match change {
             Change::Register(Previous(VecState { index, value })) => self.registers[*index] = *value,
-            Change::Frame(Previous(value)) => invert_vec(&mut self.frames, value),
+            Change::Frame(Previous(value)) => {
+                invert_vec(&mut self.frames, value);
+                self.drop_active_code();
+            }
             Change::Receipt(Previous(value)) => invert_vec(&mut self.receipts, value),
             Change::Balance(Previous(value)) => invert_map(self.balances.as_mut(), value),
-            Change::Memory(Previous(Memory { start, bytes })) => {
-                self.memory[*start..(*start + bytes.len())].copy_from_slice(&bytes[..])
-            }
+            Change::Memory(Previous(Memory { start, bytes })) => self.memory.write(*start, &bytes[..]),
             Change::Context(Previous(value)) => self.context = value.clone(),
             Change::PanicContext(Previous(value)) => self.panic_context = value.clone(),
             Change::Txn(Previous(tx)) => {
@@ -427,7 +428,7 @@ impl<S, Tx> PartialEq for Interpreter<S, Tx>
 where
     Tx: PartialEq,
 {
-    /// Does not compare storage, debugger or profiler
+    /// Does not compare storage, debugger, profiler or decode cache
     fn eq(&self, other: &Self) -> bool {
         self.registers == other.registers
             && self.memory == other.memory
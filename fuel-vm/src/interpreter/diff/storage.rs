@@ -81,8 +81,12 @@ where
             gas_costs: self.gas_costs,
             params: self.params,
             panic_context: self.panic_context,
+            decode_cache: self.decode_cache,
+            active_code: self.active_code,
             #[cfg(feature = "profile-any")]
             profiler: self.profiler,
+            #[cfg(feature = "trace")]
+            trace: self.trace,
         }
     }
 
@@ -146,8 +150,12 @@ where
             gas_costs: self.gas_costs,
             params: self.params,
             panic_context: self.panic_context,
+            decode_cache: self.decode_cache,
+            active_code: self.active_code,
             #[cfg(feature = "profile-any")]
             profiler: self.profiler,
+            #[cfg(feature = "trace")]
+            trace: self.trace,
         }
     }
 
@@ -195,9 +195,9 @@ fn test_invert_map(v: &[(u32, u32)], key: u32, value: Option<u32>) -> Vec<(u32,
 fn reset_vm_memory() {
     let mut a = Interpreter::<_, Script>::with_memory_storage();
     let mut b = Interpreter::<_, Script>::with_memory_storage();
-    a.memory.resize(200, 0);
-    b.memory.resize(200, 0);
-    b.memory[100..132].copy_from_slice(&[1u8; 32]);
+    a.memory = vec![0; 200].into();
+    b.memory = vec![0; 200].into();
+    b.memory.write(100, &[1u8; 32]);
     let diff: Diff<InitialVmState> = a.diff(&b).into();
     assert_ne!(a, b);
     b.reset_vm_state(&diff);
@@ -207,9 +207,9 @@ fn reset_vm_memory() {
 #[test]
 fn reset_vm_txns() {
     let mut a = Interpreter::<_, Script>::with_memory_storage();
-    a.memory.resize(1, 0);
+    a.memory = vec![0; 1].into();
     let mut b = Interpreter::<_, Script>::with_memory_storage();
-    b.memory.resize(1, 0);
+    b.memory = vec![0; 1].into();
     b.tx.outputs_mut().push(fuel_tx::Output::Message {
         recipient: Address::zeroed(),
         amount: 1,
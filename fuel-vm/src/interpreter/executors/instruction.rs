@@ -5,7 +5,7 @@ use crate::state::{ExecuteState, ProgramState};
 use crate::storage::InterpreterStorage;
 
 use fuel_asm::{Instruction, PanicReason, RawInstruction, RegId};
-use fuel_types::{bytes, Word};
+use fuel_types::Word;
 
 use std::ops::Div;
 
@@ -16,25 +16,38 @@ where
 {
     /// Execute the current instruction pair located in `$m[$pc]`.
     pub fn execute(&mut self) -> Result<ExecuteState, InterpreterError> {
-        // Safety: `chunks_exact` is guaranteed to return a well-formed slice
-        let [hi, lo] = self.memory[self.registers[RegId::PC] as usize..]
-            .chunks_exact(WORD_SIZE)
-            .next()
-            .map(|b| unsafe { bytes::from_slice_unchecked(b) })
+        if let Some([hi, lo]) = self.active_instructions() {
+            return self.execute_pair(hi, lo, Self::decoded_instruction);
+        }
+
+        let pc = self.registers[RegId::PC] as usize;
+        let [hi, lo] = pc
+            .checked_add(WORD_SIZE)
+            .filter(|end| *end <= self.memory.len())
+            .map(|_| self.memory.read_bytes(pc))
             .map(Word::from_be_bytes)
             .map(fuel_asm::raw_instructions_from_word)
             .ok_or(InterpreterError::Panic(PanicReason::MemoryOverflow))?;
 
+        self.execute_pair(hi, lo, Self::instruction::<RawInstruction>)
+    }
+
+    fn execute_pair<I>(
+        &mut self,
+        hi: I,
+        lo: I,
+        instruction: fn(&mut Self, I) -> Result<ExecuteState, InterpreterError>,
+    ) -> Result<ExecuteState, InterpreterError> {
         // Store the expected `$pc` after executing `hi`
         let pc = self.registers[RegId::PC] + Instruction::SIZE as Word;
-        let state = self.instruction(hi)?;
+        let state = instruction(self, hi)?;
 
         // TODO optimize
         // Should execute `lo` only if there is no rupture in the flow - that means
         // either a breakpoint or some instruction that would skip `lo` such as
         // `RET`, `JI` or `CALL`
         if self.registers[RegId::PC] == pc && state.should_continue() {
-            self.instruction(lo)
+            instruction(self, lo)
         } else {
             Ok(state)
         }
@@ -54,10 +67,54 @@ where
             .map_err(|e| InterpreterError::from_runtime(e, raw.into()))
     }
 
-    #[tracing::instrument(name = "instruction", skip(self))]
+    /// Execute an instruction taken from the decode cache.
+    ///
+    /// If the previous instruction of the pair wrote to the decoded code, `instruction` may be
+    /// stale, so the one at `$pc` is decoded from memory instead.
+    fn decoded_instruction(&mut self, instruction: Instruction) -> Result<ExecuteState, InterpreterError> {
+        if self.memory.take_code_written() {
+            self.drop_active_code();
+
+            let pc = self.registers[RegId::PC] as usize;
+            let raw = pc
+                .checked_add(Instruction::SIZE)
+                .filter(|end| *end <= self.memory.len())
+                .map(|_| self.memory.read_bytes(pc))
+                .map(RawInstruction::from_be_bytes)
+                .ok_or(InterpreterError::Panic(PanicReason::MemoryOverflow))?;
+
+            return self.instruction(raw);
+        }
+
+        #[cfg(feature = "debug")]
+        {
+            let debug = self.eval_debugger_state();
+            if !debug.should_continue() {
+                return Ok(debug.into());
+            }
+        }
+
+        self._step(instruction)
+            .map_err(|e| InterpreterError::from_runtime(e, instruction.into()))
+    }
+
     fn _instruction(&mut self, raw: RawInstruction) -> Result<ExecuteState, RuntimeError> {
         let instruction = Instruction::try_from(raw).map_err(|_| RuntimeError::from(PanicReason::ErrorFlag))?;
 
+        self._step(instruction)
+    }
+
+    fn _step(&mut self, instruction: Instruction) -> Result<ExecuteState, RuntimeError> {
+        #[cfg(feature = "trace")]
+        if self.is_tracing() {
+            return self.traced_execute(instruction);
+        }
+
+        self._execute(instruction)
+    }
+
+    #[tracing::instrument(name = "instruction", skip(self))]
+    pub(crate) fn _execute(&mut self, instruction: Instruction) -> Result<ExecuteState, RuntimeError> {
         tracing::trace!("Instruction: {:?}", instruction);
 
         // TODO additional branch that might be optimized after
@@ -473,6 +530,12 @@ where
                 self.burn(r!(a))?;
             }
 
+            Instruction::BURNS(burns) => {
+                self.gas_charge(self.gas_costs.burns)?;
+                let (a, b) = burns.unpack();
+                self.burn_sub_id(r!(a), r!(b))?;
+            }
+
             Instruction::CALL(call) => {
                 let (a, b, c, d) = call.unpack();
                 let state = self.call(r!(a), r!(b), r!(c), r!(d))?;
@@ -530,6 +593,12 @@ where
                 self.mint(r!(a))?;
             }
 
+            Instruction::MINTS(mints) => {
+                self.gas_charge(self.gas_costs.mints)?;
+                let (a, b) = mints.unpack();
+                self.mint_sub_id(r!(a), r!(b))?;
+            }
+
             Instruction::SCWQ(scwq) => {
                 self.gas_charge(self.gas_costs.scwq)?;
                 let (a, b, c) = scwq.unpack();
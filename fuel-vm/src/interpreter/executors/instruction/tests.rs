@@ -1,6 +1,6 @@
 use super::*;
 use fuel_asm::PanicReason::ReservedRegisterNotWritable;
-use fuel_asm::{op, Instruction, Opcode};
+use fuel_asm::{op, Instruction};
 
 mod math_operations;
 mod reserved_registers;
@@ -17,8 +17,8 @@ fn cant_write_to_reserved_registers(raw_random_instruction: u32) -> TestResult {
     // ignore if rA/rB isn't set to writeable register and the opcode should write to that register
     let [ra, rb, _, _] = random_instruction.reg_ids();
     match (ra, rb) {
-        (Some(r), _) if writes_to_ra(opcode) && r >= RegId::WRITABLE => return TestResult::discard(),
-        (_, Some(r)) if writes_to_rb(opcode) && r >= RegId::WRITABLE => return TestResult::discard(),
+        (Some(r), _) if opcode.writes_to_ra() && r >= RegId::WRITABLE => return TestResult::discard(),
+        (_, Some(r)) if opcode.writes_to_rb() && r >= RegId::WRITABLE => return TestResult::discard(),
         _ => (),
     }
 
@@ -35,7 +35,7 @@ fn cant_write_to_reserved_registers(raw_random_instruction: u32) -> TestResult {
     vm.init_script(tx).expect("Failed to init VM");
     let res = vm.instruction(raw_random_instruction);
 
-    if writes_to_ra(opcode) || writes_to_rb(opcode) {
+    if opcode.writes_to_ra() || opcode.writes_to_rb() {
         // if this opcode writes to $rA or $rB, expect an error since we're attempting to use a reserved register
         // This assumes that writeable register is validated before other properties of the instruction.
         match res.as_ref().map_err(|e| e.panic_reason()) {
@@ -57,7 +57,7 @@ fn cant_write_to_reserved_registers(raw_random_instruction: u32) -> TestResult {
         Err(InterpreterError::PanicInstruction(r)) if r.reason() == &ReservedRegisterNotWritable
     ) {
         // throw err if a ReservedRegisterNotWritable err was detected outside our writes_to_ra/b check
-        // This would likely happen if the opcode wasn't properly marked as true in `writes_to_ra/b`
+        // This would likely happen if the opcode wasn't properly marked as true in `Opcode::writes_to_ra/b`
         return TestResult::error(format!(
             "unexpected ReservedRegisterNotWritable, test configuration may be faulty {:?}",
             (opcode, &res)
@@ -78,173 +78,3 @@ fn cant_write_to_reserved_registers(raw_random_instruction: u32) -> TestResult {
 
     TestResult::passed()
 }
-
-// determines whether a given opcode stores a value into $rA
-fn writes_to_ra(opcode: Opcode) -> bool {
-    match opcode {
-        Opcode::ADD => true,
-        Opcode::ADDI => true,
-        Opcode::AND => true,
-        Opcode::ANDI => true,
-        Opcode::DIV => true,
-        Opcode::DIVI => true,
-        Opcode::EQ => true,
-        Opcode::EXP => true,
-        Opcode::EXPI => true,
-        Opcode::GT => true,
-        Opcode::LT => true,
-        Opcode::MLOG => true,
-        Opcode::MROO => true,
-        Opcode::MOD => true,
-        Opcode::MODI => true,
-        Opcode::MOVE => true,
-        Opcode::MOVI => true,
-        Opcode::MUL => true,
-        Opcode::MULI => true,
-        Opcode::NOT => true,
-        Opcode::OR => true,
-        Opcode::ORI => true,
-        Opcode::SLL => true,
-        Opcode::SLLI => true,
-        Opcode::SRL => true,
-        Opcode::SRLI => true,
-        Opcode::SUB => true,
-        Opcode::SUBI => true,
-        Opcode::XOR => true,
-        Opcode::XORI => true,
-        Opcode::JI => false,
-        Opcode::JNEI => false,
-        Opcode::JNZI => false,
-        Opcode::JMP => false,
-        Opcode::JNE => false,
-        Opcode::RET => false,
-        Opcode::RETD => false,
-        Opcode::CFEI => false,
-        Opcode::CFSI => false,
-        Opcode::LB => true,
-        Opcode::LW => true,
-        Opcode::ALOC => false,
-        Opcode::MCL => false,
-        Opcode::MCLI => false,
-        Opcode::MCP => false,
-        Opcode::MCPI => false,
-        Opcode::MEQ => true,
-        Opcode::SB => false,
-        Opcode::SW => false,
-        Opcode::BAL => true,
-        Opcode::BHSH => false,
-        Opcode::BHEI => true,
-        Opcode::BURN => false,
-        Opcode::CALL => false,
-        Opcode::CCP => false,
-        Opcode::CROO => false,
-        Opcode::CSIZ => true,
-        Opcode::CB => false,
-        Opcode::LDC => false,
-        Opcode::LOG => false,
-        Opcode::LOGD => false,
-        Opcode::MINT => false,
-        Opcode::RVRT => false,
-        Opcode::SMO => false,
-        Opcode::SCWQ => false,
-        Opcode::SRW => true,
-        Opcode::SRWQ => false,
-        Opcode::SWW => false,
-        Opcode::SWWQ => false,
-        Opcode::TR => false,
-        Opcode::TRO => false,
-        Opcode::ECR => false,
-        Opcode::K256 => false,
-        Opcode::S256 => false,
-        Opcode::NOOP => false,
-        Opcode::FLAG => false,
-        Opcode::GM => true,
-        Opcode::GTF => true,
-        Opcode::TIME => true,
-    }
-}
-
-// determines whether a given opcode stores a value into $rB
-fn writes_to_rb(opcode: Opcode) -> bool {
-    match opcode {
-        Opcode::ADD => false,
-        Opcode::ADDI => false,
-        Opcode::AND => false,
-        Opcode::ANDI => false,
-        Opcode::DIV => false,
-        Opcode::DIVI => false,
-        Opcode::EQ => false,
-        Opcode::EXP => false,
-        Opcode::EXPI => false,
-        Opcode::GT => false,
-        Opcode::LT => false,
-        Opcode::MLOG => false,
-        Opcode::MROO => false,
-        Opcode::MOD => false,
-        Opcode::MODI => false,
-        Opcode::MOVE => false,
-        Opcode::MOVI => false,
-        Opcode::MUL => false,
-        Opcode::MULI => false,
-        Opcode::NOT => false,
-        Opcode::OR => false,
-        Opcode::ORI => false,
-        Opcode::SLL => false,
-        Opcode::SLLI => false,
-        Opcode::SRL => false,
-        Opcode::SRLI => false,
-        Opcode::SUB => false,
-        Opcode::SUBI => false,
-        Opcode::XOR => false,
-        Opcode::XORI => false,
-        Opcode::JI => false,
-        Opcode::JNEI => false,
-        Opcode::JNZI => false,
-        Opcode::JMP => false,
-        Opcode::JNE => false,
-        Opcode::RET => false,
-        Opcode::RETD => false,
-        Opcode::CFEI => false,
-        Opcode::CFSI => false,
-        Opcode::LB => false,
-        Opcode::LW => false,
-        Opcode::ALOC => false,
-        Opcode::MCL => false,
-        Opcode::MCLI => false,
-        Opcode::MCP => false,
-        Opcode::MCPI => false,
-        Opcode::MEQ => false,
-        Opcode::SB => false,
-        Opcode::SW => false,
-        Opcode::BAL => false,
-        Opcode::BHSH => false,
-        Opcode::BHEI => false,
-        Opcode::BURN => false,
-        Opcode::CALL => false,
-        Opcode::CCP => false,
-        Opcode::CROO => false,
-        Opcode::CSIZ => false,
-        Opcode::CB => false,
-        Opcode::LDC => false,
-        Opcode::LOG => false,
-        Opcode::LOGD => false,
-        Opcode::MINT => false,
-        Opcode::RVRT => false,
-        Opcode::SMO => false,
-        Opcode::SCWQ => true,
-        Opcode::SRW => true,
-        Opcode::SRWQ => true,
-        Opcode::SWW => true,
-        Opcode::SWWQ => true,
-        Opcode::TR => false,
-        Opcode::TRO => false,
-        Opcode::ECR => false,
-        Opcode::K256 => false,
-        Opcode::S256 => false,
-        Opcode::NOOP => false,
-        Opcode::FLAG => false,
-        Opcode::GM => false,
-        Opcode::GTF => false,
-        Opcode::TIME => false,
-    }
-}
@@ -51,6 +51,7 @@ where
 
     pub(crate) fn return_from_context(&mut self, receipt: Receipt) -> Result<(), RuntimeError> {
         if let Some(frame) = self.frames.pop() {
+            self.deactivate_code();
             self.registers[RegId::CGAS] = arith::add_word(self.registers[RegId::CGAS], frame.context_gas())?;
 
             let cgas = self.registers[RegId::CGAS];
@@ -93,15 +94,15 @@ where
             return Err(PanicReason::MemoryOverflow.into());
         }
 
-        let ab = (a + b) as usize;
-        let digest = Hasher::hash(&self.memory[a as usize..ab]);
+        let data = self.memory.read(a as usize..(a + b) as usize).into_owned();
+        let digest = Hasher::hash(&data);
 
         let receipt = Receipt::return_data_with_len(
             self.internal_contract_or_default(),
             a,
             b,
             digest,
-            self.memory[a as usize..ab].to_vec(),
+            data,
             self.registers[RegId::PC],
             self.registers[RegId::IS],
         );
@@ -157,9 +158,9 @@ where
             return Err(PanicReason::MemoryOverflow.into());
         }
 
-        let call = Call::try_from(&self.memory[a as usize..])?;
-        let asset_id =
-            AssetId::try_from(&self.memory[c as usize..cx as usize]).expect("Unreachable! Checked memory range");
+        let call_end = cmp::min(a as usize + Call::LEN, self.memory.len());
+        let call = Call::try_from(&*self.memory.read(a as usize..call_end))?;
+        let asset_id = AssetId::new(self.memory.read_bytes(c as usize));
 
         let mut frame = self.call_frame(call, asset_id)?;
 
@@ -168,7 +169,7 @@ where
         if self.is_external_context() {
             self.external_asset_id_balance_sub(&asset_id, b)?;
         } else {
-            let source_contract = *self.internal_contract()?;
+            let source_contract = self.internal_contract()?;
             self.balance_decrease(&source_contract, &asset_id, b)?;
         }
 
@@ -207,22 +208,22 @@ where
         self.registers[RegId::SSP] = self.registers[RegId::SP];
 
         let fpx = arith::add_word(self.registers[RegId::FP], frame_bytes.len() as Word)?;
-        self.memory[self.registers[RegId::FP] as usize..fpx as usize].copy_from_slice(frame_bytes.as_slice());
+        self.memory
+            .write(self.registers[RegId::FP] as usize, frame_bytes.as_slice());
 
-        let code_range = (fpx as usize)..arith::add_usize(fpx as usize, frame.code_size() as usize);
+        let mut code = vec![0; frame.code_size() as usize];
         let bytes_read = self
             .storage
-            .read(frame.to(), &mut self.memory[code_range.clone()])
+            .read(frame.to(), &mut code)
             .map_err(RuntimeError::from_io)?
             .ok_or(PanicReason::ContractNotFound)?;
         if bytes_read as Word != frame.code_size() {
             return Err(PanicReason::ContractNotFound.into());
         }
-        let pad_len = frame.code_size_padding();
-        if pad_len > 0 {
-            self.memory[code_range.end..code_range.end + pad_len as usize]
-                .copy_from_slice(&[0; 32][..pad_len as usize]);
-        }
+        // The padding is zeroed along with the code
+        code.resize(code.len() + frame.code_size_padding() as usize, 0);
+        let code_range = (fpx as usize)..arith::add_usize(fpx as usize, frame.code_size() as usize);
+        self.memory.write(code_range.start, &code);
 
         self.registers[RegId::BAL] = b;
         self.registers[RegId::PC] = fpx;
@@ -243,6 +244,7 @@ where
 
         self.append_receipt(receipt);
 
+        self.activate_code(frame.to(), code_range)?;
         self.frames.push(frame);
 
         Ok(())
@@ -281,6 +283,10 @@ where
 
     pub(crate) fn call(&mut self, a: Word, b: Word, c: Word, d: Word) -> Result<ProgramState, RuntimeError> {
         self._prepare_call(a, b, c, d)?;
+
+        #[cfg(feature = "trace")]
+        self.end_step(None);
+
         self.run_call()
     }
 }
@@ -27,8 +27,12 @@ where
         self.initial_balances = initial_balances.clone();
 
         self.frames.clear();
+        self.active_code.clear();
         self.receipts.clear();
 
+        // Leftovers of a previous transaction must not be observable
+        self.memory.reset();
+
         // Optimized for memset
         self.registers.iter_mut().for_each(|r| *r = 0);
 
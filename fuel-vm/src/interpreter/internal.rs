@@ -7,7 +7,7 @@ use crate::error::RuntimeError;
 use fuel_asm::{Instruction, PanicReason, RegId};
 use fuel_tx::field::ReceiptsRoot;
 use fuel_tx::{Output, Receipt};
-use fuel_types::bytes::SerializableVec;
+use fuel_types::bytes::{SerializableVec, SizedBytes};
 use fuel_types::{AssetId, Bytes32, ContractId, RegisterId, Word};
 
 use core::mem;
@@ -39,10 +39,16 @@ where
                 .outputs_offset_at(idx)
                 .ok_or(PanicReason::OutputNotFound)?;
 
-        let tx = &mut self.tx;
-        let mem = &mut self.memory[offset..];
+        let len = self
+            .transaction()
+            .outputs()
+            .get(idx)
+            .ok_or(PanicReason::OutputNotFound)?
+            .serialized_size();
 
-        tx.output_to_mem(idx, mem)?;
+        let mut mem = vec![0; len];
+        self.tx.output_to_mem(idx, &mut mem)?;
+        self.memory.write(offset, &mem);
 
         Ok(())
     }
@@ -68,7 +74,7 @@ where
 
             // Transaction memory space length is already checked on initialization so its
             // guaranteed to fit
-            self.memory[offset..offset + Bytes32::LEN].copy_from_slice(&root[..]);
+            self.memory.write(offset, &root[..]);
         }
     }
 }
@@ -87,7 +93,7 @@ impl<S, Tx> Interpreter<S, Tx> {
     pub(crate) fn push_stack(&mut self, data: &[u8]) -> Result<(), RuntimeError> {
         let ssp = self.reserve_stack(data.len() as Word)?;
 
-        self.memory[ssp as usize..self.registers[RegId::SSP] as usize].copy_from_slice(data);
+        self.memory.write(ssp as usize, data);
 
         Ok(())
     }
@@ -134,20 +140,14 @@ impl<S, Tx> Interpreter<S, Tx> {
         is_register_writable(ra)
     }
 
-    pub(crate) fn internal_contract(&self) -> Result<&ContractId, RuntimeError> {
-        let (c, cx) = self.internal_contract_bounds()?;
+    pub(crate) fn internal_contract(&self) -> Result<ContractId, RuntimeError> {
+        let (c, _) = self.internal_contract_bounds()?;
 
-        // Safety: Memory bounds logically verified by the interpreter
-        let contract = unsafe { ContractId::as_ref_unchecked(&self.memory[c..cx]) };
-
-        Ok(contract)
+        Ok(ContractId::new(self.memory.read_bytes(c)))
     }
 
     pub(crate) fn internal_contract_or_default(&self) -> ContractId {
-        // Safety: memory bounds checked by `internal_contract_bounds`
-        self.internal_contract_bounds()
-            .map(|(c, cx)| unsafe { ContractId::from_slice_unchecked(&self.memory[c..cx]) })
-            .unwrap_or_default()
+        self.internal_contract().unwrap_or_default()
     }
 
     pub(crate) fn internal_contract_bounds(&self) -> Result<(usize, usize), RuntimeError> {
@@ -186,9 +186,9 @@ impl<S, Tx> Interpreter<S, Tx> {
         self.params().tx_offset()
     }
 
-    pub(crate) fn tx_id(&self) -> &Bytes32 {
-        // Safety: vm parameters guarantees enough space for txid
-        unsafe { Bytes32::as_ref_unchecked(&self.memory[..Bytes32::LEN]) }
+    pub(crate) fn tx_id(&self) -> Bytes32 {
+        // vm parameters guarantees enough space for txid
+        Bytes32::new(self.memory.read_bytes(0))
     }
 
     pub(crate) fn set_frame_pointer(&mut self, fp: Word) {
@@ -309,7 +309,9 @@ mod tests {
         // verify the vm memory is updated properly
         let position = vm.tx_offset() + vm.transaction().outputs_offset_at(0).unwrap();
         let mut mem_output = Output::variable(Default::default(), Default::default(), Default::default());
-        let _ = mem_output.write(&vm.memory()[position..]).unwrap();
+        let _ = mem_output
+            .write(&vm.memory().read(position..vm.memory().len()))
+            .unwrap();
         assert_eq!(vm.transaction().outputs()[0], mem_output);
     }
 }
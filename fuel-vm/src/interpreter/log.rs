@@ -32,8 +32,8 @@ where
             return Err(PanicReason::MemoryOverflow.into());
         }
 
-        let cd = (c + d) as usize;
-        let digest = Hasher::hash(&self.memory[c as usize..cd]);
+        let data = self.memory.read(c as usize..(c + d) as usize).into_owned();
+        let digest = Hasher::hash(&data);
 
         let receipt = Receipt::log_data_with_len(
             self.internal_contract_or_default(),
@@ -42,7 +42,7 @@ where
             c,
             d,
             digest,
-            self.memory[c as usize..cd].to_vec(),
+            data,
             self.registers[RegId::PC],
             self.registers[RegId::IS],
         );
@@ -5,7 +5,11 @@ use crate::{consts::*, context::Context};
 use fuel_asm::{PanicReason, RegId};
 use fuel_types::{RegisterId, Word};
 
-use std::{ops, ptr};
+use std::ops;
+
+mod paged;
+
+pub use paged::Memory;
 
 #[allow(clippy::derive_hash_xor_eq)]
 #[derive(Debug, Clone, Eq, Hash)]
@@ -272,10 +276,7 @@ where
         if overflow || bcw > VM_MAX_RAM as RegisterId {
             Err(PanicReason::MemoryOverflow.into())
         } else {
-            // Safe conversion of sized slice
-            self.registers[ra] = <[u8; 8]>::try_from(&self.memory[bc..bcw])
-                .map(Word::from_be_bytes)
-                .unwrap_or_else(|_| unreachable!());
+            self.registers[ra] = Word::from_be_bytes(self.memory.read_bytes(bc));
 
             self.inc_pc()
         }
@@ -303,7 +304,7 @@ where
         if overflow || acw > VM_MAX_RAM || !self.has_ownership_range(&range) {
             Err(PanicReason::MemoryOverflow.into())
         } else {
-            self.memory[ac as usize..acw as usize].copy_from_slice(&b.to_be_bytes());
+            self.memory.write(ac as usize, &b.to_be_bytes());
 
             self.inc_pc()
         }
@@ -328,10 +329,7 @@ where
         if overflow || ab > VM_MAX_RAM || b > MEM_MAX_ACCESS_SIZE || !self.has_ownership_range(&range) {
             Err(PanicReason::MemoryOverflow.into())
         } else {
-            // trivial compiler optimization for memset
-            for i in &mut self.memory[a as usize..ab as usize] {
-                *i = 0
-            }
+            self.memory.fill(a as usize..ab as usize, 0);
 
             self.inc_pc()
         }
@@ -353,14 +351,7 @@ where
         {
             Err(PanicReason::MemoryOverflow.into())
         } else {
-            // The pointers are granted to be aligned so this is a safe
-            // operation
-            let src = &self.memory[b as usize] as *const u8;
-            let dst = &mut self.memory[a as usize] as *mut u8;
-
-            unsafe {
-                ptr::copy_nonoverlapping(src, dst, c as usize);
-            }
+            self.memory.copy_within(b as usize, a as usize, c as usize);
 
             self.inc_pc()
         }
@@ -376,7 +367,8 @@ where
         if overflow || bd > VM_MAX_RAM || cd > VM_MAX_RAM || d > MEM_MAX_ACCESS_SIZE {
             Err(PanicReason::MemoryOverflow.into())
         } else {
-            self.registers[ra] = (self.memory[b as usize..bd as usize] == self.memory[c as usize..cd as usize]) as Word;
+            self.registers[ra] =
+                (self.memory.read(b as usize..bd as usize) == self.memory.read(c as usize..cd as usize)) as Word;
 
             self.inc_pc()
         }
@@ -451,7 +443,7 @@ fn try_mem_write(
     addr: usize,
     data: &[u8],
     registers: OwnershipRegisters,
-    memory: &mut [u8],
+    memory: &mut Memory,
 ) -> Result<(), RuntimeError> {
     let ax = addr.checked_add(data.len()).ok_or(PanicReason::ArithmeticOverflow)?;
 
@@ -461,18 +453,16 @@ fn try_mem_write(
 
     registers
         .has_ownership_range(&range)
-        .then(|| {
-            let src = data.as_ptr();
-            let dst = &mut memory[addr] as *mut u8;
-
-            unsafe {
-                ptr::copy_nonoverlapping(src, dst, data.len());
-            }
-        })
+        .then(|| memory.write(addr, data))
         .ok_or_else(|| PanicReason::MemoryOwnership.into())
 }
 
-fn try_zeroize(addr: usize, len: usize, registers: OwnershipRegisters, memory: &mut [u8]) -> Result<(), RuntimeError> {
+fn try_zeroize(
+    addr: usize,
+    len: usize,
+    registers: OwnershipRegisters,
+    memory: &mut Memory,
+) -> Result<(), RuntimeError> {
     let ax = addr.checked_add(len).ok_or(PanicReason::ArithmeticOverflow)?;
 
     let range = (ax <= VM_MAX_RAM as usize)
@@ -481,9 +471,7 @@ fn try_zeroize(addr: usize, len: usize, registers: OwnershipRegisters, memory: &
 
     registers
         .has_ownership_range(&range)
-        .then(|| {
-            memory[addr..].iter_mut().take(len).for_each(|m| *m = 0);
-        })
+        .then(|| memory.fill(addr..ax, 0))
         .ok_or_else(|| PanicReason::MemoryOwnership.into())
 }
 
@@ -707,9 +695,9 @@ mod tests {
         => (false, [0u8; 100]); "Internal too large for heap"
     )]
     fn test_mem_write(addr: usize, data: &[u8], registers: OwnershipRegisters) -> (bool, [u8; 100]) {
-        let mut memory = [0u8; 100];
-        let r = try_mem_write(addr, data, registers, &mut memory[..]).is_ok();
-        (r, memory)
+        let mut memory: Memory = vec![0u8; 100].into();
+        let r = try_mem_write(addr, data, registers, &mut memory).is_ok();
+        (r, memory.read_bytes(0))
     }
 
     #[test_case(
@@ -758,8 +746,8 @@ mod tests {
         => (false, [1u8; 100]); "Internal too large for heap"
     )]
     fn test_try_zeroize(addr: usize, len: usize, registers: OwnershipRegisters) -> (bool, [u8; 100]) {
-        let mut memory = [1u8; 100];
-        let r = try_zeroize(addr, len, registers, &mut memory[..]).is_ok();
-        (r, memory)
+        let mut memory: Memory = vec![1u8; 100].into();
+        let r = try_zeroize(addr, len, registers, &mut memory).is_ok();
+        (r, memory.read_bytes(0))
     }
 }
@@ -0,0 +1,525 @@
+use crate::consts::*;
+
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::{fmt, iter, ops};
+
+type Page = [u8; VM_MEMORY_PAGE_SIZE];
+
+static ZERO_PAGE: Page = [0; VM_MEMORY_PAGE_SIZE];
+
+/// VM memory of [`VM_MAX_RAM`] bytes for an interpreter, divided in pages of
+/// [`VM_MEMORY_PAGE_SIZE`] bytes.
+///
+/// A page is only allocated when it is first written to; until then it reads as zeroes. Pages
+/// are shared between clones and copied on write, so cloning an interpreter costs a pointer per
+/// page, and a clone only copies the pages it writes to.
+///
+/// Since the pages aren't contiguous, reads go through [`Memory::read`],
+/// [`Memory::read_bytes`] or [`Memory::chunks`], while writes go through [`Memory::write`],
+/// [`Memory::fill`] and [`Memory::copy_within`] so they are accounted for.
+#[derive(Clone)]
+pub struct Memory {
+    len: usize,
+    pages: Vec<Option<Arc<Page>>>,
+    /// Ranges holding decoded contract code, see [`crate::interpreter::DecodeCache`].
+    code: Vec<ops::Range<usize>>,
+    code_written: bool,
+    /// Ranges written while logging writes for the execution trace.
+    #[cfg(feature = "trace")]
+    writes: Option<Vec<ops::Range<usize>>>,
+}
+
+impl Memory {
+    /// Create a new, zeroed memory of [`VM_MAX_RAM`] bytes with no allocated pages.
+    pub fn new() -> Self {
+        Self::zeroed(VM_MAX_RAM as usize)
+    }
+
+    fn zeroed(len: usize) -> Self {
+        Self {
+            len,
+            pages: vec![None; num_integer::div_ceil(len, VM_MEMORY_PAGE_SIZE)],
+            code: vec![],
+            code_written: false,
+            #[cfg(feature = "trace")]
+            writes: None,
+        }
+    }
+
+    /// The number of bytes of the memory.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return `true` if the memory holds no bytes.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of pages of the memory.
+    pub fn pages(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Check if the page at the given index was written to, and so is allocated.
+    pub fn is_dirty(&self, page: usize) -> bool {
+        self.pages.get(page).map_or(false, Option::is_some)
+    }
+
+    /// Iterate over the indices of the pages that were written to, in ascending order.
+    pub fn dirty_pages(&self) -> impl Iterator<Item = usize> + '_ {
+        self.pages
+            .iter()
+            .enumerate()
+            .filter_map(|(page, bytes)| bytes.as_ref().map(|_| page))
+    }
+
+    /// The byte range covered by the page at the given index.
+    pub fn page_range(&self, page: usize) -> ops::Range<usize> {
+        let start = (page * VM_MEMORY_PAGE_SIZE).min(self.len);
+        let end = start.saturating_add(VM_MEMORY_PAGE_SIZE).min(self.len);
+
+        start..end
+    }
+
+    /// The contents of `range`, split at page boundaries.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the range is out of bounds, like slice indexing.
+    pub fn chunks(&self, range: ops::Range<usize>) -> impl Iterator<Item = &[u8]> + '_ {
+        self.check_bounds(&range);
+
+        let mut start = range.start;
+        iter::from_fn(move || {
+            if start >= range.end {
+                return None;
+            }
+
+            let page = start / VM_MEMORY_PAGE_SIZE;
+            let offset = start % VM_MEMORY_PAGE_SIZE;
+            let len = (VM_MEMORY_PAGE_SIZE - offset).min(range.end - start);
+            let bytes = self.pages[page].as_deref().unwrap_or(&ZERO_PAGE);
+
+            start += len;
+            Some(&bytes[offset..offset + len])
+        })
+    }
+
+    /// The contents of `range`, borrowed if it spans a single page.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the range is out of bounds, like slice indexing.
+    pub fn read(&self, range: ops::Range<usize>) -> Cow<'_, [u8]> {
+        let mut chunks = self.chunks(range.clone());
+
+        match (chunks.next(), chunks.next()) {
+            (None, _) => Cow::Borrowed(&[]),
+            (Some(chunk), None) => Cow::Borrowed(chunk),
+            (Some(first), Some(second)) => {
+                let mut bytes = Vec::with_capacity(range.len());
+                bytes.extend_from_slice(first);
+                bytes.extend_from_slice(second);
+                chunks.for_each(|chunk| bytes.extend_from_slice(chunk));
+
+                Cow::Owned(bytes)
+            }
+        }
+    }
+
+    /// Copy the `N` bytes starting at `addr`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the range is out of bounds, like slice indexing.
+    pub fn read_bytes<const N: usize>(&self, addr: usize) -> [u8; N] {
+        let mut bytes = [0u8; N];
+        self.read_into(addr, &mut bytes);
+        bytes
+    }
+
+    /// Fill `buf` with the bytes starting at `addr`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the range is out of bounds, like slice indexing.
+    pub fn read_into(&self, addr: usize, buf: &mut [u8]) {
+        let mut offset = 0;
+
+        for chunk in self.chunks(addr..addr.saturating_add(buf.len())) {
+            buf[offset..offset + chunk.len()].copy_from_slice(chunk);
+            offset += chunk.len();
+        }
+    }
+
+    /// Iterate over every byte of the memory.
+    pub fn iter(&self) -> impl Iterator<Item = &u8> + '_ {
+        self.chunks(0..self.len).flatten()
+    }
+
+    /// Copy the whole memory into a contiguous buffer.
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.read(0..self.len).into_owned()
+    }
+
+    /// Copy `data` to the memory starting at `addr`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the range is out of bounds, like slice indexing.
+    pub fn write(&mut self, addr: usize, data: &[u8]) {
+        self.mark_written(&(addr..addr.saturating_add(data.len())));
+        self.write_pages(addr, data);
+    }
+
+    /// Copy `data` to the pages starting at `addr`, without accounting for the write.
+    fn write_pages(&mut self, addr: usize, data: &[u8]) {
+        let mut data = data;
+        self.for_each_page(addr..addr.saturating_add(data.len()), |page, bytes| {
+            let (chunk, rest) = data.split_at(bytes.len());
+            Self::page_mut(page)[bytes].copy_from_slice(chunk);
+            data = rest;
+        });
+    }
+
+    /// Set every byte of `range` to `value`.
+    ///
+    /// Zeroing a whole page releases it.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the range is out of bounds, like slice indexing.
+    pub fn fill(&mut self, range: ops::Range<usize>, value: u8) {
+        self.mark_written(&range);
+
+        self.for_each_page(range, |page, bytes| match page {
+            None if value == 0 => (),
+            Some(_) if value == 0 && bytes.len() == VM_MEMORY_PAGE_SIZE => *page = None,
+            _ => Self::page_mut(page)[bytes].fill(value),
+        });
+    }
+
+    /// Copy `len` bytes from `src` to `dst`, with the semantics of [`slice::copy_within`].
+    ///
+    /// The bytes go through a buffer of a single page, copied backwards when the destination
+    /// overlaps the end of the source.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if either range is out of bounds, like slice indexing.
+    pub fn copy_within(&mut self, src: usize, dst: usize, len: usize) {
+        let range = dst..dst.saturating_add(len);
+        self.check_bounds(&(src..src.saturating_add(len)));
+        self.check_bounds(&range);
+        self.mark_written(&range);
+
+        let mut buf = ZERO_PAGE;
+        let copy = |offset: usize| {
+            let buf = &mut buf[..(len - offset).min(VM_MEMORY_PAGE_SIZE)];
+            self.read_into(src + offset, buf);
+            self.write_pages(dst + offset, buf);
+        };
+
+        let offsets = (0..len).step_by(VM_MEMORY_PAGE_SIZE);
+        if dst > src {
+            offsets.rev().for_each(copy);
+        } else {
+            offsets.for_each(copy);
+        }
+    }
+
+    /// Release every page, restoring the memory to its initial state.
+    ///
+    /// The cost is proportional to the number of pages, not to the memory touched.
+    pub fn reset(&mut self) {
+        self.pages.iter_mut().for_each(|page| *page = None);
+        self.unwatch_all_code();
+    }
+
+    /// Watch writes to a range holding decoded code.
+    pub(crate) fn watch_code(&mut self, range: ops::Range<usize>) {
+        self.code.push(range);
+    }
+
+    /// Stop watching the most recently watched code range.
+    pub(crate) fn unwatch_code(&mut self) {
+        self.code.pop();
+    }
+
+    /// Stop watching every code range.
+    pub(crate) fn unwatch_all_code(&mut self) {
+        self.code.clear();
+        self.code_written = false;
+    }
+
+    /// Return `true` if a watched code range was written to since the last call.
+    pub(crate) fn take_code_written(&mut self) -> bool {
+        core::mem::take(&mut self.code_written)
+    }
+
+    /// Start logging the ranges written to, until [`Self::take_writes`].
+    #[cfg(feature = "trace")]
+    pub(crate) fn log_writes(&mut self) {
+        self.writes = Some(vec![]);
+    }
+
+    /// Stop logging writes, returning the ranges written to since [`Self::log_writes`].
+    #[cfg(feature = "trace")]
+    pub(crate) fn take_writes(&mut self) -> Vec<ops::Range<usize>> {
+        self.writes.take().unwrap_or_default()
+    }
+
+    fn check_bounds(&self, range: &ops::Range<usize>) {
+        assert!(
+            range.start <= range.end && range.end <= self.len,
+            "memory range {range:?} out of bounds of {}",
+            self.len
+        );
+    }
+
+    /// Call `f` with every page spanned by `range` and the part of the page it covers.
+    fn for_each_page<F>(&mut self, range: ops::Range<usize>, mut f: F)
+    where
+        F: FnMut(&mut Option<Arc<Page>>, ops::Range<usize>),
+    {
+        self.check_bounds(&range);
+
+        let mut start = range.start;
+        while start < range.end {
+            let page = start / VM_MEMORY_PAGE_SIZE;
+            let offset = start % VM_MEMORY_PAGE_SIZE;
+            let len = (VM_MEMORY_PAGE_SIZE - offset).min(range.end - start);
+
+            f(&mut self.pages[page], offset..offset + len);
+            start += len;
+        }
+    }
+
+    /// Allocate the page if it wasn't written to yet, or copy it if it is shared with a clone.
+    fn page_mut(page: &mut Option<Arc<Page>>) -> &mut Page {
+        Arc::make_mut(page.get_or_insert_with(|| Arc::new(ZERO_PAGE)))
+    }
+
+    fn mark_written(&mut self, range: &ops::Range<usize>) {
+        if range.is_empty() {
+            return;
+        }
+
+        #[cfg(feature = "trace")]
+        if let Some(writes) = &mut self.writes {
+            writes.push(range.start..range.end.min(self.len));
+        }
+
+        if self
+            .code
+            .iter()
+            .any(|code| range.start < code.end && code.start < range.end)
+        {
+            self.code_written = true;
+        }
+    }
+}
+
+impl Default for Memory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Vec<u8>> for Memory {
+    /// Wrap arbitrary contents, with all pages allocated.
+    fn from(bytes: Vec<u8>) -> Self {
+        let mut memory = Self::zeroed(bytes.len());
+        memory
+            .pages
+            .iter_mut()
+            .zip(bytes.chunks(VM_MEMORY_PAGE_SIZE))
+            .for_each(|(page, chunk)| Self::page_mut(page)[..chunk.len()].copy_from_slice(chunk));
+        memory
+    }
+}
+
+impl fmt::Debug for Memory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Memory")
+            .field("len", &self.len)
+            .field("dirty_pages", &self.dirty_pages().count())
+            .finish()
+    }
+}
+
+impl PartialEq for Memory {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+            && self.pages.iter().zip(&other.pages).all(|(a, b)| match (a, b) {
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b) || a == b,
+                (Some(page), None) | (None, Some(page)) => **page == ZERO_PAGE,
+                (None, None) => true,
+            })
+    }
+}
+
+impl Eq for Memory {}
+
+impl ops::Index<usize> for Memory {
+    type Output = u8;
+
+    fn index(&self, index: usize) -> &u8 {
+        self.check_bounds(&(index..index.saturating_add(1)));
+
+        let bytes = self.pages[index / VM_MEMORY_PAGE_SIZE].as_deref().unwrap_or(&ZERO_PAGE);
+
+        &bytes[index % VM_MEMORY_PAGE_SIZE]
+    }
+}
+
+impl ops::IndexMut<usize> for Memory {
+    fn index_mut(&mut self, index: usize) -> &mut u8 {
+        let range = index..index.saturating_add(1);
+        self.check_bounds(&range);
+        self.mark_written(&range);
+
+        &mut Self::page_mut(&mut self.pages[index / VM_MEMORY_PAGE_SIZE])[index % VM_MEMORY_PAGE_SIZE]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pages_are_allocated_on_first_write() {
+        let mut memory = Memory::new();
+        assert_eq!(memory.dirty_pages().count(), 0);
+        assert_eq!(memory[VM_MEMORY_PAGE_SIZE * 3], 0);
+        assert!(memory.read(10..VM_MEMORY_PAGE_SIZE * 2).iter().all(|b| *b == 0));
+
+        memory[10] = 1;
+        memory.write(VM_MEMORY_PAGE_SIZE - 1, &[2, 3]);
+        memory.copy_within(0, 5 * VM_MEMORY_PAGE_SIZE, 16);
+        memory[VM_MAX_RAM as usize - 1] = 4;
+        memory.fill(0..0, 5);
+        memory.fill(7 * VM_MEMORY_PAGE_SIZE..8 * VM_MEMORY_PAGE_SIZE, 0);
+
+        let last = memory.pages() - 1;
+        assert_eq!(memory.dirty_pages().collect::<Vec<_>>(), vec![0, 1, 5, last]);
+        assert!(memory.is_dirty(5));
+        assert!(!memory.is_dirty(4));
+        assert!(!memory.is_dirty(last + 1));
+        assert_eq!(
+            memory.read(VM_MEMORY_PAGE_SIZE - 1..VM_MEMORY_PAGE_SIZE + 1).as_ref(),
+            &[2, 3]
+        );
+        assert_eq!(memory.read_bytes::<2>(5 * VM_MEMORY_PAGE_SIZE + 10), [1, 0]);
+    }
+
+    #[test]
+    fn reads_span_pages() {
+        let mut memory = Memory::new();
+        let data: Vec<u8> = (0..=255).cycle().take(3 * VM_MEMORY_PAGE_SIZE).collect();
+        memory.write(100, &data);
+
+        let range = 100..100 + data.len();
+        assert!(matches!(memory.read(range.clone()), Cow::Owned(bytes) if bytes == data));
+        assert!(matches!(memory.read(100..200), Cow::Borrowed(bytes) if bytes == &data[..100]));
+        assert_eq!(memory.chunks(range).count(), 4);
+        assert_eq!(
+            memory.read_bytes::<4>(VM_MEMORY_PAGE_SIZE - 2),
+            [
+                data[VM_MEMORY_PAGE_SIZE - 102],
+                data[VM_MEMORY_PAGE_SIZE - 101],
+                data[VM_MEMORY_PAGE_SIZE - 100],
+                data[VM_MEMORY_PAGE_SIZE - 99]
+            ]
+        );
+    }
+
+    #[test]
+    fn overlapping_copies_span_pages() {
+        let data: Vec<u8> = (0..=255).cycle().take(5 * VM_MEMORY_PAGE_SIZE).collect();
+        let len = 3 * VM_MEMORY_PAGE_SIZE + 10;
+
+        for (src, dst) in [(100, 1_000), (1_000, 100), (7, 7), (VM_MEMORY_PAGE_SIZE - 3, 0)] {
+            let mut memory = Memory::from(data.clone());
+            let mut expected = data.clone();
+
+            memory.copy_within(src, dst, len);
+            expected.copy_within(src..src + len, dst);
+            assert_eq!(memory.to_vec(), expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn reads_out_of_bounds_panic() {
+        Memory::from(vec![0; 10]).read(5..11);
+    }
+
+    #[test]
+    fn clones_share_pages_until_written() {
+        let mut memory = Memory::new();
+        memory.write(3 * VM_MEMORY_PAGE_SIZE + 7, &[1, 2, 3]);
+        memory.write(0, &[4, 5, 6, 7]);
+
+        let mut clone = memory.clone();
+        assert_eq!(memory, clone);
+        assert!(memory.pages.iter().zip(&clone.pages).all(|(a, b)| match (a, b) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (a, b) => a.is_none() && b.is_none(),
+        }));
+
+        clone[0] = 0;
+        assert_ne!(memory, clone);
+        assert_eq!(memory[0], 4);
+        assert!(!Arc::ptr_eq(
+            memory.pages[0].as_ref().unwrap(),
+            clone.pages[0].as_ref().unwrap()
+        ));
+        assert!(Arc::ptr_eq(
+            memory.pages[3].as_ref().unwrap(),
+            clone.pages[3].as_ref().unwrap()
+        ));
+    }
+
+    #[test]
+    fn reset_releases_pages() {
+        let mut memory = Memory::new();
+        memory.fill(VM_MEMORY_PAGE_SIZE * 7..VM_MEMORY_PAGE_SIZE * 9, 0xff);
+        memory[42] = 1;
+
+        memory.reset();
+        assert_eq!(memory.dirty_pages().count(), 0);
+        assert_eq!(memory, Memory::new());
+    }
+
+    #[test]
+    fn writes_to_watched_code_are_reported() {
+        let mut memory = Memory::new();
+        memory.watch_code(100..200);
+
+        memory.fill(0..100, 1);
+        memory.copy_within(0, 200, 8);
+        assert!(!memory.take_code_written());
+
+        memory.copy_within(0, 196, 8);
+        assert!(memory.take_code_written());
+        assert!(!memory.take_code_written());
+
+        memory.unwatch_code();
+        memory[150] = 1;
+        assert!(!memory.take_code_written());
+    }
+
+    #[test]
+    fn from_vec_allocates_all_pages() {
+        let memory = Memory::from(vec![1; VM_MEMORY_PAGE_SIZE + 1]);
+
+        assert_eq!(memory.pages(), 2);
+        assert_eq!(memory.dirty_pages().collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(memory.page_range(1), VM_MEMORY_PAGE_SIZE..VM_MEMORY_PAGE_SIZE + 1);
+        assert_eq!(memory.to_vec(), vec![1; VM_MEMORY_PAGE_SIZE + 1]);
+        assert_eq!(memory.clone(), memory);
+    }
+}
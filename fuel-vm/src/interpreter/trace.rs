@@ -0,0 +1,124 @@
+use super::{ExecutableTransaction, Interpreter};
+use crate::error::RuntimeError;
+use crate::state::ExecuteState;
+use crate::storage::InterpreterStorage;
+use crate::trace::{MemoryWrite, RegisterChange, StepStart, StorageAccess, TraceStep};
+
+use fuel_asm::{Instruction, PanicReason, RegId};
+use fuel_types::Word;
+
+use std::ops::Range;
+
+impl<S, Tx> Interpreter<S, Tx> {
+    /// Return `true` if a tracer is set.
+    pub(crate) const fn is_tracing(&self) -> bool {
+        self.trace.is_enabled()
+    }
+
+    /// Record a storage access of the instruction being executed, if a tracer is set.
+    pub(crate) fn trace_storage(&mut self, access: impl FnOnce() -> StorageAccess) {
+        if self.trace.is_enabled() {
+            self.trace.record_storage(access());
+        }
+    }
+}
+
+impl<S, Tx> Interpreter<S, Tx>
+where
+    S: InterpreterStorage,
+    Tx: ExecutableTransaction,
+{
+    /// Execute an instruction and report its effects to the tracer.
+    pub(crate) fn traced_execute(&mut self, instruction: Instruction) -> Result<ExecuteState, RuntimeError> {
+        self.trace.begin_step(StepStart {
+            pc: self.registers[RegId::PC],
+            depth: self.frames.len(),
+            instruction,
+            registers: self.registers,
+        });
+        self.memory.log_writes();
+
+        let result = self._execute(instruction);
+
+        self.end_step(match &result {
+            Err(RuntimeError::Recoverable(reason)) => Some(*reason),
+            _ => None,
+        });
+
+        result
+    }
+}
+
+impl<S, Tx> Interpreter<S, Tx> {
+    /// Report the instruction being traced, if it wasn't already.
+    ///
+    /// `CALL` ends its step once the frame is set up, so that the step precedes the ones of the
+    /// callee.
+    pub(crate) fn end_step(&mut self, panic: Option<PanicReason>) {
+        let start = match self.trace.take_start() {
+            Some(start) => start,
+            None => return,
+        };
+
+        let writes = merge_ranges(self.memory.take_writes());
+
+        let step = TraceStep {
+            pc: start.pc,
+            depth: start.depth,
+            instruction: start.instruction,
+            registers: start
+                .registers
+                .iter()
+                .zip(self.registers.iter())
+                .enumerate()
+                .filter(|(_, (before, after))| before != after)
+                .map(|(register, (_, value))| RegisterChange {
+                    register,
+                    value: *value,
+                })
+                .collect(),
+            memory: writes
+                .into_iter()
+                .map(|range| MemoryWrite {
+                    address: range.start as Word,
+                    data: self.memory.read(range).into_owned(),
+                })
+                .collect(),
+            storage: self.trace.take_storage(),
+            gas_before: start.registers[RegId::GGAS],
+            gas_after: self.registers[RegId::GGAS],
+            panic,
+        };
+
+        self.trace.on_step(&step);
+    }
+}
+
+/// Sort the ranges and merge the overlapping and adjacent ones.
+fn merge_ranges(mut ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    ranges.sort_by_key(|range| range.start);
+
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_ranges_joins_overlapping_and_adjacent() {
+        assert_eq!(
+            merge_ranges(vec![8..16, 0..4, 2..6, 16..20, 30..32]),
+            vec![0..6, 8..20, 30..32]
+        );
+        assert!(merge_ranges(vec![]).is_empty());
+    }
+}
@@ -22,6 +22,9 @@ pub mod util;
 #[cfg(feature = "profile-any")]
 pub mod profiler;
 
+#[cfg(feature = "trace")]
+pub mod trace;
+
 // Fully re-export fuel dependencies
 #[doc(no_inline)]
 pub use fuel_asm;
@@ -59,12 +62,19 @@ pub mod prelude {
     pub use crate::error::{Bug, BugId, BugVariant, Infallible, InterpreterError, RuntimeError};
     pub use crate::gas::GasCosts;
     pub use crate::gas::GasCostsValues;
-    pub use crate::interpreter::{ExecutableTransaction, Interpreter, MemoryRange};
+    pub use crate::interpreter::{DecodeCache, ExecutableTransaction, Interpreter, MemoryRange};
     pub use crate::memory_client::MemoryClient;
     pub use crate::predicate::RuntimePredicate;
     pub use crate::state::{Debugger, ProgramState, StateTransition, StateTransitionRef};
-    pub use crate::storage::{InterpreterStorage, MemoryStorage, PredicateStorage};
-    pub use crate::transactor::Transactor;
+    pub use crate::storage::{
+        BlockInfo, ForkStorage, InterpreterStorage, MemoryStorage, OverlayStorage, PredicateStorage, ProvingStorage,
+        SlotProof, StateFetcher, StorageProofs, StorageProver, StorageSnapshot, VerifierStorage, WitnessBundle,
+        WitnessRecorder, WitnessStorage,
+    };
+
+    #[cfg(feature = "file-storage")]
+    pub use crate::storage::FileStorage;
+    pub use crate::transactor::{GasEstimate, Transactor};
 
     #[cfg(feature = "debug")]
     pub use crate::state::{Breakpoint, DebugEval};
@@ -83,4 +93,7 @@ pub mod prelude {
         CoverageProfilingData, GasProfilingData, InstructionLocation, PerLocationIter, PerLocationKeys,
         PerLocationValues, ProfileReceiver, Profiler, ProfilingData, StderrReceiver,
     };
+
+    #[cfg(feature = "trace")]
+    pub use crate::trace::{JsonLinesTracer, MemoryWrite, RegisterChange, StorageAccess, TraceStep, Tracer};
 }
@@ -63,15 +63,13 @@ impl MemoryClient {
 
     /// Execute a transaction.
     ///
-    /// Since the memory storage is `Infallible`, associatively, the memory
-    /// client should also be.
+    /// The memory storage only fails for blocks missing from its history, in
+    /// which case no receipts are returned.
     pub fn transact(&mut self, tx: Checked<Script>) -> &[Receipt] {
         self.transactor.transact(tx);
 
         // TODO `Transactor::result` should accept error as generic so compile-time
         // constraints can be applied.
-        //
-        // In this case, we should expect `Infallible` error.
         if let Ok(state) = self.transactor.result() {
             if state.should_revert() {
                 self.transactor.as_mut().revert();
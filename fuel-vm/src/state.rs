@@ -1,5 +1,7 @@
 //! Runtime state representation for the VM
 
+use crate::crypto;
+
 use fuel_tx::Receipt;
 use fuel_types::{Bytes32, Word};
 
@@ -149,6 +151,14 @@ impl<Tx> StateTransition<Tx> {
         self.receipts.as_slice()
     }
 
+    /// Receipts root of the transaction, along with the inclusion proof set of the receipt at
+    /// `index`.
+    ///
+    /// The proof can be checked with [`crypto::verify_receipt_proof`].
+    pub fn receipt_proof(&self, index: usize) -> Option<(Bytes32, Vec<Bytes32>)> {
+        crypto::receipt_proof(&self.receipts, index)
+    }
+
     /// Convert this instance into its internal attributes.
     pub fn into_inner(self) -> (ProgramState, Tx, Vec<Receipt>) {
         (self.state, self.tx, self.receipts)
@@ -197,6 +207,14 @@ impl<'a, Tx> StateTransitionRef<'a, Tx> {
             .iter()
             .any(|r| matches!(r, Receipt::Revert { .. } | Receipt::Panic { .. }))
     }
+
+    /// Receipts root of the transaction, along with the inclusion proof set of the receipt at
+    /// `index`.
+    ///
+    /// The proof can be checked with [`crypto::verify_receipt_proof`].
+    pub fn receipt_proof(&self, index: usize) -> Option<(Bytes32, Vec<Bytes32>)> {
+        crypto::receipt_proof(self.receipts, index)
+    }
 }
 
 impl<'a, Tx> From<&'a StateTransition<Tx>> for StateTransitionRef<'a, Tx> {
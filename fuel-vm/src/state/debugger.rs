@@ -83,6 +83,11 @@ impl Debugger {
         self.last_state.replace(state);
     }
 
+    /// Forget the last known state of the VM.
+    pub fn reset_last_state(&mut self) {
+        self.last_state.take();
+    }
+
     /// Retried the last state of execution; return `None` if the VM was never
     /// executed.
     pub const fn last_state(&self) -> &Option<ProgramState> {
@@ -4,13 +4,27 @@ use fuel_storage::Mappable;
 use fuel_tx::Contract;
 use fuel_types::{AssetId, Bytes32, ContractId, Salt, Word};
 
+#[cfg(feature = "file-storage")]
+mod file;
+mod fork;
 mod interpreter;
 mod memory;
+mod overlay;
 mod predicate;
+mod proof;
+mod snapshot;
+mod witness;
 
+#[cfg(feature = "file-storage")]
+pub use file::FileStorage;
+pub use fork::{ForkStorage, StateFetcher};
 pub use interpreter::InterpreterStorage;
-pub use memory::MemoryStorage;
+pub use memory::{BlockInfo, MemoryStorage};
+pub use overlay::OverlayStorage;
 pub use predicate::PredicateStorage;
+pub use proof::{ProvingStorage, SlotProof, StorageProofs, StorageProver, VerifierStorage};
+pub use snapshot::StorageSnapshot;
+pub use witness::{WitnessBundle, WitnessRecorder, WitnessStorage};
 
 /// The storage table for contract's raw byte code.
 pub struct ContractsRawCode;
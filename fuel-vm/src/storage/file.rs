@@ -0,0 +1,729 @@
+//! File-backed storage, for local tooling that needs to keep its state between runs.
+
+use super::snapshot::{read_array, read_block, read_code, read_len, write_block, write_code, write_len};
+use super::{
+    BlockInfo, ContractsAssets, ContractsInfo, ContractsRawCode, ContractsState, InterpreterStorage, MemoryStorage,
+    StorageSnapshot,
+};
+
+use fuel_storage::{Mappable, MerkleRoot, MerkleRootStorage, StorageInspect, StorageMutate, StorageRead, StorageSize};
+use fuel_tx::Contract;
+use fuel_types::{Address, AssetId, Bytes32, ContractId, Salt, Word};
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Storage persisted in an append-only log file.
+///
+/// The contents are kept in a [`MemoryStorage`] that is rebuilt from the log when the file is
+/// opened. Changes are buffered until [`Self::commit`], which appends them to the log as a single
+/// batch; a batch interrupted by a crash is discarded when the file is opened again.
+#[derive(Debug)]
+pub struct FileStorage {
+    path: PathBuf,
+    file: File,
+    len: u64,
+    storage: MemoryStorage,
+    committed_blocks: (u32, BTreeMap<u32, BlockInfo>),
+    pending: Vec<Record>,
+}
+
+impl FileStorage {
+    /// Magic bytes starting the log file.
+    pub const MAGIC: [u8; 4] = *b"FVML";
+
+    /// Current version of the log format.
+    pub const VERSION: u32 = 1;
+
+    /// Open the storage at `path`, creating an empty one if the file doesn't exist.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+
+        let mut log = Vec::new();
+        file.read_to_end(&mut log)?;
+
+        let mut storage = MemoryStorage::default();
+
+        let header = [&Self::MAGIC[..], &Self::VERSION.to_be_bytes()].concat();
+
+        // The file is new, or its creation was interrupted before the header was written
+        let len = if log.len() < header.len() && header.starts_with(&log) {
+            file.set_len(0)?;
+            file.seek(SeekFrom::Start(0))?;
+            file.write_all(&header)?;
+            file.sync_data()?;
+
+            header.len() as u64
+        } else {
+            let len = replay(&log, &mut storage)?;
+
+            if len < log.len() as u64 {
+                tracing::warn!("discarding {} bytes of uncommitted changes", log.len() as u64 - len);
+                file.set_len(len)?;
+            }
+
+            len
+        };
+
+        file.seek(SeekFrom::Start(len))?;
+
+        storage.commit();
+        storage.persist();
+
+        Ok(Self {
+            path,
+            file,
+            len,
+            committed_blocks: storage.block_history(),
+            storage,
+            pending: Vec::new(),
+        })
+    }
+
+    /// Path of the log file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append the changes made since the last commit to the log.
+    pub fn commit(&mut self) -> io::Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+
+        let mut batch = Vec::new();
+        self.pending
+            .iter()
+            .chain(core::iter::once(&Record::Commit))
+            .try_for_each(|record| record.write(&mut batch))?;
+
+        if let Err(e) = self.file.write_all(&batch).and_then(|_| self.file.sync_data()) {
+            // Drop the partial batch so the next commits aren't appended after it
+            self.file.set_len(self.len)?;
+            self.file.seek(SeekFrom::Start(self.len))?;
+
+            return Err(e);
+        }
+
+        self.len += batch.len() as u64;
+        self.pending.clear();
+        self.storage.commit();
+        self.storage.persist();
+        self.committed_blocks = self.storage.block_history();
+
+        Ok(())
+    }
+
+    /// Discard the changes made since the last commit.
+    pub fn revert(&mut self) {
+        self.pending.clear();
+        self.storage.rollback();
+        self.storage.set_block_history(self.committed_blocks.clone());
+    }
+
+    /// Append a block to the chain, making it the current block. Return the new block height.
+    ///
+    /// See [`MemoryStorage::push_block`].
+    pub fn push_block(&mut self, block: BlockInfo) -> io::Result<u32> {
+        let height = self.storage.push_block(block)?;
+        self.pending.push(Record::PushBlock(block));
+
+        Ok(height)
+    }
+
+    /// Record the block at the provided height.
+    ///
+    /// See [`MemoryStorage::set_block`].
+    pub fn set_block(&mut self, height: u32, block: BlockInfo) -> Option<BlockInfo> {
+        self.pending.push(Record::SetBlock(height, block));
+        self.storage.set_block(height, block)
+    }
+
+    /// Block recorded at the provided height.
+    pub fn block(&self, height: u32) -> Option<&BlockInfo> {
+        self.storage.block(height)
+    }
+
+    /// Dump the contents of the storage, including the changes not committed yet.
+    pub fn snapshot(&self) -> StorageSnapshot {
+        self.storage.snapshot()
+    }
+}
+
+impl AsRef<MemoryStorage> for FileStorage {
+    fn as_ref(&self) -> &MemoryStorage {
+        &self.storage
+    }
+}
+
+/// Replay the committed batches of the log, returning the length of the log they span.
+///
+/// Only the last batch may be cut short by the end of the log; any other malformed record is an
+/// error.
+fn replay(log: &[u8], storage: &mut MemoryStorage) -> io::Result<u64> {
+    let mut reader = log;
+
+    if read_array(&mut reader)? != FileStorage::MAGIC {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "not a storage log"));
+    }
+
+    let version = u32::from_be_bytes(read_array(&mut reader)?);
+    if version != FileStorage::VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unsupported storage log version {version}"),
+        ));
+    }
+
+    let mut committed = log.len() - reader.len();
+    let mut batch = Vec::new();
+
+    while !reader.is_empty() {
+        match Record::read(&mut reader) {
+            Ok(Record::Commit) => {
+                batch.drain(..).try_for_each(|record: Record| record.apply(storage))?;
+                committed = log.len() - reader.len();
+            }
+
+            Ok(record) => batch.push(record),
+
+            // A record cut short by the end of the log can only belong to an interrupted batch
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => break,
+
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(committed as u64)
+}
+
+/// Change to the storage, as written in the log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Record {
+    CodeInsert(ContractId, Vec<u8>),
+    CodeRemove(ContractId),
+    InfoInsert(ContractId, Salt, Bytes32),
+    InfoRemove(ContractId),
+    StateInsert(ContractId, Bytes32, Bytes32),
+    StateRemove(ContractId, Bytes32),
+    StateInsertRange(ContractId, Bytes32, Vec<Bytes32>),
+    StateRemoveRange(ContractId, Bytes32, Word),
+    BalanceInsert(ContractId, AssetId, Word),
+    BalanceRemove(ContractId, AssetId),
+    PushBlock(BlockInfo),
+    SetBlock(u32, BlockInfo),
+    /// End of a batch of changes.
+    Commit,
+}
+
+impl Record {
+    fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        match self {
+            Self::CodeInsert(contract, code) => {
+                w.write_all(&[0x01])?;
+                w.write_all(contract.as_ref())?;
+                write_code(w, code)
+            }
+            Self::CodeRemove(contract) => {
+                w.write_all(&[0x02])?;
+                w.write_all(contract.as_ref())
+            }
+            Self::InfoInsert(contract, salt, root) => {
+                w.write_all(&[0x03])?;
+                w.write_all(contract.as_ref())?;
+                w.write_all(salt.as_ref())?;
+                w.write_all(root.as_ref())
+            }
+            Self::InfoRemove(contract) => {
+                w.write_all(&[0x04])?;
+                w.write_all(contract.as_ref())
+            }
+            Self::StateInsert(contract, key, value) => {
+                w.write_all(&[0x05])?;
+                w.write_all(contract.as_ref())?;
+                w.write_all(key.as_ref())?;
+                w.write_all(value.as_ref())
+            }
+            Self::StateRemove(contract, key) => {
+                w.write_all(&[0x06])?;
+                w.write_all(contract.as_ref())?;
+                w.write_all(key.as_ref())
+            }
+            Self::StateInsertRange(contract, start, values) => {
+                w.write_all(&[0x07])?;
+                w.write_all(contract.as_ref())?;
+                w.write_all(start.as_ref())?;
+                write_len(w, values.len())?;
+                values.iter().try_for_each(|value| w.write_all(value.as_ref()))
+            }
+            Self::StateRemoveRange(contract, start, range) => {
+                w.write_all(&[0x08])?;
+                w.write_all(contract.as_ref())?;
+                w.write_all(start.as_ref())?;
+                w.write_all(&range.to_be_bytes())
+            }
+            Self::BalanceInsert(contract, asset_id, balance) => {
+                w.write_all(&[0x09])?;
+                w.write_all(contract.as_ref())?;
+                w.write_all(asset_id.as_ref())?;
+                w.write_all(&balance.to_be_bytes())
+            }
+            Self::BalanceRemove(contract, asset_id) => {
+                w.write_all(&[0x0a])?;
+                w.write_all(contract.as_ref())?;
+                w.write_all(asset_id.as_ref())
+            }
+            Self::PushBlock(block) => {
+                w.write_all(&[0x0b])?;
+                write_block(w, block)
+            }
+            Self::SetBlock(height, block) => {
+                w.write_all(&[0x0c])?;
+                w.write_all(&height.to_be_bytes())?;
+                write_block(w, block)
+            }
+            Self::Commit => w.write_all(&[0xff]),
+        }
+    }
+
+    fn read<R: Read>(r: &mut R) -> io::Result<Self> {
+        let [tag] = read_array(r)?;
+
+        let record = match tag {
+            0x01 => Self::CodeInsert(read_array(r)?.into(), read_code(r)?),
+            0x02 => Self::CodeRemove(read_array(r)?.into()),
+            0x03 => Self::InfoInsert(read_array(r)?.into(), read_array(r)?.into(), read_array(r)?.into()),
+            0x04 => Self::InfoRemove(read_array(r)?.into()),
+            0x05 => Self::StateInsert(read_array(r)?.into(), read_array(r)?.into(), read_array(r)?.into()),
+            0x06 => Self::StateRemove(read_array(r)?.into(), read_array(r)?.into()),
+            0x07 => {
+                let contract = read_array(r)?.into();
+                let start = read_array(r)?.into();
+                let values = (0..read_len(r)?)
+                    .map(|_| read_array(r).map(Bytes32::from))
+                    .collect::<io::Result<_>>()?;
+
+                Self::StateInsertRange(contract, start, values)
+            }
+            0x08 => Self::StateRemoveRange(
+                read_array(r)?.into(),
+                read_array(r)?.into(),
+                Word::from_be_bytes(read_array(r)?),
+            ),
+            0x09 => Self::BalanceInsert(
+                read_array(r)?.into(),
+                read_array(r)?.into(),
+                Word::from_be_bytes(read_array(r)?),
+            ),
+            0x0a => Self::BalanceRemove(read_array(r)?.into(), read_array(r)?.into()),
+            0x0b => Self::PushBlock(read_block(r)?),
+            0x0c => Self::SetBlock(u32::from_be_bytes(read_array(r)?), read_block(r)?),
+            0xff => Self::Commit,
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "unknown storage log record")),
+        };
+
+        Ok(record)
+    }
+
+    fn apply(self, storage: &mut MemoryStorage) -> io::Result<()> {
+        match self {
+            Self::CodeInsert(contract, code) => {
+                StorageMutate::<ContractsRawCode>::insert(storage, &contract, &code)?;
+            }
+            Self::CodeRemove(contract) => {
+                StorageMutate::<ContractsRawCode>::remove(storage, &contract)?;
+            }
+            Self::InfoInsert(contract, salt, root) => {
+                StorageMutate::<ContractsInfo>::insert(storage, &contract, &(salt, root))?;
+            }
+            Self::InfoRemove(contract) => {
+                StorageMutate::<ContractsInfo>::remove(storage, &contract)?;
+            }
+            Self::StateInsert(contract, key, value) => {
+                StorageMutate::<ContractsState>::insert(storage, &(&contract, &key).into(), &value)?;
+            }
+            Self::StateRemove(contract, key) => {
+                StorageMutate::<ContractsState>::remove(storage, &(&contract, &key).into())?;
+            }
+            Self::StateInsertRange(contract, start, values) => {
+                storage.merkle_contract_state_insert_range(&contract, &start, &values)?;
+            }
+            Self::StateRemoveRange(contract, start, range) => {
+                storage.merkle_contract_state_remove_range(&contract, &start, range)?;
+            }
+            Self::BalanceInsert(contract, asset_id, balance) => {
+                StorageMutate::<ContractsAssets>::insert(storage, &(&contract, &asset_id).into(), &balance)?;
+            }
+            Self::BalanceRemove(contract, asset_id) => {
+                StorageMutate::<ContractsAssets>::remove(storage, &(&contract, &asset_id).into())?;
+            }
+            Self::PushBlock(block) => {
+                storage.push_block(block)?;
+            }
+            Self::SetBlock(height, block) => {
+                storage.set_block(height, block);
+            }
+            Self::Commit => (),
+        }
+
+        Ok(())
+    }
+}
+
+impl StorageInspect<ContractsRawCode> for FileStorage {
+    type Error = io::Error;
+
+    fn get(&self, key: &ContractId) -> io::Result<Option<Cow<'_, Contract>>> {
+        StorageInspect::<ContractsRawCode>::get(&self.storage, key)
+    }
+
+    fn contains_key(&self, key: &ContractId) -> io::Result<bool> {
+        StorageInspect::<ContractsRawCode>::contains_key(&self.storage, key)
+    }
+}
+
+impl StorageMutate<ContractsRawCode> for FileStorage {
+    fn insert(&mut self, key: &ContractId, value: &[u8]) -> io::Result<Option<Contract>> {
+        self.pending.push(Record::CodeInsert(*key, value.to_vec()));
+        StorageMutate::<ContractsRawCode>::insert(&mut self.storage, key, value)
+    }
+
+    fn remove(&mut self, key: &ContractId) -> io::Result<Option<Contract>> {
+        self.pending.push(Record::CodeRemove(*key));
+        StorageMutate::<ContractsRawCode>::remove(&mut self.storage, key)
+    }
+}
+
+impl StorageSize<ContractsRawCode> for FileStorage {
+    fn size_of_value(&self, key: &ContractId) -> io::Result<Option<usize>> {
+        StorageSize::<ContractsRawCode>::size_of_value(&self.storage, key)
+    }
+}
+
+impl StorageRead<ContractsRawCode> for FileStorage {
+    fn read(&self, key: &ContractId, buf: &mut [u8]) -> io::Result<Option<usize>> {
+        StorageRead::<ContractsRawCode>::read(&self.storage, key, buf)
+    }
+
+    fn read_alloc(&self, key: &ContractId) -> io::Result<Option<Vec<u8>>> {
+        StorageRead::<ContractsRawCode>::read_alloc(&self.storage, key)
+    }
+}
+
+impl StorageInspect<ContractsInfo> for FileStorage {
+    type Error = io::Error;
+
+    fn get(&self, key: &ContractId) -> io::Result<Option<Cow<'_, (Salt, Bytes32)>>> {
+        StorageInspect::<ContractsInfo>::get(&self.storage, key)
+    }
+
+    fn contains_key(&self, key: &ContractId) -> io::Result<bool> {
+        StorageInspect::<ContractsInfo>::contains_key(&self.storage, key)
+    }
+}
+
+impl StorageMutate<ContractsInfo> for FileStorage {
+    fn insert(&mut self, key: &ContractId, value: &(Salt, Bytes32)) -> io::Result<Option<(Salt, Bytes32)>> {
+        self.pending.push(Record::InfoInsert(*key, value.0, value.1));
+        StorageMutate::<ContractsInfo>::insert(&mut self.storage, key, value)
+    }
+
+    fn remove(&mut self, key: &ContractId) -> io::Result<Option<(Salt, Bytes32)>> {
+        self.pending.push(Record::InfoRemove(*key));
+        StorageMutate::<ContractsInfo>::remove(&mut self.storage, key)
+    }
+}
+
+impl StorageInspect<ContractsAssets> for FileStorage {
+    type Error = io::Error;
+
+    fn get(&self, key: &<ContractsAssets as Mappable>::Key) -> io::Result<Option<Cow<'_, Word>>> {
+        StorageInspect::<ContractsAssets>::get(&self.storage, key)
+    }
+
+    fn contains_key(&self, key: &<ContractsAssets as Mappable>::Key) -> io::Result<bool> {
+        StorageInspect::<ContractsAssets>::contains_key(&self.storage, key)
+    }
+}
+
+impl StorageMutate<ContractsAssets> for FileStorage {
+    fn insert(&mut self, key: &<ContractsAssets as Mappable>::Key, value: &Word) -> io::Result<Option<Word>> {
+        self.pending
+            .push(Record::BalanceInsert(*key.contract_id(), *key.asset_id(), *value));
+        StorageMutate::<ContractsAssets>::insert(&mut self.storage, key, value)
+    }
+
+    fn remove(&mut self, key: &<ContractsAssets as Mappable>::Key) -> io::Result<Option<Word>> {
+        self.pending
+            .push(Record::BalanceRemove(*key.contract_id(), *key.asset_id()));
+        StorageMutate::<ContractsAssets>::remove(&mut self.storage, key)
+    }
+}
+
+impl MerkleRootStorage<ContractId, ContractsAssets> for FileStorage {
+    fn root(&self, parent: &ContractId) -> io::Result<MerkleRoot> {
+        MerkleRootStorage::<ContractId, ContractsAssets>::root(&self.storage, parent)
+    }
+}
+
+impl StorageInspect<ContractsState> for FileStorage {
+    type Error = io::Error;
+
+    fn get(&self, key: &<ContractsState as Mappable>::Key) -> io::Result<Option<Cow<'_, Bytes32>>> {
+        StorageInspect::<ContractsState>::get(&self.storage, key)
+    }
+
+    fn contains_key(&self, key: &<ContractsState as Mappable>::Key) -> io::Result<bool> {
+        StorageInspect::<ContractsState>::contains_key(&self.storage, key)
+    }
+}
+
+impl StorageMutate<ContractsState> for FileStorage {
+    fn insert(&mut self, key: &<ContractsState as Mappable>::Key, value: &Bytes32) -> io::Result<Option<Bytes32>> {
+        self.pending
+            .push(Record::StateInsert(*key.contract_id(), *key.state_key(), *value));
+        StorageMutate::<ContractsState>::insert(&mut self.storage, key, value)
+    }
+
+    fn remove(&mut self, key: &<ContractsState as Mappable>::Key) -> io::Result<Option<Bytes32>> {
+        self.pending
+            .push(Record::StateRemove(*key.contract_id(), *key.state_key()));
+        StorageMutate::<ContractsState>::remove(&mut self.storage, key)
+    }
+}
+
+impl MerkleRootStorage<ContractId, ContractsState> for FileStorage {
+    fn root(&self, parent: &ContractId) -> io::Result<MerkleRoot> {
+        MerkleRootStorage::<ContractId, ContractsState>::root(&self.storage, parent)
+    }
+}
+
+impl InterpreterStorage for FileStorage {
+    type DataError = io::Error;
+
+    fn block_height(&self) -> io::Result<u32> {
+        self.storage.block_height()
+    }
+
+    fn timestamp(&self, height: u32) -> io::Result<Word> {
+        self.storage.timestamp(height)
+    }
+
+    fn block_hash(&self, block_height: u32) -> io::Result<Bytes32> {
+        self.storage.block_hash(block_height)
+    }
+
+    fn coinbase(&self) -> io::Result<Address> {
+        self.storage.coinbase()
+    }
+
+    fn merkle_contract_state_range(
+        &self,
+        id: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> io::Result<Vec<Option<Cow<'_, Bytes32>>>> {
+        self.storage.merkle_contract_state_range(id, start_key, range)
+    }
+
+    fn merkle_contract_state_insert_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: &[Bytes32],
+    ) -> io::Result<Option<()>> {
+        self.pending
+            .push(Record::StateInsertRange(*contract, *start_key, values.to_vec()));
+        self.storage
+            .merkle_contract_state_insert_range(contract, start_key, values)
+    }
+
+    fn merkle_contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> io::Result<Option<()>> {
+        self.pending
+            .push(Record::StateRemoveRange(*contract, *start_key, range));
+        self.storage
+            .merkle_contract_state_remove_range(contract, start_key, range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::util::test_helpers::deploy_contract;
+
+    use fuel_asm::{op, RegId};
+    use fuel_storage::StorageAsRef;
+
+    /// Log file in the temporary directory, removed on drop.
+    struct TempLog(PathBuf);
+
+    impl TempLog {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("fuel-vm-{}-{name}.log", std::process::id()));
+            let _ = std::fs::remove_file(&path);
+
+            Self(path)
+        }
+    }
+
+    impl Drop for TempLog {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    fn deploy(storage: &mut FileStorage) -> ContractId {
+        let slots = [StorageSlot::new([1; 32].into(), [2; 32].into())];
+
+        deploy_contract(storage, vec![op::ret(RegId::ONE)], &slots)
+    }
+
+    #[test]
+    fn committed_changes_survive_reopening() {
+        let log = TempLog::new("reopen");
+        let mut storage = FileStorage::open(&log.0).unwrap();
+
+        let contract = deploy(&mut storage);
+        storage
+            .merkle_contract_asset_id_balance_insert(&contract, &AssetId::zeroed(), 10)
+            .unwrap();
+        storage
+            .merkle_contract_state_insert_range(&contract, &[3; 32].into(), &[[4; 32].into(); 2])
+            .unwrap();
+        storage
+            .merkle_contract_state_remove_range(&contract, &[1; 32].into(), 1)
+            .unwrap();
+        storage.push_block(BlockInfo::default()).unwrap();
+        storage.commit().unwrap();
+
+        let snapshot = storage.snapshot();
+        let state_root = storage.storage::<ContractsState>().root(&contract).unwrap();
+        drop(storage);
+
+        let storage = FileStorage::open(&log.0).unwrap();
+
+        assert_eq!(storage.snapshot(), snapshot);
+        assert_eq!(storage.storage::<ContractsState>().root(&contract).unwrap(), state_root);
+        assert!(storage.storage_contract_exists(&contract).unwrap());
+        assert_eq!(storage.block_height().unwrap(), 2);
+    }
+
+    #[test]
+    fn uncommitted_changes_are_discarded() {
+        let log = TempLog::new("discard");
+        let mut storage = FileStorage::open(&log.0).unwrap();
+
+        let contract = deploy(&mut storage);
+        storage.commit().unwrap();
+        let snapshot = storage.snapshot();
+
+        storage
+            .merkle_contract_state_insert(&contract, &[5; 32].into(), &[6; 32].into())
+            .unwrap();
+        storage.push_block(BlockInfo::default()).unwrap();
+        storage.revert();
+
+        assert_eq!(storage.snapshot(), snapshot);
+
+        storage
+            .merkle_contract_state_insert(&contract, &[7; 32].into(), &[8; 32].into())
+            .unwrap();
+        drop(storage);
+
+        assert_eq!(FileStorage::open(&log.0).unwrap().snapshot(), snapshot);
+    }
+
+    #[test]
+    fn interrupted_batch_is_truncated() {
+        let log = TempLog::new("torn");
+        let mut storage = FileStorage::open(&log.0).unwrap();
+
+        let contract = deploy(&mut storage);
+        storage.commit().unwrap();
+        let snapshot = storage.snapshot();
+        let len = std::fs::metadata(&log.0).unwrap().len();
+        drop(storage);
+
+        // A batch missing its commit marker, then a truncated record
+        let mut file = OpenOptions::new().append(true).open(&log.0).unwrap();
+        Record::StateRemove(contract, [1; 32].into()).write(&mut file).unwrap();
+        file.write_all(&[0x05, 0xaa]).unwrap();
+        drop(file);
+
+        let mut storage = FileStorage::open(&log.0).unwrap();
+
+        assert_eq!(storage.snapshot(), snapshot);
+        assert_eq!(std::fs::metadata(&log.0).unwrap().len(), len);
+
+        storage
+            .merkle_contract_state_insert(&contract, &[9; 32].into(), &[9; 32].into())
+            .unwrap();
+        storage.commit().unwrap();
+        let snapshot = storage.snapshot();
+        drop(storage);
+
+        assert_eq!(FileStorage::open(&log.0).unwrap().snapshot(), snapshot);
+    }
+
+    #[test]
+    fn corrupted_records_are_rejected() {
+        let log = TempLog::new("corrupted");
+        let mut storage = FileStorage::open(&log.0).unwrap();
+
+        deploy(&mut storage);
+        storage.commit().unwrap();
+        let len = std::fs::metadata(&log.0).unwrap().len();
+        drop(storage);
+
+        // An unknown record isn't an interrupted write, so the log is kept as is
+        let mut file = OpenOptions::new().append(true).open(&log.0).unwrap();
+        file.write_all(&[0x00; 64]).unwrap();
+        drop(file);
+
+        let err = FileStorage::open(&log.0).expect_err("the log is corrupted");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(std::fs::metadata(&log.0).unwrap().len(), len + 64);
+    }
+
+    #[test]
+    fn interrupted_creation_is_recovered() {
+        let log = TempLog::new("created");
+        std::fs::write(&log.0, &FileStorage::MAGIC[..3]).unwrap();
+
+        let mut storage = FileStorage::open(&log.0).unwrap();
+        storage.push_block(BlockInfo::default()).unwrap();
+        storage.commit().unwrap();
+        drop(storage);
+
+        assert_eq!(FileStorage::open(&log.0).unwrap().block_height().unwrap(), 2);
+    }
+
+    #[test]
+    fn rejects_foreign_files() {
+        let log = TempLog::new("foreign");
+        std::fs::write(&log.0, b"not a log").unwrap();
+
+        assert!(FileStorage::open(&log.0).is_err());
+
+        std::fs::write(&log.0, b"FV").unwrap();
+        assert!(FileStorage::open(&log.0).is_ok());
+
+        std::fs::write(&log.0, b"VF").unwrap();
+        assert!(FileStorage::open(&log.0).is_err());
+    }
+}
@@ -0,0 +1,669 @@
+//! Storage forked lazily from a remote chain state.
+
+use super::memory::state_keys;
+use super::{
+    ContractsAssetKey, ContractsAssets, ContractsInfo, ContractsRawCode, ContractsState, ContractsStateKey,
+    InterpreterStorage, MemoryStorage,
+};
+
+use fuel_storage::{
+    Mappable, MerkleRoot, MerkleRootStorage, StorageAsRef, StorageInspect, StorageMutate, StorageRead, StorageSize,
+};
+use fuel_tx::Contract;
+use fuel_types::{Address, AssetId, Bytes32, ContractId, Salt, Word};
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::io;
+
+/// Source of the state of a [`ForkStorage`], e.g. a client of a remote node.
+///
+/// `None` means the entry doesn't exist in the forked state.
+pub trait StateFetcher {
+    /// Fetch the bytecode of a contract.
+    fn contract_code(&self, contract: &ContractId) -> io::Result<Option<Contract>>;
+
+    /// Fetch the salt and code root of a contract.
+    fn contract_info(&self, contract: &ContractId) -> io::Result<Option<(Salt, Bytes32)>>;
+
+    /// Fetch a state slot of a contract.
+    fn contract_state(&self, contract: &ContractId, key: &Bytes32) -> io::Result<Option<Bytes32>>;
+
+    /// Fetch an asset balance of a contract.
+    fn contract_balance(&self, contract: &ContractId, asset_id: &AssetId) -> io::Result<Option<Word>>;
+}
+
+impl<F> StateFetcher for &F
+where
+    F: StateFetcher + ?Sized,
+{
+    fn contract_code(&self, contract: &ContractId) -> io::Result<Option<Contract>> {
+        (*self).contract_code(contract)
+    }
+
+    fn contract_info(&self, contract: &ContractId) -> io::Result<Option<(Salt, Bytes32)>> {
+        (*self).contract_info(contract)
+    }
+
+    fn contract_state(&self, contract: &ContractId, key: &Bytes32) -> io::Result<Option<Bytes32>> {
+        (*self).contract_state(contract, key)
+    }
+
+    fn contract_balance(&self, contract: &ContractId, asset_id: &AssetId) -> io::Result<Option<Word>> {
+        (*self).contract_balance(contract, asset_id)
+    }
+}
+
+/// Serve the state of a memory storage, e.g. to mock a remote node in tests.
+impl StateFetcher for MemoryStorage {
+    fn contract_code(&self, contract: &ContractId) -> io::Result<Option<Contract>> {
+        Ok(self.storage::<ContractsRawCode>().get(contract)?.map(Cow::into_owned))
+    }
+
+    fn contract_info(&self, contract: &ContractId) -> io::Result<Option<(Salt, Bytes32)>> {
+        Ok(self.storage::<ContractsInfo>().get(contract)?.map(Cow::into_owned))
+    }
+
+    fn contract_state(&self, contract: &ContractId, key: &Bytes32) -> io::Result<Option<Bytes32>> {
+        Ok(self
+            .storage::<ContractsState>()
+            .get(&(contract, key).into())?
+            .map(Cow::into_owned))
+    }
+
+    fn contract_balance(&self, contract: &ContractId, asset_id: &AssetId) -> io::Result<Option<Word>> {
+        Ok(self
+            .storage::<ContractsAssets>()
+            .get(&(contract, asset_id).into())?
+            .map(Cow::into_owned))
+    }
+}
+
+/// Storage reading the entries it doesn't have from a [`StateFetcher`].
+///
+/// Fetched entries are cached in a local [`MemoryStorage`], which also receives every write, so
+/// each entry is fetched at most once and the forked state is never modified. Block data is served
+/// by the local storage.
+///
+/// The merkle roots can't be computed, since the forked state is never fetched as a whole, so
+/// they fail with [`io::ErrorKind::Unsupported`].
+#[derive(Debug)]
+pub struct ForkStorage<F> {
+    fetcher: F,
+    cache: RefCell<ForkCache>,
+}
+
+#[derive(Debug)]
+struct ForkCache {
+    local: MemoryStorage,
+    code: HashSet<ContractId>,
+    info: HashSet<ContractId>,
+    state: HashSet<ContractsStateKey>,
+    balances: HashSet<ContractsAssetKey>,
+}
+
+impl<F> ForkStorage<F>
+where
+    F: StateFetcher,
+{
+    /// Fork the state served by `fetcher`, with `local` holding the block data and any state
+    /// that should override the forked one.
+    pub fn new(fetcher: F, local: MemoryStorage) -> Self {
+        Self {
+            fetcher,
+            cache: RefCell::new(ForkCache {
+                local,
+                code: Default::default(),
+                info: Default::default(),
+                state: Default::default(),
+                balances: Default::default(),
+            }),
+        }
+    }
+
+    /// The state fetcher.
+    pub const fn fetcher(&self) -> &F {
+        &self.fetcher
+    }
+
+    /// Local storage, with the fetched entries and the changes made on top of the fork.
+    pub fn into_local(self) -> MemoryStorage {
+        self.cache.into_inner().local
+    }
+
+    fn load_code(&self, contract: &ContractId) -> io::Result<()> {
+        let mut cache = self.cache.borrow_mut();
+
+        if !cache.code.contains(contract) && !cache.local.storage::<ContractsRawCode>().contains_key(contract)? {
+            if let Some(code) = self.fetcher.contract_code(contract)? {
+                StorageMutate::<ContractsRawCode>::insert(&mut cache.local, contract, code.as_ref())?;
+            }
+        }
+
+        cache.code.insert(*contract);
+
+        Ok(())
+    }
+
+    fn load_info(&self, contract: &ContractId) -> io::Result<()> {
+        let mut cache = self.cache.borrow_mut();
+
+        if !cache.info.contains(contract) && !cache.local.storage::<ContractsInfo>().contains_key(contract)? {
+            if let Some(info) = self.fetcher.contract_info(contract)? {
+                StorageMutate::<ContractsInfo>::insert(&mut cache.local, contract, &info)?;
+            }
+        }
+
+        cache.info.insert(*contract);
+
+        Ok(())
+    }
+
+    fn load_state(&self, key: &ContractsStateKey) -> io::Result<()> {
+        let mut cache = self.cache.borrow_mut();
+
+        if !cache.state.contains(key) && !cache.local.storage::<ContractsState>().contains_key(key)? {
+            if let Some(value) = self.fetcher.contract_state(key.contract_id(), key.state_key())? {
+                StorageMutate::<ContractsState>::insert(&mut cache.local, key, &value)?;
+            }
+        }
+
+        cache.state.insert(*key);
+
+        Ok(())
+    }
+
+    fn load_balance(&self, key: &ContractsAssetKey) -> io::Result<()> {
+        let mut cache = self.cache.borrow_mut();
+
+        if !cache.balances.contains(key) && !cache.local.storage::<ContractsAssets>().contains_key(key)? {
+            if let Some(balance) = self.fetcher.contract_balance(key.contract_id(), key.asset_id())? {
+                StorageMutate::<ContractsAssets>::insert(&mut cache.local, key, &balance)?;
+            }
+        }
+
+        cache.balances.insert(*key);
+
+        Ok(())
+    }
+
+    /// Load `range` consecutive state slots starting at `start_key`.
+    fn load_state_range(&self, contract: &ContractId, start_key: &Bytes32, range: Word) -> io::Result<()> {
+        state_keys(start_key)
+            .take(range as usize)
+            .try_for_each(|key| self.load_state(&(contract, &key).into()))
+    }
+}
+
+impl<F> StorageInspect<ContractsRawCode> for ForkStorage<F>
+where
+    F: StateFetcher,
+{
+    type Error = io::Error;
+
+    fn get(&self, key: &ContractId) -> io::Result<Option<Cow<'_, Contract>>> {
+        self.load_code(key)?;
+
+        let cache = self.cache.borrow();
+        Ok(cache
+            .local
+            .storage::<ContractsRawCode>()
+            .get(key)?
+            .map(|code| Cow::Owned(code.into_owned())))
+    }
+
+    fn contains_key(&self, key: &ContractId) -> io::Result<bool> {
+        self.load_code(key)?;
+
+        self.cache
+            .borrow()
+            .local
+            .storage::<ContractsRawCode>()
+            .contains_key(key)
+    }
+}
+
+impl<F> StorageMutate<ContractsRawCode> for ForkStorage<F>
+where
+    F: StateFetcher,
+{
+    fn insert(&mut self, key: &ContractId, value: &[u8]) -> io::Result<Option<Contract>> {
+        self.load_code(key)?;
+
+        StorageMutate::<ContractsRawCode>::insert(&mut self.cache.get_mut().local, key, value)
+    }
+
+    fn remove(&mut self, key: &ContractId) -> io::Result<Option<Contract>> {
+        self.load_code(key)?;
+
+        StorageMutate::<ContractsRawCode>::remove(&mut self.cache.get_mut().local, key)
+    }
+}
+
+impl<F> StorageSize<ContractsRawCode> for ForkStorage<F>
+where
+    F: StateFetcher,
+{
+    fn size_of_value(&self, key: &ContractId) -> io::Result<Option<usize>> {
+        self.load_code(key)?;
+
+        StorageSize::<ContractsRawCode>::size_of_value(&self.cache.borrow().local, key)
+    }
+}
+
+impl<F> StorageRead<ContractsRawCode> for ForkStorage<F>
+where
+    F: StateFetcher,
+{
+    fn read(&self, key: &ContractId, buf: &mut [u8]) -> io::Result<Option<usize>> {
+        self.load_code(key)?;
+
+        StorageRead::<ContractsRawCode>::read(&self.cache.borrow().local, key, buf)
+    }
+
+    fn read_alloc(&self, key: &ContractId) -> io::Result<Option<Vec<u8>>> {
+        self.load_code(key)?;
+
+        StorageRead::<ContractsRawCode>::read_alloc(&self.cache.borrow().local, key)
+    }
+}
+
+impl<F> StorageInspect<ContractsInfo> for ForkStorage<F>
+where
+    F: StateFetcher,
+{
+    type Error = io::Error;
+
+    fn get(&self, key: &ContractId) -> io::Result<Option<Cow<'_, (Salt, Bytes32)>>> {
+        self.load_info(key)?;
+
+        let cache = self.cache.borrow();
+        Ok(cache
+            .local
+            .storage::<ContractsInfo>()
+            .get(key)?
+            .map(|info| Cow::Owned(info.into_owned())))
+    }
+
+    fn contains_key(&self, key: &ContractId) -> io::Result<bool> {
+        self.load_info(key)?;
+
+        self.cache.borrow().local.storage::<ContractsInfo>().contains_key(key)
+    }
+}
+
+impl<F> StorageMutate<ContractsInfo> for ForkStorage<F>
+where
+    F: StateFetcher,
+{
+    fn insert(&mut self, key: &ContractId, value: &(Salt, Bytes32)) -> io::Result<Option<(Salt, Bytes32)>> {
+        self.load_info(key)?;
+
+        StorageMutate::<ContractsInfo>::insert(&mut self.cache.get_mut().local, key, value)
+    }
+
+    fn remove(&mut self, key: &ContractId) -> io::Result<Option<(Salt, Bytes32)>> {
+        self.load_info(key)?;
+
+        StorageMutate::<ContractsInfo>::remove(&mut self.cache.get_mut().local, key)
+    }
+}
+
+impl<F> StorageInspect<ContractsAssets> for ForkStorage<F>
+where
+    F: StateFetcher,
+{
+    type Error = io::Error;
+
+    fn get(&self, key: &<ContractsAssets as Mappable>::Key) -> io::Result<Option<Cow<'_, Word>>> {
+        self.load_balance(key)?;
+
+        let cache = self.cache.borrow();
+        Ok(cache
+            .local
+            .storage::<ContractsAssets>()
+            .get(key)?
+            .map(|balance| Cow::Owned(balance.into_owned())))
+    }
+
+    fn contains_key(&self, key: &<ContractsAssets as Mappable>::Key) -> io::Result<bool> {
+        self.load_balance(key)?;
+
+        self.cache.borrow().local.storage::<ContractsAssets>().contains_key(key)
+    }
+}
+
+impl<F> StorageMutate<ContractsAssets> for ForkStorage<F>
+where
+    F: StateFetcher,
+{
+    fn insert(&mut self, key: &<ContractsAssets as Mappable>::Key, value: &Word) -> io::Result<Option<Word>> {
+        self.load_balance(key)?;
+
+        StorageMutate::<ContractsAssets>::insert(&mut self.cache.get_mut().local, key, value)
+    }
+
+    fn remove(&mut self, key: &<ContractsAssets as Mappable>::Key) -> io::Result<Option<Word>> {
+        self.load_balance(key)?;
+
+        StorageMutate::<ContractsAssets>::remove(&mut self.cache.get_mut().local, key)
+    }
+}
+
+impl<F> MerkleRootStorage<ContractId, ContractsAssets> for ForkStorage<F>
+where
+    F: StateFetcher,
+{
+    fn root(&self, parent: &ContractId) -> io::Result<MerkleRoot> {
+        Err(partial_state_root(parent))
+    }
+}
+
+impl<F> StorageInspect<ContractsState> for ForkStorage<F>
+where
+    F: StateFetcher,
+{
+    type Error = io::Error;
+
+    fn get(&self, key: &<ContractsState as Mappable>::Key) -> io::Result<Option<Cow<'_, Bytes32>>> {
+        self.load_state(key)?;
+
+        let cache = self.cache.borrow();
+        Ok(cache
+            .local
+            .storage::<ContractsState>()
+            .get(key)?
+            .map(|value| Cow::Owned(value.into_owned())))
+    }
+
+    fn contains_key(&self, key: &<ContractsState as Mappable>::Key) -> io::Result<bool> {
+        self.load_state(key)?;
+
+        self.cache.borrow().local.storage::<ContractsState>().contains_key(key)
+    }
+}
+
+impl<F> StorageMutate<ContractsState> for ForkStorage<F>
+where
+    F: StateFetcher,
+{
+    fn insert(&mut self, key: &<ContractsState as Mappable>::Key, value: &Bytes32) -> io::Result<Option<Bytes32>> {
+        self.load_state(key)?;
+
+        StorageMutate::<ContractsState>::insert(&mut self.cache.get_mut().local, key, value)
+    }
+
+    fn remove(&mut self, key: &<ContractsState as Mappable>::Key) -> io::Result<Option<Bytes32>> {
+        self.load_state(key)?;
+
+        StorageMutate::<ContractsState>::remove(&mut self.cache.get_mut().local, key)
+    }
+}
+
+impl<F> MerkleRootStorage<ContractId, ContractsState> for ForkStorage<F>
+where
+    F: StateFetcher,
+{
+    fn root(&self, parent: &ContractId) -> io::Result<MerkleRoot> {
+        Err(partial_state_root(parent))
+    }
+}
+
+impl<F> InterpreterStorage for ForkStorage<F>
+where
+    F: StateFetcher,
+{
+    type DataError = io::Error;
+
+    fn block_height(&self) -> io::Result<u32> {
+        self.cache.borrow().local.block_height()
+    }
+
+    fn timestamp(&self, height: u32) -> io::Result<Word> {
+        self.cache.borrow().local.timestamp(height)
+    }
+
+    fn block_hash(&self, block_height: u32) -> io::Result<Bytes32> {
+        self.cache.borrow().local.block_hash(block_height)
+    }
+
+    fn coinbase(&self) -> io::Result<Address> {
+        self.cache.borrow().local.coinbase()
+    }
+
+    fn merkle_contract_state_range(
+        &self,
+        id: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> io::Result<Vec<Option<Cow<'_, Bytes32>>>> {
+        self.load_state_range(id, start_key, range)?;
+
+        Ok(self
+            .cache
+            .borrow()
+            .local
+            .merkle_contract_state_range(id, start_key, range)?
+            .into_iter()
+            .map(|value| value.map(|value| Cow::Owned(value.into_owned())))
+            .collect())
+    }
+
+    fn merkle_contract_state_insert_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: &[Bytes32],
+    ) -> io::Result<Option<()>> {
+        self.load_state_range(contract, start_key, values.len() as Word)?;
+
+        self.cache
+            .get_mut()
+            .local
+            .merkle_contract_state_insert_range(contract, start_key, values)
+    }
+
+    fn merkle_contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> io::Result<Option<()>> {
+        self.load_state_range(contract, start_key, range)?;
+
+        self.cache
+            .get_mut()
+            .local
+            .merkle_contract_state_remove_range(contract, start_key, range)
+    }
+}
+
+/// Error for the merkle roots of a contract whose state is only partially known.
+pub(super) fn partial_state_root(contract: &ContractId) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("the merkle roots of contract {contract} aren't known from its partial state"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::WORD_SIZE;
+    use crate::prelude::*;
+    use crate::script_with_data_offset;
+    use crate::util::test_helpers::deploy_contract;
+
+    use fuel_asm::{op, RegId};
+    use std::cell::Cell;
+
+    /// Remote state counting the fetches it serves.
+    #[derive(Default)]
+    struct CountingFetcher {
+        remote: MemoryStorage,
+        fetches: Cell<usize>,
+    }
+
+    impl CountingFetcher {
+        fn count<T>(&self, value: T) -> T {
+            self.fetches.set(self.fetches.get() + 1);
+            value
+        }
+    }
+
+    impl StateFetcher for CountingFetcher {
+        fn contract_code(&self, contract: &ContractId) -> io::Result<Option<Contract>> {
+            self.count(StateFetcher::contract_code(&self.remote, contract))
+        }
+
+        fn contract_info(&self, contract: &ContractId) -> io::Result<Option<(Salt, Bytes32)>> {
+            self.count(StateFetcher::contract_info(&self.remote, contract))
+        }
+
+        fn contract_state(&self, contract: &ContractId, key: &Bytes32) -> io::Result<Option<Bytes32>> {
+            self.count(StateFetcher::contract_state(&self.remote, contract, key))
+        }
+
+        fn contract_balance(&self, contract: &ContractId, asset_id: &AssetId) -> io::Result<Option<Word>> {
+            self.count(StateFetcher::contract_balance(&self.remote, contract, asset_id))
+        }
+    }
+
+    /// Deploy a contract returning the first word of its state slot keyed by its id.
+    fn deploy(storage: &mut MemoryStorage) -> ContractId {
+        deploy_contract(storage, vec![op::srw(0x10, 0x11, RegId::FP), op::ret(0x10)], &[])
+    }
+
+    fn call(storage: &mut ForkStorage<&CountingFetcher>, contract: ContractId) -> Word {
+        let params = ConsensusParameters::DEFAULT;
+        let (script, _) = script_with_data_offset!(
+            data_offset,
+            vec![
+                op::movi(0x10, data_offset),
+                op::call(0x10, RegId::ZERO, 0x10, RegId::CGAS),
+                op::ret(RegId::ONE),
+            ],
+            params.tx_offset()
+        );
+        let mut script_data = contract.to_vec();
+        script_data.extend([0u8; WORD_SIZE * 2]);
+
+        let tx = Transaction::script(
+            0,
+            1_000_000,
+            0,
+            script.into_iter().collect(),
+            script_data,
+            vec![Input::contract(
+                Default::default(),
+                Default::default(),
+                Default::default(),
+                Default::default(),
+                contract,
+            )],
+            vec![Output::contract(0, Default::default(), Default::default())],
+            vec![],
+        )
+        .into_checked(0, &params, &GasCosts::default())
+        .expect("failed to generate a checked tx");
+
+        let mut transactor = Transactor::new(&mut *storage, params, Default::default());
+        transactor.transact(tx);
+
+        transactor
+            .receipts()
+            .expect("failed to execute script")
+            .iter()
+            .find_map(|receipt| match receipt {
+                Receipt::Return { id, val, .. } if *id == contract => Some(*val),
+                _ => None,
+            })
+            .expect("the contract should return")
+    }
+
+    #[test]
+    fn remote_state_is_fetched_once() {
+        let mut fetcher = CountingFetcher::default();
+        let contract = deploy(&mut fetcher.remote);
+
+        let mut value = Bytes32::zeroed();
+        value[..WORD_SIZE].copy_from_slice(&7u64.to_be_bytes());
+        fetcher
+            .remote
+            .merkle_contract_state_insert(&contract, &Bytes32::new(*contract), &value)
+            .unwrap();
+
+        let mut storage = ForkStorage::new(&fetcher, MemoryStorage::default());
+
+        assert_eq!(call(&mut storage, contract), 7);
+        let fetches = fetcher.fetches.get();
+        assert!(fetches > 0);
+
+        assert_eq!(call(&mut storage, contract), 7);
+        assert_eq!(fetcher.fetches.get(), fetches);
+
+        // The state slot was loaded, so the local root matches the remote one
+        let local = storage.into_local();
+        assert_eq!(
+            local.storage::<ContractsState>().root(&contract).unwrap(),
+            fetcher.remote.storage::<ContractsState>().root(&contract).unwrap()
+        );
+    }
+
+    #[test]
+    fn local_changes_shadow_remote_state() {
+        let mut fetcher = CountingFetcher::default();
+        let contract = deploy(&mut fetcher.remote);
+        let key = Bytes32::new(*contract);
+        fetcher
+            .remote
+            .merkle_contract_state_insert(&contract, &key, &[1; 32].into())
+            .unwrap();
+        fetcher
+            .remote
+            .merkle_contract_asset_id_balance_insert(&contract, &AssetId::zeroed(), 10)
+            .unwrap();
+
+        let mut storage = ForkStorage::new(&fetcher, MemoryStorage::default());
+
+        assert_eq!(
+            storage.merkle_contract_state_remove(&contract, &key).unwrap(),
+            Some(Bytes32::from([1; 32]))
+        );
+        assert_eq!(storage.merkle_contract_state(&contract, &key).unwrap(), None);
+        assert_eq!(call(&mut storage, contract), 0);
+
+        assert_eq!(
+            storage
+                .merkle_contract_asset_id_balance_insert(&contract, &AssetId::zeroed(), 5)
+                .unwrap(),
+            Some(10)
+        );
+        assert_eq!(
+            storage
+                .merkle_contract_asset_id_balance(&contract, &AssetId::zeroed())
+                .unwrap(),
+            Some(5)
+        );
+        assert_eq!(
+            fetcher
+                .remote
+                .merkle_contract_asset_id_balance(&contract, &AssetId::zeroed())
+                .unwrap(),
+            Some(10)
+        );
+
+        assert_eq!(
+            storage
+                .merkle_contract_state_range(&contract, &[0xff; 32].into(), 3)
+                .unwrap(),
+            vec![None]
+        );
+
+        assert_eq!(
+            storage.storage::<ContractsAssets>().root(&contract).unwrap_err().kind(),
+            io::ErrorKind::Unsupported
+        );
+    }
+}
@@ -1,29 +1,121 @@
-use crate::crypto;
-use crate::error::Infallible;
 use crate::storage::{
     ContractsAssetKey, ContractsAssets, ContractsInfo, ContractsRawCode, ContractsState, ContractsStateKey,
-    InterpreterStorage,
+    InterpreterStorage, StorageSnapshot,
 };
 
 use fuel_crypto::Hasher;
+use fuel_merkle::sparse::{in_memory::MerkleTree as SparseMerkleTree, Proof};
 use fuel_storage::{
     Mappable, MerkleRoot, MerkleRootStorage, StorageAsRef, StorageInspect, StorageMutate, StorageRead, StorageSize,
 };
 use fuel_tx::Contract;
 use fuel_types::{Address, Bytes32, ContractId, Salt, Word};
-use itertools::Itertools;
 use tai64::Tai64;
 
 use std::borrow::Cow;
 use std::collections::BTreeMap;
-use std::io::Read;
+use std::io::{self, Read};
 
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Clone)]
 struct MemoryStorageInner {
     contracts: BTreeMap<ContractId, Contract>,
     balances: BTreeMap<ContractsAssetKey, Word>,
     contract_state: BTreeMap<ContractsStateKey, Bytes32>,
     contract_code_root: BTreeMap<ContractId, (Salt, Bytes32)>,
+    balances_trees: BTreeMap<ContractId, SparseMerkleTree>,
+    contract_state_trees: BTreeMap<ContractId, SparseMerkleTree>,
+}
+
+impl MemoryStorageInner {
+    fn balance_insert(&mut self, key: ContractsAssetKey, value: Word) -> Option<Word> {
+        self.balances_trees
+            .entry(*key.contract_id())
+            .or_default()
+            .update(key.asset_id(), &value.to_be_bytes());
+
+        self.balances.insert(key, value)
+    }
+
+    fn balance_remove(&mut self, key: &ContractsAssetKey) -> Option<Word> {
+        let value = self.balances.remove(key);
+
+        if value.is_some() {
+            Self::tree_delete(&mut self.balances_trees, key.contract_id(), key.asset_id());
+        }
+
+        value
+    }
+
+    fn state_insert(&mut self, key: ContractsStateKey, value: Bytes32) -> Option<Bytes32> {
+        self.contract_state_trees
+            .entry(*key.contract_id())
+            .or_default()
+            .update(key.state_key(), value.as_ref());
+
+        self.contract_state.insert(key, value)
+    }
+
+    fn state_remove(&mut self, key: &ContractsStateKey) -> Option<Bytes32> {
+        let value = self.contract_state.remove(key);
+
+        if value.is_some() {
+            Self::tree_delete(&mut self.contract_state_trees, key.contract_id(), key.state_key());
+        }
+
+        value
+    }
+
+    /// Delete a leaf from the tree of the contract, dropping the tree once it is empty.
+    fn tree_delete(trees: &mut BTreeMap<ContractId, SparseMerkleTree>, contract: &ContractId, key: &[u8; 32]) {
+        if let Some(tree) = trees.get_mut(contract) {
+            tree.delete(key);
+
+            if tree.root() == *Bytes32::zeroed() {
+                trees.remove(contract);
+            }
+        }
+    }
+
+    fn tree_proof(trees: &BTreeMap<ContractId, SparseMerkleTree>, contract: &ContractId, key: &[u8; 32]) -> Proof {
+        trees
+            .get(contract)
+            .map(|tree| {
+                tree.prove(key)
+                    .expect("the in-memory tree can't fail to load its nodes")
+            })
+            .unwrap_or_default()
+    }
+
+    fn tree_root(trees: &BTreeMap<ContractId, SparseMerkleTree>, contract: &ContractId) -> MerkleRoot {
+        trees
+            .get(contract)
+            .map(SparseMerkleTree::root)
+            .unwrap_or_else(|| *Bytes32::zeroed())
+    }
+}
+
+// The trees are derived from the balances and state, so they are left out of the comparison.
+impl PartialEq for MemoryStorageInner {
+    fn eq(&self, other: &Self) -> bool {
+        self.contracts == other.contracts
+            && self.balances == other.balances
+            && self.contract_state == other.contract_state
+            && self.contract_code_root == other.contract_code_root
+    }
+}
+
+impl Eq for MemoryStorageInner {}
+
+/// Header data of a block, as exposed to the VM.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockInfo {
+    /// Hash of the block header, returned by `BHSH`.
+    pub hash: Bytes32,
+    /// Timestamp of the block as a TAI64 label, returned by `TIME`.
+    pub timestamp: Word,
+    /// Address of the block producer, returned by `CB`.
+    pub coinbase: Address,
 }
 
 #[derive(Debug, Clone)]
@@ -34,9 +126,15 @@ struct MemoryStorageInner {
 /// - memory: the transactions will be applied to this state.
 /// - transacted: will receive the committed `memory` state.
 /// - persisted: will receive the persisted `transacted` state.
+///
+/// The block history is shared by the 3 states. Once a block is recorded, querying a height without
+/// a recorded [`BlockInfo`] fails with [`io::ErrorKind::NotFound`]. Without any recorded block,
+/// every height gets a timestamp of `UNIX_EPOCH + height * 10`, the hash of the height as block
+/// hash and the default coinbase.
 pub struct MemoryStorage {
     block_height: u32,
     coinbase: Address,
+    blocks: BTreeMap<u32, BlockInfo>,
     memory: MemoryStorageInner,
     transacted: MemoryStorageInner,
     persisted: MemoryStorageInner,
@@ -48,6 +146,7 @@ impl MemoryStorage {
         Self {
             block_height,
             coinbase,
+            blocks: Default::default(),
             memory: Default::default(),
             transacted: Default::default(),
             persisted: Default::default(),
@@ -96,6 +195,129 @@ impl MemoryStorage {
     pub fn set_block_height(&mut self, block_height: u32) {
         self.block_height = block_height;
     }
+
+    /// Append a block to the chain, making it the current block. Return the new block height.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidInput`] if the block height overflows.
+    pub fn push_block(&mut self, block: BlockInfo) -> io::Result<u32> {
+        self.block_height = self
+            .block_height
+            .checked_add(1)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "the block height overflows"))?;
+        self.blocks.insert(self.block_height, block);
+
+        Ok(self.block_height)
+    }
+
+    /// Record the block at the provided height, replacing the one recorded before, if any.
+    pub fn set_block(&mut self, height: u32, block: BlockInfo) -> Option<BlockInfo> {
+        self.blocks.insert(height, block)
+    }
+
+    /// Block recorded at the provided height.
+    pub fn block(&self, height: u32) -> Option<&BlockInfo> {
+        self.blocks.get(&height)
+    }
+
+    /// Block recorded at the provided height, or `None` if the block history isn't in use.
+    fn history_block(&self, height: u32) -> io::Result<Option<&BlockInfo>> {
+        if self.blocks.is_empty() {
+            return Ok(None);
+        }
+
+        self.block(height).map(Some).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("the block at height {height} isn't known"),
+            )
+        })
+    }
+
+    /// Proof of a state slot of a contract against its state root.
+    pub(super) fn state_proof(&self, key: &ContractsStateKey) -> Proof {
+        MemoryStorageInner::tree_proof(&self.memory.contract_state_trees, key.contract_id(), key.state_key())
+    }
+
+    /// Proof of an asset balance of a contract against its balance root.
+    pub(super) fn balance_proof(&self, key: &ContractsAssetKey) -> Proof {
+        MemoryStorageInner::tree_proof(&self.memory.balances_trees, key.contract_id(), key.asset_id())
+    }
+
+    /// Current block height and recorded blocks.
+    #[cfg(feature = "file-storage")]
+    pub(super) fn block_history(&self) -> (u32, BTreeMap<u32, BlockInfo>) {
+        (self.block_height, self.blocks.clone())
+    }
+
+    /// Replace the current block height and recorded blocks.
+    #[cfg(feature = "file-storage")]
+    pub(super) fn set_block_history(&mut self, (block_height, blocks): (u32, BTreeMap<u32, BlockInfo>)) {
+        self.block_height = block_height;
+        self.blocks = blocks;
+    }
+
+    /// Dump the memory state and the block history.
+    pub fn snapshot(&self) -> StorageSnapshot {
+        let mut snapshot = StorageSnapshot {
+            version: StorageSnapshot::VERSION,
+            block_height: self.block_height,
+            coinbase: self.coinbase,
+            blocks: self.blocks.clone(),
+            contracts_raw_code: self.memory.contracts.clone(),
+            contracts_info: self.memory.contract_code_root.clone(),
+            ..Default::default()
+        };
+
+        for (key, value) in &self.memory.contract_state {
+            snapshot
+                .contracts_state
+                .entry(*key.contract_id())
+                .or_default()
+                .insert(*key.state_key(), *value);
+        }
+
+        for (key, balance) in &self.memory.balances {
+            snapshot
+                .contracts_assets
+                .entry(*key.contract_id())
+                .or_default()
+                .insert(*key.asset_id(), *balance);
+        }
+
+        snapshot
+    }
+
+    /// Create a storage from a snapshot, with its contents persisted.
+    pub fn from_snapshot(snapshot: StorageSnapshot) -> Self {
+        let mut storage = Self::new(snapshot.block_height, snapshot.coinbase);
+
+        storage.blocks = snapshot.blocks;
+        storage.memory.contracts = snapshot.contracts_raw_code;
+        storage.memory.contract_code_root = snapshot.contracts_info;
+
+        for (contract, state) in &snapshot.contracts_state {
+            for (key, value) in state {
+                storage.memory.state_insert((contract, key).into(), *value);
+            }
+        }
+
+        for (contract, balances) in &snapshot.contracts_assets {
+            for (asset_id, balance) in balances {
+                storage.memory.balance_insert((contract, asset_id).into(), *balance);
+            }
+        }
+
+        storage.transacted = storage.memory.clone();
+        storage.persisted = storage.memory.clone();
+
+        storage
+    }
+}
+
+impl From<StorageSnapshot> for MemoryStorage {
+    fn from(snapshot: StorageSnapshot) -> Self {
+        Self::from_snapshot(snapshot)
+    }
 }
 
 impl Default for MemoryStorage {
@@ -108,29 +330,29 @@ impl Default for MemoryStorage {
 }
 
 impl StorageInspect<ContractsRawCode> for MemoryStorage {
-    type Error = Infallible;
+    type Error = io::Error;
 
-    fn get(&self, key: &ContractId) -> Result<Option<Cow<'_, Contract>>, Infallible> {
+    fn get(&self, key: &ContractId) -> Result<Option<Cow<'_, Contract>>, io::Error> {
         Ok(self.memory.contracts.get(key).map(Cow::Borrowed))
     }
 
-    fn contains_key(&self, key: &ContractId) -> Result<bool, Infallible> {
+    fn contains_key(&self, key: &ContractId) -> Result<bool, io::Error> {
         Ok(self.memory.contracts.contains_key(key))
     }
 }
 
 impl StorageMutate<ContractsRawCode> for MemoryStorage {
-    fn insert(&mut self, key: &ContractId, value: &[u8]) -> Result<Option<Contract>, Infallible> {
+    fn insert(&mut self, key: &ContractId, value: &[u8]) -> Result<Option<Contract>, io::Error> {
         Ok(self.memory.contracts.insert(*key, value.into()))
     }
 
-    fn remove(&mut self, key: &ContractId) -> Result<Option<Contract>, Infallible> {
+    fn remove(&mut self, key: &ContractId) -> Result<Option<Contract>, io::Error> {
         Ok(self.memory.contracts.remove(key))
     }
 }
 
 impl StorageSize<ContractsRawCode> for MemoryStorage {
-    fn size_of_value(&self, key: &ContractId) -> Result<Option<usize>, Infallible> {
+    fn size_of_value(&self, key: &ContractId) -> Result<Option<usize>, io::Error> {
         Ok(self.memory.contracts.get(key).map(|c| c.as_ref().len()))
     }
 }
@@ -146,72 +368,63 @@ impl StorageRead<ContractsRawCode> for MemoryStorage {
 }
 
 impl StorageInspect<ContractsInfo> for MemoryStorage {
-    type Error = Infallible;
+    type Error = io::Error;
 
-    fn get(&self, key: &ContractId) -> Result<Option<Cow<'_, (Salt, Bytes32)>>, Infallible> {
+    fn get(&self, key: &ContractId) -> Result<Option<Cow<'_, (Salt, Bytes32)>>, io::Error> {
         Ok(self.memory.contract_code_root.get(key).map(Cow::Borrowed))
     }
 
-    fn contains_key(&self, key: &ContractId) -> Result<bool, Infallible> {
+    fn contains_key(&self, key: &ContractId) -> Result<bool, io::Error> {
         Ok(self.memory.contract_code_root.contains_key(key))
     }
 }
 
 impl StorageMutate<ContractsInfo> for MemoryStorage {
-    fn insert(&mut self, key: &ContractId, value: &(Salt, Bytes32)) -> Result<Option<(Salt, Bytes32)>, Infallible> {
+    fn insert(&mut self, key: &ContractId, value: &(Salt, Bytes32)) -> Result<Option<(Salt, Bytes32)>, io::Error> {
         Ok(self.memory.contract_code_root.insert(*key, *value))
     }
 
-    fn remove(&mut self, key: &ContractId) -> Result<Option<(Salt, Bytes32)>, Infallible> {
+    fn remove(&mut self, key: &ContractId) -> Result<Option<(Salt, Bytes32)>, io::Error> {
         Ok(self.memory.contract_code_root.remove(key))
     }
 }
 
 impl StorageInspect<ContractsAssets> for MemoryStorage {
-    type Error = Infallible;
+    type Error = io::Error;
 
-    fn get(&self, key: &<ContractsAssets as Mappable>::Key) -> Result<Option<Cow<'_, Word>>, Infallible> {
+    fn get(&self, key: &<ContractsAssets as Mappable>::Key) -> Result<Option<Cow<'_, Word>>, io::Error> {
         Ok(self.memory.balances.get(key).map(Cow::Borrowed))
     }
 
-    fn contains_key(&self, key: &<ContractsAssets as Mappable>::Key) -> Result<bool, Infallible> {
+    fn contains_key(&self, key: &<ContractsAssets as Mappable>::Key) -> Result<bool, io::Error> {
         Ok(self.memory.balances.contains_key(key))
     }
 }
 
 impl StorageMutate<ContractsAssets> for MemoryStorage {
-    fn insert(&mut self, key: &<ContractsAssets as Mappable>::Key, value: &Word) -> Result<Option<Word>, Infallible> {
-        Ok(self.memory.balances.insert(*key, *value))
+    fn insert(&mut self, key: &<ContractsAssets as Mappable>::Key, value: &Word) -> Result<Option<Word>, io::Error> {
+        Ok(self.memory.balance_insert(*key, *value))
     }
 
-    fn remove(&mut self, key: &<ContractsAssets as Mappable>::Key) -> Result<Option<Word>, Infallible> {
-        Ok(self.memory.balances.remove(key))
+    fn remove(&mut self, key: &<ContractsAssets as Mappable>::Key) -> Result<Option<Word>, io::Error> {
+        Ok(self.memory.balance_remove(key))
     }
 }
 
 impl MerkleRootStorage<ContractId, ContractsAssets> for MemoryStorage {
-    fn root(&self, parent: &ContractId) -> Result<MerkleRoot, Infallible> {
-        let root = self
-            .memory
-            .balances
-            .iter()
-            .filter_map(|(key, balance)| (key.contract_id() == parent).then_some((key.asset_id(), balance)))
-            .sorted_by_key(|t| t.0)
-            .map(|(_, &balance)| balance)
-            .map(Word::to_be_bytes);
-
-        Ok(crypto::ephemeral_merkle_root(root).into())
+    fn root(&self, parent: &ContractId) -> Result<MerkleRoot, io::Error> {
+        Ok(MemoryStorageInner::tree_root(&self.memory.balances_trees, parent))
     }
 }
 
 impl StorageInspect<ContractsState> for MemoryStorage {
-    type Error = Infallible;
+    type Error = io::Error;
 
-    fn get(&self, key: &<ContractsState as Mappable>::Key) -> Result<Option<Cow<'_, Bytes32>>, Infallible> {
+    fn get(&self, key: &<ContractsState as Mappable>::Key) -> Result<Option<Cow<'_, Bytes32>>, io::Error> {
         Ok(self.memory.contract_state.get(key).map(Cow::Borrowed))
     }
 
-    fn contains_key(&self, key: &<ContractsState as Mappable>::Key) -> Result<bool, Infallible> {
+    fn contains_key(&self, key: &<ContractsState as Mappable>::Key) -> Result<bool, io::Error> {
         Ok(self.memory.contract_state.contains_key(key))
     }
 }
@@ -221,33 +434,25 @@ impl StorageMutate<ContractsState> for MemoryStorage {
         &mut self,
         key: &<ContractsState as Mappable>::Key,
         value: &Bytes32,
-    ) -> Result<Option<Bytes32>, Infallible> {
-        Ok(self.memory.contract_state.insert(*key, *value))
+    ) -> Result<Option<Bytes32>, io::Error> {
+        Ok(self.memory.state_insert(*key, *value))
     }
 
-    fn remove(&mut self, key: &<ContractsState as Mappable>::Key) -> Result<Option<Bytes32>, Infallible> {
-        Ok(self.memory.contract_state.remove(key))
+    fn remove(&mut self, key: &<ContractsState as Mappable>::Key) -> Result<Option<Bytes32>, io::Error> {
+        Ok(self.memory.state_remove(key))
     }
 }
 
 impl MerkleRootStorage<ContractId, ContractsState> for MemoryStorage {
-    fn root(&self, parent: &ContractId) -> Result<MerkleRoot, Infallible> {
-        let root = self
-            .memory
-            .contract_state
-            .iter()
-            .filter_map(|(key, value)| (key.contract_id() == parent).then_some((key.state_key(), value)))
-            .sorted_by_key(|t| t.0)
-            .map(|(_, value)| value);
-
-        Ok(crypto::ephemeral_merkle_root(root).into())
+    fn root(&self, parent: &ContractId) -> Result<MerkleRoot, io::Error> {
+        Ok(MemoryStorageInner::tree_root(&self.memory.contract_state_trees, parent))
     }
 }
 
 impl InterpreterStorage for MemoryStorage {
-    type DataError = Infallible;
+    type DataError = io::Error;
 
-    fn block_height(&self) -> Result<u32, Infallible> {
+    fn block_height(&self) -> Result<u32, io::Error> {
         Ok(self.block_height)
     }
 
@@ -255,15 +460,24 @@ impl InterpreterStorage for MemoryStorage {
         const GENESIS: Tai64 = Tai64::UNIX_EPOCH;
         const INTERVAL: Word = 10;
 
-        Ok((GENESIS + (height as Word * INTERVAL)).0)
+        Ok(self
+            .history_block(height)?
+            .map(|block| block.timestamp)
+            .unwrap_or_else(|| (GENESIS + (height as Word * INTERVAL)).0))
     }
 
-    fn block_hash(&self, block_height: u32) -> Result<Bytes32, Infallible> {
-        Ok(Hasher::hash(block_height.to_be_bytes()))
+    fn block_hash(&self, block_height: u32) -> Result<Bytes32, io::Error> {
+        Ok(self
+            .history_block(block_height)?
+            .map(|block| block.hash)
+            .unwrap_or_else(|| Hasher::hash(block_height.to_be_bytes())))
     }
 
-    fn coinbase(&self) -> Result<Address, Infallible> {
-        Ok(self.coinbase)
+    fn coinbase(&self) -> Result<Address, io::Error> {
+        Ok(self
+            .history_block(self.block_height)?
+            .map(|block| block.coinbase)
+            .unwrap_or(self.coinbase))
     }
 
     fn merkle_contract_state_range(
@@ -277,30 +491,23 @@ impl InterpreterStorage for MemoryStorage {
         let mut iter = self.memory.contract_state.range(start..end);
 
         let mut next_item = iter.next();
-        Ok(std::iter::successors(Some(**start_key), |n| {
-            let mut n = *n;
-            if add_one(&mut n) {
-                None
-            } else {
-                Some(n)
-            }
-        })
-        .map(|next_key: [u8; 32]| match next_item.take() {
-            Some((k, v)) => match next_key.cmp(k.state_key()) {
-                std::cmp::Ordering::Less => {
-                    next_item = Some((k, v));
-                    None
-                }
-                std::cmp::Ordering::Equal => {
-                    next_item = iter.next();
-                    Some(Cow::Borrowed(v))
-                }
-                std::cmp::Ordering::Greater => None,
-            },
-            None => None,
-        })
-        .take(range as usize)
-        .collect())
+        Ok(state_keys(start_key)
+            .map(|next_key| match next_item.take() {
+                Some((k, v)) => match next_key.cmp(k.state_key()) {
+                    std::cmp::Ordering::Less => {
+                        next_item = Some((k, v));
+                        None
+                    }
+                    std::cmp::Ordering::Equal => {
+                        next_item = iter.next();
+                        Some(Cow::Borrowed(v))
+                    }
+                    std::cmp::Ordering::Greater => None,
+                },
+                None => None,
+            })
+            .take(range as usize)
+            .collect())
     }
 
     fn merkle_contract_state_insert_range(
@@ -310,22 +517,10 @@ impl InterpreterStorage for MemoryStorage {
         values: &[Bytes32],
     ) -> Result<Option<()>, Self::DataError> {
         let mut any_unset_key = false;
-        let values: Vec<_> = std::iter::successors(Some(**start_key), |n| {
-            let mut n = *n;
-            if add_one(&mut n) {
-                None
-            } else {
-                Some(n)
-            }
-        })
-        .zip(values)
-        .map(|(key, value)| {
-            let key = (contract, &Bytes32::from(key)).into();
-            any_unset_key |= !self.memory.contract_state.contains_key(&key);
-            (key, *value)
-        })
-        .collect();
-        self.memory.contract_state.extend(values);
+        state_keys(start_key).zip(values).for_each(|(key, value)| {
+            let key = (contract, &key).into();
+            any_unset_key |= self.memory.state_insert(key, *value).is_none();
+        });
         Ok((!any_unset_key).then_some(()))
     }
 
@@ -336,27 +531,23 @@ impl InterpreterStorage for MemoryStorage {
         range: Word,
     ) -> Result<Option<()>, Self::DataError> {
         let mut all_set_key = true;
-        let mut values: std::collections::HashSet<_> = std::iter::successors(Some(**start_key), |n| {
-            let mut n = *n;
-            if add_one(&mut n) {
-                None
-            } else {
-                Some(n)
-            }
-        })
-        .take(range as usize)
-        .collect();
-        self.memory.contract_state.retain(|key, _| {
-            let c = key.contract_id();
-            let k = key.state_key();
-            let r = values.remove(&**k);
-            all_set_key &= c == contract && r;
-            c != contract || !r
+        state_keys(start_key).take(range as usize).for_each(|key| {
+            let key = (contract, &key).into();
+            all_set_key &= self.memory.state_remove(&key).is_some();
         });
-        Ok((all_set_key && values.is_empty()).then_some(()))
+        Ok(all_set_key.then_some(()))
     }
 }
 
+/// Consecutive state keys starting at `start_key`, up to the last key.
+pub(super) fn state_keys(start_key: &Bytes32) -> impl Iterator<Item = Bytes32> {
+    std::iter::successors(Some(*start_key), |key| {
+        let mut key = **key;
+        (!add_one(&mut key)).then_some(key.into())
+    })
+}
+
+/// Increment a 32 bytes big-endian key, returning `true` on overflow.
 fn add_one(a: &mut [u8; 32]) -> bool {
     let right = u128::from_be_bytes(a[16..].try_into().unwrap());
     let (right, of) = right.overflowing_add(1);
@@ -401,4 +592,75 @@ mod tests {
             .map(|v| v.map(|v| v.into_owned()))
             .collect()
     }
+
+    #[test]
+    fn state_root_matches_initial_state_root() {
+        use fuel_tx::StorageSlot;
+
+        let contract = ContractId::from([1; 32]);
+        let other = ContractId::from([2; 32]);
+        let slots: Vec<_> = (0..4u8)
+            .map(|k| StorageSlot::new(key(k).into(), [k + 1; 32].into()))
+            .collect();
+
+        let mut mem = MemoryStorage::default();
+        let root = |mem: &MemoryStorage| mem.storage::<ContractsState>().root(&contract).unwrap();
+
+        assert_eq!(root(&mem), *Contract::default_state_root());
+
+        mem.merkle_contract_state_insert_range(&contract, &key(0).into(), &[Bytes32::zeroed(); 2])
+            .unwrap();
+        mem.merkle_contract_state_insert(&other, &key(0).into(), &Bytes32::zeroed())
+            .unwrap();
+        for slot in &slots {
+            mem.merkle_contract_state_insert(&contract, slot.key(), slot.value())
+                .unwrap();
+        }
+
+        assert_eq!(root(&mem), *Contract::initial_state_root(slots.iter()));
+
+        mem.commit();
+        mem.merkle_contract_state_remove_range(&contract, &key(0).into(), 2)
+            .unwrap();
+
+        assert_eq!(root(&mem), *Contract::initial_state_root(slots[2..].iter()));
+
+        mem.revert();
+
+        assert_eq!(root(&mem), *Contract::initial_state_root(slots.iter()));
+
+        mem.merkle_contract_state_remove_range(&contract, &key(0).into(), 4)
+            .unwrap();
+
+        assert_eq!(root(&mem), *Contract::default_state_root());
+    }
+
+    #[test]
+    fn balance_root_is_sparse_merkle_root() {
+        use fuel_storage::StorageAsMut;
+        use fuel_types::AssetId;
+
+        let contract = ContractId::from([1; 32]);
+        let assets = [AssetId::from([1; 32]), AssetId::from([2; 32])];
+
+        let mut mem = MemoryStorage::default();
+        let mut expected = SparseMerkleTree::new();
+
+        for (i, asset) in assets.iter().enumerate() {
+            mem.merkle_contract_asset_id_balance_insert(&contract, asset, i as Word + 10)
+                .unwrap();
+            expected.update(asset, &(i as Word + 10).to_be_bytes());
+        }
+
+        let root = |mem: &MemoryStorage| mem.storage::<ContractsAssets>().root(&contract).unwrap();
+
+        assert_eq!(root(&mem), expected.root());
+
+        mem.storage_as_mut::<ContractsAssets>()
+            .remove(&(&contract, &assets[0]).into())
+            .unwrap();
+        expected.delete(&assets[0]);
+
+        assert_eq!(root(&mem), expected.root());
+    }
 }
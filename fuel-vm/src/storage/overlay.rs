@@ -0,0 +1,280 @@
+//! Storage layering uncommitted changes on top of a borrowed storage.
+
+use super::{
+    ContractsAssets, ContractsInfo, ContractsRawCode, ContractsState, ForkStorage, InterpreterStorage, MemoryStorage,
+    StateFetcher,
+};
+
+use fuel_storage::{Mappable, MerkleRoot, MerkleRootStorage, StorageInspect, StorageMutate, StorageRead, StorageSize};
+use fuel_tx::Contract;
+use fuel_types::{Address, AssetId, Bytes32, ContractId, Salt, Word};
+
+use std::borrow::Cow;
+use std::io;
+
+/// Serve the state of a borrowed interpreter storage to a [`ForkStorage`].
+#[derive(Debug)]
+struct StorageFetcher<'a, S>(&'a S);
+
+impl<S> StateFetcher for StorageFetcher<'_, S>
+where
+    S: InterpreterStorage,
+{
+    fn contract_code(&self, contract: &ContractId) -> io::Result<Option<Contract>> {
+        Ok(StorageInspect::<ContractsRawCode>::get(self.0, contract)
+            .map_err(Into::into)?
+            .map(Cow::into_owned))
+    }
+
+    fn contract_info(&self, contract: &ContractId) -> io::Result<Option<(Salt, Bytes32)>> {
+        Ok(StorageInspect::<ContractsInfo>::get(self.0, contract)
+            .map_err(Into::into)?
+            .map(Cow::into_owned))
+    }
+
+    fn contract_state(&self, contract: &ContractId, key: &Bytes32) -> io::Result<Option<Bytes32>> {
+        Ok(StorageInspect::<ContractsState>::get(self.0, &(contract, key).into())
+            .map_err(Into::into)?
+            .map(Cow::into_owned))
+    }
+
+    fn contract_balance(&self, contract: &ContractId, asset_id: &AssetId) -> io::Result<Option<Word>> {
+        Ok(
+            StorageInspect::<ContractsAssets>::get(self.0, &(contract, asset_id).into())
+                .map_err(Into::into)?
+                .map(Cow::into_owned),
+        )
+    }
+}
+
+/// Storage reading through to a borrowed [`InterpreterStorage`] and keeping every write in memory,
+/// e.g. to dry-run a transaction.
+///
+/// The borrowed storage is never modified, and the changes are dropped with the overlay. Block data
+/// is served by the borrowed storage.
+///
+/// The merkle roots can't be computed and fail, as for [`ForkStorage`].
+#[derive(Debug)]
+pub struct OverlayStorage<'a, S> {
+    fork: ForkStorage<StorageFetcher<'a, S>>,
+}
+
+impl<'a, S> OverlayStorage<'a, S>
+where
+    S: InterpreterStorage,
+{
+    /// Layer an empty overlay on top of `storage`.
+    pub fn new(storage: &'a S) -> Self {
+        Self {
+            fork: ForkStorage::new(StorageFetcher(storage), MemoryStorage::default()),
+        }
+    }
+
+    /// The storage underneath the overlay.
+    pub const fn storage(&self) -> &'a S {
+        self.fork.fetcher().0
+    }
+}
+
+impl<S> StorageInspect<ContractsRawCode> for OverlayStorage<'_, S>
+where
+    S: InterpreterStorage,
+{
+    type Error = io::Error;
+
+    fn get(&self, key: &ContractId) -> io::Result<Option<Cow<'_, Contract>>> {
+        StorageInspect::<ContractsRawCode>::get(&self.fork, key)
+    }
+
+    fn contains_key(&self, key: &ContractId) -> io::Result<bool> {
+        StorageInspect::<ContractsRawCode>::contains_key(&self.fork, key)
+    }
+}
+
+impl<S> StorageMutate<ContractsRawCode> for OverlayStorage<'_, S>
+where
+    S: InterpreterStorage,
+{
+    fn insert(&mut self, key: &ContractId, value: &[u8]) -> io::Result<Option<Contract>> {
+        StorageMutate::<ContractsRawCode>::insert(&mut self.fork, key, value)
+    }
+
+    fn remove(&mut self, key: &ContractId) -> io::Result<Option<Contract>> {
+        StorageMutate::<ContractsRawCode>::remove(&mut self.fork, key)
+    }
+}
+
+impl<S> StorageSize<ContractsRawCode> for OverlayStorage<'_, S>
+where
+    S: InterpreterStorage,
+{
+    fn size_of_value(&self, key: &ContractId) -> io::Result<Option<usize>> {
+        StorageSize::<ContractsRawCode>::size_of_value(&self.fork, key)
+    }
+}
+
+impl<S> StorageRead<ContractsRawCode> for OverlayStorage<'_, S>
+where
+    S: InterpreterStorage,
+{
+    fn read(&self, key: &ContractId, buf: &mut [u8]) -> io::Result<Option<usize>> {
+        StorageRead::<ContractsRawCode>::read(&self.fork, key, buf)
+    }
+
+    fn read_alloc(&self, key: &ContractId) -> io::Result<Option<Vec<u8>>> {
+        StorageRead::<ContractsRawCode>::read_alloc(&self.fork, key)
+    }
+}
+
+impl<S> StorageInspect<ContractsInfo> for OverlayStorage<'_, S>
+where
+    S: InterpreterStorage,
+{
+    type Error = io::Error;
+
+    fn get(&self, key: &ContractId) -> io::Result<Option<Cow<'_, (Salt, Bytes32)>>> {
+        StorageInspect::<ContractsInfo>::get(&self.fork, key)
+    }
+
+    fn contains_key(&self, key: &ContractId) -> io::Result<bool> {
+        StorageInspect::<ContractsInfo>::contains_key(&self.fork, key)
+    }
+}
+
+impl<S> StorageMutate<ContractsInfo> for OverlayStorage<'_, S>
+where
+    S: InterpreterStorage,
+{
+    fn insert(&mut self, key: &ContractId, value: &(Salt, Bytes32)) -> io::Result<Option<(Salt, Bytes32)>> {
+        StorageMutate::<ContractsInfo>::insert(&mut self.fork, key, value)
+    }
+
+    fn remove(&mut self, key: &ContractId) -> io::Result<Option<(Salt, Bytes32)>> {
+        StorageMutate::<ContractsInfo>::remove(&mut self.fork, key)
+    }
+}
+
+impl<S> StorageInspect<ContractsAssets> for OverlayStorage<'_, S>
+where
+    S: InterpreterStorage,
+{
+    type Error = io::Error;
+
+    fn get(&self, key: &<ContractsAssets as Mappable>::Key) -> io::Result<Option<Cow<'_, Word>>> {
+        StorageInspect::<ContractsAssets>::get(&self.fork, key)
+    }
+
+    fn contains_key(&self, key: &<ContractsAssets as Mappable>::Key) -> io::Result<bool> {
+        StorageInspect::<ContractsAssets>::contains_key(&self.fork, key)
+    }
+}
+
+impl<S> StorageMutate<ContractsAssets> for OverlayStorage<'_, S>
+where
+    S: InterpreterStorage,
+{
+    fn insert(&mut self, key: &<ContractsAssets as Mappable>::Key, value: &Word) -> io::Result<Option<Word>> {
+        StorageMutate::<ContractsAssets>::insert(&mut self.fork, key, value)
+    }
+
+    fn remove(&mut self, key: &<ContractsAssets as Mappable>::Key) -> io::Result<Option<Word>> {
+        StorageMutate::<ContractsAssets>::remove(&mut self.fork, key)
+    }
+}
+
+impl<S> MerkleRootStorage<ContractId, ContractsAssets> for OverlayStorage<'_, S>
+where
+    S: InterpreterStorage,
+{
+    fn root(&self, parent: &ContractId) -> io::Result<MerkleRoot> {
+        MerkleRootStorage::<ContractId, ContractsAssets>::root(&self.fork, parent)
+    }
+}
+
+impl<S> StorageInspect<ContractsState> for OverlayStorage<'_, S>
+where
+    S: InterpreterStorage,
+{
+    type Error = io::Error;
+
+    fn get(&self, key: &<ContractsState as Mappable>::Key) -> io::Result<Option<Cow<'_, Bytes32>>> {
+        StorageInspect::<ContractsState>::get(&self.fork, key)
+    }
+
+    fn contains_key(&self, key: &<ContractsState as Mappable>::Key) -> io::Result<bool> {
+        StorageInspect::<ContractsState>::contains_key(&self.fork, key)
+    }
+}
+
+impl<S> StorageMutate<ContractsState> for OverlayStorage<'_, S>
+where
+    S: InterpreterStorage,
+{
+    fn insert(&mut self, key: &<ContractsState as Mappable>::Key, value: &Bytes32) -> io::Result<Option<Bytes32>> {
+        StorageMutate::<ContractsState>::insert(&mut self.fork, key, value)
+    }
+
+    fn remove(&mut self, key: &<ContractsState as Mappable>::Key) -> io::Result<Option<Bytes32>> {
+        StorageMutate::<ContractsState>::remove(&mut self.fork, key)
+    }
+}
+
+impl<S> MerkleRootStorage<ContractId, ContractsState> for OverlayStorage<'_, S>
+where
+    S: InterpreterStorage,
+{
+    fn root(&self, parent: &ContractId) -> io::Result<MerkleRoot> {
+        MerkleRootStorage::<ContractId, ContractsState>::root(&self.fork, parent)
+    }
+}
+
+impl<S> InterpreterStorage for OverlayStorage<'_, S>
+where
+    S: InterpreterStorage,
+{
+    type DataError = io::Error;
+
+    fn block_height(&self) -> io::Result<u32> {
+        self.storage().block_height().map_err(Into::into)
+    }
+
+    fn timestamp(&self, height: u32) -> io::Result<Word> {
+        self.storage().timestamp(height).map_err(Into::into)
+    }
+
+    fn block_hash(&self, block_height: u32) -> io::Result<Bytes32> {
+        self.storage().block_hash(block_height).map_err(Into::into)
+    }
+
+    fn coinbase(&self) -> io::Result<Address> {
+        self.storage().coinbase().map_err(Into::into)
+    }
+
+    fn merkle_contract_state_range(
+        &self,
+        id: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> io::Result<Vec<Option<Cow<'_, Bytes32>>>> {
+        self.fork.merkle_contract_state_range(id, start_key, range)
+    }
+
+    fn merkle_contract_state_insert_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: &[Bytes32],
+    ) -> io::Result<Option<()>> {
+        self.fork
+            .merkle_contract_state_insert_range(contract, start_key, values)
+    }
+
+    fn merkle_contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> io::Result<Option<()>> {
+        self.fork.merkle_contract_state_remove_range(contract, start_key, range)
+    }
+}
@@ -0,0 +1,892 @@
+//! Merkle proofs of the contract state and balances accessed by a transaction, to execute it
+//! again without the full storage.
+
+use super::fork::partial_state_root;
+use super::memory::state_keys;
+use super::{
+    ContractsAssetKey, ContractsAssets, ContractsInfo, ContractsRawCode, ContractsState, ContractsStateKey,
+    InterpreterStorage, MemoryStorage,
+};
+
+use fuel_merkle::sparse::{self, Proof};
+use fuel_storage::{
+    Mappable, MerkleRoot, MerkleRootStorage, StorageAsRef, StorageInspect, StorageMutate, StorageRead, StorageSize,
+};
+use fuel_tx::{Contract, Input};
+use fuel_types::{Address, AssetId, Bytes32, ContractId, Salt, Word};
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashSet};
+use std::io;
+
+/// Value of a state slot or balance, with its proof against the root of the contract.
+///
+/// `None` means the slot is unset, and the proof shows its absence from the tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlotProof<V> {
+    /// Value of the slot.
+    pub value: Option<V>,
+    /// Proof of the value against the state or balance root of the contract.
+    pub proof: Proof,
+}
+
+impl SlotProof<Bytes32> {
+    /// Check the proof of the state slot `key` against the state root of the contract.
+    pub fn verify(&self, root: &Bytes32, key: &Bytes32) -> bool {
+        sparse::verify(root, key, self.value.as_ref().map(AsRef::as_ref), &self.proof)
+    }
+}
+
+impl SlotProof<Word> {
+    /// Check the proof of the balance of `asset_id` against the balance root of the contract.
+    pub fn verify(&self, root: &Bytes32, asset_id: &AssetId) -> bool {
+        let value = self.value.map(Word::to_be_bytes);
+
+        sparse::verify(root, asset_id, value.as_ref().map(AsRef::as_ref), &self.proof)
+    }
+}
+
+/// Proofs of every state slot and balance accessed by a transaction, against the roots the
+/// contracts had before its execution.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StorageProofs {
+    /// Proofs of the state slots.
+    pub state: BTreeMap<ContractsStateKey, SlotProof<Bytes32>>,
+    /// Proofs of the balances.
+    pub balances: BTreeMap<ContractsAssetKey, SlotProof<Word>>,
+}
+
+impl StorageProofs {
+    /// Check every proof against the `state_root` and `balance_root` of the matching
+    /// [`Input::Contract`].
+    pub fn verify(&self, inputs: &[Input]) -> io::Result<()> {
+        let roots: BTreeMap<_, _> = inputs
+            .iter()
+            .filter_map(|input| match input {
+                Input::Contract {
+                    contract_id,
+                    state_root,
+                    balance_root,
+                    ..
+                } => Some((*contract_id, (*state_root, *balance_root))),
+                _ => None,
+            })
+            .collect();
+
+        let roots_of = |contract: &ContractId| {
+            roots.get(contract).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("no contract input for the proofs of contract {contract}"),
+                )
+            })
+        };
+
+        for (key, proof) in &self.state {
+            let (state_root, _) = roots_of(key.contract_id())?;
+
+            if !proof.verify(state_root, key.state_key()) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "invalid proof for state slot {} of contract {}",
+                        key.state_key(),
+                        key.contract_id()
+                    ),
+                ));
+            }
+        }
+
+        for (key, proof) in &self.balances {
+            let (_, balance_root) = roots_of(key.contract_id())?;
+
+            if !proof.verify(balance_root, key.asset_id()) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "invalid proof for balance of asset {} of contract {}",
+                        key.asset_id(),
+                        key.contract_id()
+                    ),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Source of the proofs of the state slots and balances of contracts.
+pub trait StorageProver {
+    /// Prove a state slot of a contract.
+    fn contract_state_proof(&self, contract: &ContractId, key: &Bytes32) -> io::Result<SlotProof<Bytes32>>;
+
+    /// Prove an asset balance of a contract.
+    fn contract_balance_proof(&self, contract: &ContractId, asset_id: &AssetId) -> io::Result<SlotProof<Word>>;
+}
+
+impl<P> StorageProver for &P
+where
+    P: StorageProver + ?Sized,
+{
+    fn contract_state_proof(&self, contract: &ContractId, key: &Bytes32) -> io::Result<SlotProof<Bytes32>> {
+        (*self).contract_state_proof(contract, key)
+    }
+
+    fn contract_balance_proof(&self, contract: &ContractId, asset_id: &AssetId) -> io::Result<SlotProof<Word>> {
+        (*self).contract_balance_proof(contract, asset_id)
+    }
+}
+
+impl StorageProver for MemoryStorage {
+    fn contract_state_proof(&self, contract: &ContractId, key: &Bytes32) -> io::Result<SlotProof<Bytes32>> {
+        let key = (contract, key).into();
+
+        Ok(SlotProof {
+            value: self.storage::<ContractsState>().get(&key)?.map(Cow::into_owned),
+            proof: self.state_proof(&key),
+        })
+    }
+
+    fn contract_balance_proof(&self, contract: &ContractId, asset_id: &AssetId) -> io::Result<SlotProof<Word>> {
+        let key = (contract, asset_id).into();
+
+        Ok(SlotProof {
+            value: self.storage::<ContractsAssets>().get(&key)?.map(Cow::into_owned),
+            proof: self.balance_proof(&key),
+        })
+    }
+}
+
+/// Storage recording the proof of every state slot and balance it accesses.
+///
+/// The proofs come from `prover`, which must hold the state from before the execution: e.g. a
+/// clone of a [`MemoryStorage`] taken before wrapping it. They can then be checked by a
+/// [`VerifierStorage`] against the roots declared by the contract inputs.
+#[derive(Debug)]
+pub struct ProvingStorage<S, P> {
+    storage: S,
+    prover: P,
+    proofs: RefCell<StorageProofs>,
+}
+
+impl<S, P> ProvingStorage<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    /// Record the proofs of the accesses to `storage`, built by `prover`.
+    pub fn new(storage: S, prover: P) -> Self {
+        Self {
+            storage,
+            prover,
+            proofs: Default::default(),
+        }
+    }
+
+    /// Proofs recorded so far.
+    pub fn proofs(&self) -> StorageProofs {
+        self.proofs.borrow().clone()
+    }
+
+    /// Underlying storage and recorded proofs.
+    pub fn into_inner(self) -> (S, StorageProofs) {
+        (self.storage, self.proofs.into_inner())
+    }
+
+    fn prove_state(&self, key: &ContractsStateKey) -> io::Result<()> {
+        if !self.proofs.borrow().state.contains_key(key) {
+            let proof = self.prover.contract_state_proof(key.contract_id(), key.state_key())?;
+            self.proofs.borrow_mut().state.insert(*key, proof);
+        }
+
+        Ok(())
+    }
+
+    fn prove_balance(&self, key: &ContractsAssetKey) -> io::Result<()> {
+        if !self.proofs.borrow().balances.contains_key(key) {
+            let proof = self.prover.contract_balance_proof(key.contract_id(), key.asset_id())?;
+            self.proofs.borrow_mut().balances.insert(*key, proof);
+        }
+
+        Ok(())
+    }
+
+    fn prove_state_range(&self, contract: &ContractId, start_key: &Bytes32, range: Word) -> io::Result<()> {
+        state_keys(start_key)
+            .take(range as usize)
+            .try_for_each(|key| self.prove_state(&(contract, &key).into()))
+    }
+}
+
+impl<S, P> StorageInspect<ContractsRawCode> for ProvingStorage<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    type Error = io::Error;
+
+    fn get(&self, key: &ContractId) -> io::Result<Option<Cow<'_, Contract>>> {
+        StorageInspect::<ContractsRawCode>::get(&self.storage, key).map_err(Into::into)
+    }
+
+    fn contains_key(&self, key: &ContractId) -> io::Result<bool> {
+        StorageInspect::<ContractsRawCode>::contains_key(&self.storage, key).map_err(Into::into)
+    }
+}
+
+impl<S, P> StorageMutate<ContractsRawCode> for ProvingStorage<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    fn insert(&mut self, key: &ContractId, value: &[u8]) -> io::Result<Option<Contract>> {
+        StorageMutate::<ContractsRawCode>::insert(&mut self.storage, key, value).map_err(Into::into)
+    }
+
+    fn remove(&mut self, key: &ContractId) -> io::Result<Option<Contract>> {
+        StorageMutate::<ContractsRawCode>::remove(&mut self.storage, key).map_err(Into::into)
+    }
+}
+
+impl<S, P> StorageSize<ContractsRawCode> for ProvingStorage<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    fn size_of_value(&self, key: &ContractId) -> io::Result<Option<usize>> {
+        StorageSize::<ContractsRawCode>::size_of_value(&self.storage, key).map_err(Into::into)
+    }
+}
+
+impl<S, P> StorageRead<ContractsRawCode> for ProvingStorage<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    fn read(&self, key: &ContractId, buf: &mut [u8]) -> io::Result<Option<usize>> {
+        StorageRead::<ContractsRawCode>::read(&self.storage, key, buf).map_err(Into::into)
+    }
+
+    fn read_alloc(&self, key: &ContractId) -> io::Result<Option<Vec<u8>>> {
+        StorageRead::<ContractsRawCode>::read_alloc(&self.storage, key).map_err(Into::into)
+    }
+}
+
+impl<S, P> StorageInspect<ContractsInfo> for ProvingStorage<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    type Error = io::Error;
+
+    fn get(&self, key: &ContractId) -> io::Result<Option<Cow<'_, (Salt, Bytes32)>>> {
+        StorageInspect::<ContractsInfo>::get(&self.storage, key).map_err(Into::into)
+    }
+
+    fn contains_key(&self, key: &ContractId) -> io::Result<bool> {
+        StorageInspect::<ContractsInfo>::contains_key(&self.storage, key).map_err(Into::into)
+    }
+}
+
+impl<S, P> StorageMutate<ContractsInfo> for ProvingStorage<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    fn insert(&mut self, key: &ContractId, value: &(Salt, Bytes32)) -> io::Result<Option<(Salt, Bytes32)>> {
+        StorageMutate::<ContractsInfo>::insert(&mut self.storage, key, value).map_err(Into::into)
+    }
+
+    fn remove(&mut self, key: &ContractId) -> io::Result<Option<(Salt, Bytes32)>> {
+        StorageMutate::<ContractsInfo>::remove(&mut self.storage, key).map_err(Into::into)
+    }
+}
+
+impl<S, P> StorageInspect<ContractsAssets> for ProvingStorage<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    type Error = io::Error;
+
+    fn get(&self, key: &<ContractsAssets as Mappable>::Key) -> io::Result<Option<Cow<'_, Word>>> {
+        self.prove_balance(key)?;
+
+        StorageInspect::<ContractsAssets>::get(&self.storage, key).map_err(Into::into)
+    }
+
+    fn contains_key(&self, key: &<ContractsAssets as Mappable>::Key) -> io::Result<bool> {
+        self.prove_balance(key)?;
+
+        StorageInspect::<ContractsAssets>::contains_key(&self.storage, key).map_err(Into::into)
+    }
+}
+
+impl<S, P> StorageMutate<ContractsAssets> for ProvingStorage<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    fn insert(&mut self, key: &<ContractsAssets as Mappable>::Key, value: &Word) -> io::Result<Option<Word>> {
+        self.prove_balance(key)?;
+
+        StorageMutate::<ContractsAssets>::insert(&mut self.storage, key, value).map_err(Into::into)
+    }
+
+    fn remove(&mut self, key: &<ContractsAssets as Mappable>::Key) -> io::Result<Option<Word>> {
+        self.prove_balance(key)?;
+
+        StorageMutate::<ContractsAssets>::remove(&mut self.storage, key).map_err(Into::into)
+    }
+}
+
+impl<S, P> MerkleRootStorage<ContractId, ContractsAssets> for ProvingStorage<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    fn root(&self, parent: &ContractId) -> io::Result<MerkleRoot> {
+        MerkleRootStorage::<ContractId, ContractsAssets>::root(&self.storage, parent).map_err(Into::into)
+    }
+}
+
+impl<S, P> StorageInspect<ContractsState> for ProvingStorage<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    type Error = io::Error;
+
+    fn get(&self, key: &<ContractsState as Mappable>::Key) -> io::Result<Option<Cow<'_, Bytes32>>> {
+        self.prove_state(key)?;
+
+        StorageInspect::<ContractsState>::get(&self.storage, key).map_err(Into::into)
+    }
+
+    fn contains_key(&self, key: &<ContractsState as Mappable>::Key) -> io::Result<bool> {
+        self.prove_state(key)?;
+
+        StorageInspect::<ContractsState>::contains_key(&self.storage, key).map_err(Into::into)
+    }
+}
+
+impl<S, P> StorageMutate<ContractsState> for ProvingStorage<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    fn insert(&mut self, key: &<ContractsState as Mappable>::Key, value: &Bytes32) -> io::Result<Option<Bytes32>> {
+        self.prove_state(key)?;
+
+        StorageMutate::<ContractsState>::insert(&mut self.storage, key, value).map_err(Into::into)
+    }
+
+    fn remove(&mut self, key: &<ContractsState as Mappable>::Key) -> io::Result<Option<Bytes32>> {
+        self.prove_state(key)?;
+
+        StorageMutate::<ContractsState>::remove(&mut self.storage, key).map_err(Into::into)
+    }
+}
+
+impl<S, P> MerkleRootStorage<ContractId, ContractsState> for ProvingStorage<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    fn root(&self, parent: &ContractId) -> io::Result<MerkleRoot> {
+        MerkleRootStorage::<ContractId, ContractsState>::root(&self.storage, parent).map_err(Into::into)
+    }
+}
+
+impl<S, P> InterpreterStorage for ProvingStorage<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    type DataError = io::Error;
+
+    fn block_height(&self) -> io::Result<u32> {
+        self.storage.block_height().map_err(Into::into)
+    }
+
+    fn timestamp(&self, height: u32) -> io::Result<Word> {
+        self.storage.timestamp(height).map_err(Into::into)
+    }
+
+    fn block_hash(&self, block_height: u32) -> io::Result<Bytes32> {
+        self.storage.block_hash(block_height).map_err(Into::into)
+    }
+
+    fn coinbase(&self) -> io::Result<Address> {
+        self.storage.coinbase().map_err(Into::into)
+    }
+
+    fn merkle_contract_state_range(
+        &self,
+        id: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> io::Result<Vec<Option<Cow<'_, Bytes32>>>> {
+        self.prove_state_range(id, start_key, range)?;
+
+        self.storage
+            .merkle_contract_state_range(id, start_key, range)
+            .map_err(Into::into)
+    }
+
+    fn merkle_contract_state_insert_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: &[Bytes32],
+    ) -> io::Result<Option<()>> {
+        self.prove_state_range(contract, start_key, values.len() as Word)?;
+
+        self.storage
+            .merkle_contract_state_insert_range(contract, start_key, values)
+            .map_err(Into::into)
+    }
+
+    fn merkle_contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> io::Result<Option<()>> {
+        self.prove_state_range(contract, start_key, range)?;
+
+        self.storage
+            .merkle_contract_state_remove_range(contract, start_key, range)
+            .map_err(Into::into)
+    }
+}
+
+/// Storage serving the contract state and balances from verified [`StorageProofs`].
+///
+/// Contract code, contract info and block data come from the wrapped storage, e.g. a light or
+/// forked storage. Accessing a state slot or a balance without a proof fails with
+/// [`io::ErrorKind::NotFound`]. Writes are kept locally. The merkle roots can't be computed from
+/// the proven entries alone, so they fail with [`io::ErrorKind::Unsupported`].
+#[derive(Debug)]
+pub struct VerifierStorage<S> {
+    storage: S,
+    local: MemoryStorage,
+    state: HashSet<ContractsStateKey>,
+    balances: HashSet<ContractsAssetKey>,
+}
+
+impl<S> VerifierStorage<S>
+where
+    S: InterpreterStorage,
+{
+    /// Serve the state and balances proven by `proofs`, after checking them against the roots of
+    /// the contract inputs of the transaction.
+    pub fn new(storage: S, proofs: StorageProofs, inputs: &[Input]) -> io::Result<Self> {
+        proofs.verify(inputs)?;
+
+        let mut local = MemoryStorage::default();
+
+        for (key, proof) in &proofs.state {
+            if let Some(value) = &proof.value {
+                StorageMutate::<ContractsState>::insert(&mut local, key, value)?;
+            }
+        }
+
+        for (key, proof) in &proofs.balances {
+            if let Some(value) = &proof.value {
+                StorageMutate::<ContractsAssets>::insert(&mut local, key, value)?;
+            }
+        }
+
+        Ok(Self {
+            storage,
+            local,
+            state: proofs.state.into_keys().collect(),
+            balances: proofs.balances.into_keys().collect(),
+        })
+    }
+
+    /// Underlying storage.
+    pub fn into_inner(self) -> S {
+        self.storage
+    }
+
+    fn check_state(&self, key: &ContractsStateKey) -> io::Result<()> {
+        if self.state.contains(key) {
+            Ok(())
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!(
+                    "no proof for state slot {} of contract {}",
+                    key.state_key(),
+                    key.contract_id()
+                ),
+            ))
+        }
+    }
+
+    fn check_balance(&self, key: &ContractsAssetKey) -> io::Result<()> {
+        if self.balances.contains(key) {
+            Ok(())
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!(
+                    "no proof for balance of asset {} of contract {}",
+                    key.asset_id(),
+                    key.contract_id()
+                ),
+            ))
+        }
+    }
+
+    fn check_state_range(&self, contract: &ContractId, start_key: &Bytes32, range: Word) -> io::Result<()> {
+        state_keys(start_key)
+            .take(range as usize)
+            .try_for_each(|key| self.check_state(&(contract, &key).into()))
+    }
+}
+
+impl<S> StorageInspect<ContractsRawCode> for VerifierStorage<S>
+where
+    S: InterpreterStorage,
+{
+    type Error = io::Error;
+
+    fn get(&self, key: &ContractId) -> io::Result<Option<Cow<'_, Contract>>> {
+        StorageInspect::<ContractsRawCode>::get(&self.storage, key).map_err(Into::into)
+    }
+
+    fn contains_key(&self, key: &ContractId) -> io::Result<bool> {
+        StorageInspect::<ContractsRawCode>::contains_key(&self.storage, key).map_err(Into::into)
+    }
+}
+
+impl<S> StorageMutate<ContractsRawCode> for VerifierStorage<S>
+where
+    S: InterpreterStorage,
+{
+    fn insert(&mut self, key: &ContractId, value: &[u8]) -> io::Result<Option<Contract>> {
+        StorageMutate::<ContractsRawCode>::insert(&mut self.storage, key, value).map_err(Into::into)
+    }
+
+    fn remove(&mut self, key: &ContractId) -> io::Result<Option<Contract>> {
+        StorageMutate::<ContractsRawCode>::remove(&mut self.storage, key).map_err(Into::into)
+    }
+}
+
+impl<S> StorageSize<ContractsRawCode> for VerifierStorage<S>
+where
+    S: InterpreterStorage,
+{
+    fn size_of_value(&self, key: &ContractId) -> io::Result<Option<usize>> {
+        StorageSize::<ContractsRawCode>::size_of_value(&self.storage, key).map_err(Into::into)
+    }
+}
+
+impl<S> StorageRead<ContractsRawCode> for VerifierStorage<S>
+where
+    S: InterpreterStorage,
+{
+    fn read(&self, key: &ContractId, buf: &mut [u8]) -> io::Result<Option<usize>> {
+        StorageRead::<ContractsRawCode>::read(&self.storage, key, buf).map_err(Into::into)
+    }
+
+    fn read_alloc(&self, key: &ContractId) -> io::Result<Option<Vec<u8>>> {
+        StorageRead::<ContractsRawCode>::read_alloc(&self.storage, key).map_err(Into::into)
+    }
+}
+
+impl<S> StorageInspect<ContractsInfo> for VerifierStorage<S>
+where
+    S: InterpreterStorage,
+{
+    type Error = io::Error;
+
+    fn get(&self, key: &ContractId) -> io::Result<Option<Cow<'_, (Salt, Bytes32)>>> {
+        StorageInspect::<ContractsInfo>::get(&self.storage, key).map_err(Into::into)
+    }
+
+    fn contains_key(&self, key: &ContractId) -> io::Result<bool> {
+        StorageInspect::<ContractsInfo>::contains_key(&self.storage, key).map_err(Into::into)
+    }
+}
+
+impl<S> StorageMutate<ContractsInfo> for VerifierStorage<S>
+where
+    S: InterpreterStorage,
+{
+    fn insert(&mut self, key: &ContractId, value: &(Salt, Bytes32)) -> io::Result<Option<(Salt, Bytes32)>> {
+        StorageMutate::<ContractsInfo>::insert(&mut self.storage, key, value).map_err(Into::into)
+    }
+
+    fn remove(&mut self, key: &ContractId) -> io::Result<Option<(Salt, Bytes32)>> {
+        StorageMutate::<ContractsInfo>::remove(&mut self.storage, key).map_err(Into::into)
+    }
+}
+
+impl<S> StorageInspect<ContractsAssets> for VerifierStorage<S>
+where
+    S: InterpreterStorage,
+{
+    type Error = io::Error;
+
+    fn get(&self, key: &<ContractsAssets as Mappable>::Key) -> io::Result<Option<Cow<'_, Word>>> {
+        self.check_balance(key)?;
+
+        self.local.storage::<ContractsAssets>().get(key)
+    }
+
+    fn contains_key(&self, key: &<ContractsAssets as Mappable>::Key) -> io::Result<bool> {
+        self.check_balance(key)?;
+
+        self.local.storage::<ContractsAssets>().contains_key(key)
+    }
+}
+
+impl<S> StorageMutate<ContractsAssets> for VerifierStorage<S>
+where
+    S: InterpreterStorage,
+{
+    fn insert(&mut self, key: &<ContractsAssets as Mappable>::Key, value: &Word) -> io::Result<Option<Word>> {
+        self.check_balance(key)?;
+
+        StorageMutate::<ContractsAssets>::insert(&mut self.local, key, value)
+    }
+
+    fn remove(&mut self, key: &<ContractsAssets as Mappable>::Key) -> io::Result<Option<Word>> {
+        self.check_balance(key)?;
+
+        StorageMutate::<ContractsAssets>::remove(&mut self.local, key)
+    }
+}
+
+impl<S> MerkleRootStorage<ContractId, ContractsAssets> for VerifierStorage<S>
+where
+    S: InterpreterStorage,
+{
+    fn root(&self, parent: &ContractId) -> io::Result<MerkleRoot> {
+        Err(partial_state_root(parent))
+    }
+}
+
+impl<S> StorageInspect<ContractsState> for VerifierStorage<S>
+where
+    S: InterpreterStorage,
+{
+    type Error = io::Error;
+
+    fn get(&self, key: &<ContractsState as Mappable>::Key) -> io::Result<Option<Cow<'_, Bytes32>>> {
+        self.check_state(key)?;
+
+        self.local.storage::<ContractsState>().get(key)
+    }
+
+    fn contains_key(&self, key: &<ContractsState as Mappable>::Key) -> io::Result<bool> {
+        self.check_state(key)?;
+
+        self.local.storage::<ContractsState>().contains_key(key)
+    }
+}
+
+impl<S> StorageMutate<ContractsState> for VerifierStorage<S>
+where
+    S: InterpreterStorage,
+{
+    fn insert(&mut self, key: &<ContractsState as Mappable>::Key, value: &Bytes32) -> io::Result<Option<Bytes32>> {
+        self.check_state(key)?;
+
+        StorageMutate::<ContractsState>::insert(&mut self.local, key, value)
+    }
+
+    fn remove(&mut self, key: &<ContractsState as Mappable>::Key) -> io::Result<Option<Bytes32>> {
+        self.check_state(key)?;
+
+        StorageMutate::<ContractsState>::remove(&mut self.local, key)
+    }
+}
+
+impl<S> MerkleRootStorage<ContractId, ContractsState> for VerifierStorage<S>
+where
+    S: InterpreterStorage,
+{
+    fn root(&self, parent: &ContractId) -> io::Result<MerkleRoot> {
+        Err(partial_state_root(parent))
+    }
+}
+
+impl<S> InterpreterStorage for VerifierStorage<S>
+where
+    S: InterpreterStorage,
+{
+    type DataError = io::Error;
+
+    fn block_height(&self) -> io::Result<u32> {
+        self.storage.block_height().map_err(Into::into)
+    }
+
+    fn timestamp(&self, height: u32) -> io::Result<Word> {
+        self.storage.timestamp(height).map_err(Into::into)
+    }
+
+    fn block_hash(&self, block_height: u32) -> io::Result<Bytes32> {
+        self.storage.block_hash(block_height).map_err(Into::into)
+    }
+
+    fn coinbase(&self) -> io::Result<Address> {
+        self.storage.coinbase().map_err(Into::into)
+    }
+
+    fn merkle_contract_state_range(
+        &self,
+        id: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> io::Result<Vec<Option<Cow<'_, Bytes32>>>> {
+        self.check_state_range(id, start_key, range)?;
+
+        self.local.merkle_contract_state_range(id, start_key, range)
+    }
+
+    fn merkle_contract_state_insert_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: &[Bytes32],
+    ) -> io::Result<Option<()>> {
+        self.check_state_range(contract, start_key, values.len() as Word)?;
+
+        self.local
+            .merkle_contract_state_insert_range(contract, start_key, values)
+    }
+
+    fn merkle_contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> io::Result<Option<()>> {
+        self.check_state_range(contract, start_key, range)?;
+
+        self.local
+            .merkle_contract_state_remove_range(contract, start_key, range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::WORD_SIZE;
+    use crate::prelude::*;
+    use crate::storage::{ContractsInfo, ContractsRawCode};
+    use crate::util::test_helpers::{call_contract, deploy_contract, transact_receipts};
+
+    use fuel_asm::{op, RegId};
+    use fuel_tx::field::Inputs;
+
+    /// Deploy a contract incrementing the first word of its state slot keyed by its id, and
+    /// returning the new value.
+    fn deploy(storage: &mut MemoryStorage) -> ContractId {
+        deploy_contract(
+            storage,
+            vec![
+                op::srw(0x10, 0x11, RegId::FP),
+                op::addi(0x10, 0x10, 1),
+                op::sww(RegId::FP, 0x11, 0x10),
+                op::ret(0x10),
+            ],
+            &[],
+        )
+    }
+
+    /// Storage with the code of the contract only.
+    fn light(storage: &MemoryStorage, contract: &ContractId) -> MemoryStorage {
+        let mut light = MemoryStorage::default();
+        let code = storage.storage::<ContractsRawCode>().get(contract).unwrap().unwrap();
+        let (salt, root) = *storage.storage::<ContractsInfo>().get(contract).unwrap().unwrap();
+
+        light.storage_contract_insert(contract, &code).unwrap();
+        light.storage_contract_root_insert(contract, &salt, &root).unwrap();
+
+        light
+    }
+
+    #[test]
+    fn proofs_allow_reexecution_from_a_light_storage() {
+        let mut storage = MemoryStorage::default();
+        let contract = deploy(&mut storage);
+
+        let mut value = Bytes32::zeroed();
+        value[..WORD_SIZE].copy_from_slice(&41u64.to_be_bytes());
+        storage
+            .merkle_contract_state_insert(&contract, &Bytes32::new(*contract), &value)
+            .unwrap();
+        // Other slots change the root without being read
+        storage
+            .merkle_contract_state_insert(&contract, &[1; 32].into(), &[1; 32].into())
+            .unwrap();
+
+        let tx = call_contract(&storage, contract, &[]);
+        let light = light(&storage, &contract);
+
+        let prover = storage.clone();
+        let mut proving = ProvingStorage::new(&mut storage, &prover);
+        let expected = transact_receipts(&mut proving, tx.clone()).expect("failed to execute the call");
+        let proofs = proving.proofs();
+
+        assert!(expected
+            .iter()
+            .any(|receipt| matches!(receipt, Receipt::Return { val: 42, .. })));
+        assert_eq!(proofs.state.len(), 1);
+        assert_eq!(proofs.state.values().next().unwrap().value, Some(value));
+        // The call credits the contract with the forwarded amount
+        assert_eq!(proofs.balances.len(), 1);
+        assert_eq!(proofs.balances.values().next().unwrap().value, None);
+
+        let mut verifier = VerifierStorage::new(light, proofs, tx.transaction().inputs()).expect("invalid proofs");
+        assert_eq!(
+            transact_receipts(&mut verifier, tx).expect("failed to execute the call"),
+            expected
+        );
+
+        // The proven slots alone don't determine the roots after the call
+        assert_eq!(
+            verifier.storage::<ContractsState>().root(&contract).unwrap_err().kind(),
+            io::ErrorKind::Unsupported
+        );
+    }
+
+    #[test]
+    fn verifier_rejects_invalid_or_missing_proofs() {
+        let mut storage = MemoryStorage::default();
+        let contract = deploy(&mut storage);
+        storage
+            .merkle_contract_state_insert(&contract, &Bytes32::new(*contract), &[1; 32].into())
+            .unwrap();
+
+        let tx = call_contract(&storage, contract, &[]);
+        let inputs = tx.transaction().inputs();
+
+        let prover = storage.clone();
+        let mut proving = ProvingStorage::new(&mut storage, &prover);
+        transact_receipts(&mut proving, tx.clone()).expect("failed to execute the call");
+        let proofs = proving.proofs();
+
+        let mut tampered = proofs.clone();
+        tampered.state.values_mut().next().unwrap().value = Some([2; 32].into());
+        let err = VerifierStorage::new(MemoryStorage::default(), tampered, inputs).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let err = VerifierStorage::new(MemoryStorage::default(), proofs.clone(), &[]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        // Without the proof of the balance, the call can't credit the contract
+        let mut missing = proofs;
+        missing.balances.clear();
+        let verifier = VerifierStorage::new(light(&prover, &contract), missing, inputs).expect("invalid proofs");
+        assert!(transact_receipts(verifier, tx).is_none());
+    }
+}
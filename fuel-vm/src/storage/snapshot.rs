@@ -0,0 +1,323 @@
+//! Portable dump of a [`MemoryStorage`](super::MemoryStorage), to share fixtures and replay
+//! captured states.
+
+use super::BlockInfo;
+
+use fuel_tx::Contract;
+use fuel_types::{Address, AssetId, Bytes32, ContractId, Salt, Word};
+
+use std::collections::BTreeMap;
+use std::io::{self, Read, Write};
+
+/// Contents of a [`MemoryStorage`](super::MemoryStorage): contract code, info, state and
+/// balances, along with the block history.
+///
+/// The snapshot can be encoded as JSON, with the `snapshot-json` feature, or in a compact binary form.
+/// Both encodings carry [`Self::VERSION`] and decoding fails on any other version.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StorageSnapshot {
+    /// Version of the snapshot format.
+    pub version: u32,
+    /// Current block height.
+    pub block_height: u32,
+    /// Coinbase of the blocks without a recorded [`BlockInfo`].
+    pub coinbase: Address,
+    /// Recorded blocks, by height.
+    pub blocks: BTreeMap<u32, BlockInfo>,
+    /// `ContractsRawCode` entries.
+    pub contracts_raw_code: BTreeMap<ContractId, Contract>,
+    /// `ContractsInfo` entries.
+    pub contracts_info: BTreeMap<ContractId, (Salt, Bytes32)>,
+    /// `ContractsState` entries, by contract and state key.
+    pub contracts_state: BTreeMap<ContractId, BTreeMap<Bytes32, Bytes32>>,
+    /// `ContractsAssets` entries, by contract and asset.
+    pub contracts_assets: BTreeMap<ContractId, BTreeMap<AssetId, Word>>,
+}
+
+impl StorageSnapshot {
+    /// Current version of the snapshot format.
+    pub const VERSION: u32 = 1;
+
+    /// Magic bytes starting the binary encoding.
+    pub const MAGIC: [u8; 4] = *b"FVMS";
+
+    /// Write the snapshot as a JSON document.
+    #[cfg(feature = "snapshot-json")]
+    pub fn write_json<W: Write>(&self, writer: W) -> io::Result<()> {
+        serde_json::to_writer(writer, self).map_err(io::Error::from)
+    }
+
+    /// Read a snapshot from a JSON document.
+    #[cfg(feature = "snapshot-json")]
+    pub fn read_json<R: Read>(mut reader: R) -> io::Result<Self> {
+        // Keys and hashes deserialize from borrowed strings, so the document is buffered first
+        let mut json = Vec::new();
+        reader.read_to_end(&mut json)?;
+
+        let snapshot: Self = serde_json::from_slice(&json).map_err(io::Error::from)?;
+
+        check_version(snapshot.version)?;
+
+        Ok(snapshot)
+    }
+
+    /// Write the snapshot in the binary form.
+    ///
+    /// After the magic bytes and the version, the fields are written in declaration order.
+    /// Numbers are big-endian, collections are prefixed by their length as a word, and maps are
+    /// sorted by key.
+    pub fn write_binary<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let w = &mut writer;
+
+        w.write_all(&Self::MAGIC)?;
+        w.write_all(&self.version.to_be_bytes())?;
+        w.write_all(&self.block_height.to_be_bytes())?;
+        w.write_all(self.coinbase.as_ref())?;
+
+        write_len(w, self.blocks.len())?;
+        for (height, block) in &self.blocks {
+            w.write_all(&height.to_be_bytes())?;
+            write_block(w, block)?;
+        }
+
+        write_len(w, self.contracts_raw_code.len())?;
+        for (contract, code) in &self.contracts_raw_code {
+            w.write_all(contract.as_ref())?;
+            write_code(w, code.as_ref())?;
+        }
+
+        write_len(w, self.contracts_info.len())?;
+        for (contract, (salt, root)) in &self.contracts_info {
+            w.write_all(contract.as_ref())?;
+            w.write_all(salt.as_ref())?;
+            w.write_all(root.as_ref())?;
+        }
+
+        write_len(w, self.contracts_state.len())?;
+        for (contract, state) in &self.contracts_state {
+            w.write_all(contract.as_ref())?;
+            write_len(w, state.len())?;
+            for (key, value) in state {
+                w.write_all(key.as_ref())?;
+                w.write_all(value.as_ref())?;
+            }
+        }
+
+        write_len(w, self.contracts_assets.len())?;
+        for (contract, balances) in &self.contracts_assets {
+            w.write_all(contract.as_ref())?;
+            write_len(w, balances.len())?;
+            for (asset_id, balance) in balances {
+                w.write_all(asset_id.as_ref())?;
+                w.write_all(&balance.to_be_bytes())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read a snapshot written by [`Self::write_binary`].
+    pub fn read_binary<R: Read>(mut reader: R) -> io::Result<Self> {
+        let r = &mut reader;
+
+        if read_array(r)? != Self::MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "not a storage snapshot"));
+        }
+
+        let version = u32::from_be_bytes(read_array(r)?);
+        check_version(version)?;
+
+        let block_height = u32::from_be_bytes(read_array(r)?);
+        let coinbase = read_array(r)?.into();
+
+        let blocks = (0..read_len(r)?)
+            .map(|_| Ok((u32::from_be_bytes(read_array(r)?), read_block(r)?)))
+            .collect::<io::Result<_>>()?;
+
+        let contracts_raw_code = (0..read_len(r)?)
+            .map(|_| Ok((read_array(r)?.into(), read_code(r)?.into())))
+            .collect::<io::Result<_>>()?;
+
+        let contracts_info = (0..read_len(r)?)
+            .map(|_| Ok((read_array(r)?.into(), (read_array(r)?.into(), read_array(r)?.into()))))
+            .collect::<io::Result<_>>()?;
+
+        let contracts_state = (0..read_len(r)?)
+            .map(|_| {
+                let contract = read_array(r)?.into();
+                let state = (0..read_len(r)?)
+                    .map(|_| Ok((read_array(r)?.into(), read_array(r)?.into())))
+                    .collect::<io::Result<_>>()?;
+
+                Ok((contract, state))
+            })
+            .collect::<io::Result<_>>()?;
+
+        let contracts_assets = (0..read_len(r)?)
+            .map(|_| {
+                let contract = read_array(r)?.into();
+                let balances = (0..read_len(r)?)
+                    .map(|_| Ok((read_array(r)?.into(), Word::from_be_bytes(read_array(r)?))))
+                    .collect::<io::Result<_>>()?;
+
+                Ok((contract, balances))
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Self {
+            version,
+            block_height,
+            coinbase,
+            blocks,
+            contracts_raw_code,
+            contracts_info,
+            contracts_state,
+            contracts_assets,
+        })
+    }
+}
+
+fn check_version(version: u32) -> io::Result<()> {
+    if version == StorageSnapshot::VERSION {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unsupported snapshot version {version}"),
+        ))
+    }
+}
+
+pub(super) fn write_len<W: Write>(writer: &mut W, len: usize) -> io::Result<()> {
+    writer.write_all(&(len as Word).to_be_bytes())
+}
+
+pub(super) fn read_len<R: Read>(reader: &mut R) -> io::Result<usize> {
+    usize::try_from(Word::from_be_bytes(read_array(reader)?))
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "snapshot length overflow"))
+}
+
+pub(super) fn read_array<R: Read, const N: usize>(reader: &mut R) -> io::Result<[u8; N]> {
+    let mut array = [0u8; N];
+    reader.read_exact(&mut array)?;
+
+    Ok(array)
+}
+
+pub(super) fn write_block<W: Write>(writer: &mut W, block: &BlockInfo) -> io::Result<()> {
+    writer.write_all(block.hash.as_ref())?;
+    writer.write_all(&block.timestamp.to_be_bytes())?;
+    writer.write_all(block.coinbase.as_ref())
+}
+
+pub(super) fn read_block<R: Read>(reader: &mut R) -> io::Result<BlockInfo> {
+    Ok(BlockInfo {
+        hash: read_array(reader)?.into(),
+        timestamp: Word::from_be_bytes(read_array(reader)?),
+        coinbase: read_array(reader)?.into(),
+    })
+}
+
+/// Write a contract code, prefixed by its length.
+pub(super) fn write_code<W: Write>(writer: &mut W, code: &[u8]) -> io::Result<()> {
+    write_len(writer, code.len())?;
+    writer.write_all(code)
+}
+
+pub(super) fn read_code<R: Read>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let len = read_len(reader)?;
+    let mut code = Vec::new();
+    reader.take(len as u64).read_to_end(&mut code)?;
+
+    if code.len() != len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+
+    Ok(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{ContractsAssets, ContractsState, InterpreterStorage, MemoryStorage};
+
+    use fuel_storage::StorageAsRef;
+
+    fn storage() -> MemoryStorage {
+        let mut storage = MemoryStorage::default();
+        let contract = ContractId::from([1; 32]);
+
+        storage
+            .push_block(BlockInfo {
+                hash: [2; 32].into(),
+                timestamp: 1_000,
+                coinbase: [3; 32].into(),
+            })
+            .unwrap();
+        storage
+            .storage_contract_insert(&contract, &Contract::from(vec![4; 12]))
+            .unwrap();
+        storage
+            .storage_contract_root_insert(&contract, &[5; 32].into(), &[6; 32].into())
+            .unwrap();
+        storage
+            .merkle_contract_state_insert_range(&contract, &[7; 32].into(), &[[8; 32].into(); 3])
+            .unwrap();
+        storage
+            .merkle_contract_asset_id_balance_insert(&contract, &[9; 32].into(), 10)
+            .unwrap();
+
+        storage
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let storage = storage();
+        let snapshot = storage.snapshot();
+
+        let mut buf = Vec::new();
+        snapshot.write_binary(&mut buf).unwrap();
+
+        let restored = MemoryStorage::from_snapshot(StorageSnapshot::read_binary(buf.as_slice()).unwrap());
+        let contract = ContractId::from([1; 32]);
+
+        assert_eq!(restored.snapshot(), snapshot);
+        assert_eq!(
+            restored.storage::<ContractsState>().root(&contract).unwrap(),
+            storage.storage::<ContractsState>().root(&contract).unwrap()
+        );
+        assert_eq!(
+            restored.storage::<ContractsAssets>().root(&contract).unwrap(),
+            storage.storage::<ContractsAssets>().root(&contract).unwrap()
+        );
+        assert_eq!(restored.block_hash(2).unwrap(), Bytes32::from([2; 32]));
+
+        assert!(StorageSnapshot::read_binary(&buf[..buf.len() - 1]).is_err());
+        assert!(StorageSnapshot::read_binary(&buf[1..]).is_err());
+
+        let mut future = buf.clone();
+        future[4..8].copy_from_slice(&(StorageSnapshot::VERSION + 1).to_be_bytes());
+        assert!(StorageSnapshot::read_binary(future.as_slice()).is_err());
+    }
+
+    #[cfg(feature = "snapshot-json")]
+    #[test]
+    fn json_round_trip() {
+        let snapshot = storage().snapshot();
+
+        let mut buf = Vec::new();
+        snapshot.write_json(&mut buf).unwrap();
+
+        assert_eq!(StorageSnapshot::read_json(buf.as_slice()).unwrap(), snapshot);
+
+        let future = StorageSnapshot {
+            version: StorageSnapshot::VERSION + 1,
+            ..snapshot
+        };
+        buf.clear();
+        future.write_json(&mut buf).unwrap();
+
+        assert!(StorageSnapshot::read_json(buf.as_slice()).is_err());
+    }
+}
@@ -0,0 +1,1050 @@
+//! Stateless execution from a self-contained witness of the storage accessed by a transaction.
+
+use super::snapshot::{read_array, read_block, read_code, read_len, write_block, write_code, write_len};
+use super::{
+    BlockInfo, ContractsAssetKey, ContractsAssets, ContractsInfo, ContractsRawCode, ContractsState, ContractsStateKey,
+    InterpreterStorage, MemoryStorage, ProvingStorage, SlotProof, StorageProofs, StorageProver, VerifierStorage,
+};
+
+use fuel_merkle::sparse::Proof;
+use fuel_storage::{Mappable, MerkleRoot, MerkleRootStorage, StorageInspect, StorageMutate, StorageRead, StorageSize};
+use fuel_tx::{Contract, Input};
+use fuel_types::{Address, Bytes32, ContractId, Salt, Word};
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashSet};
+use std::io::{self, Read, Write};
+
+/// Everything a transaction reads from the storage: the block data, the contract code and info,
+/// and the proven state slots and balances.
+///
+/// Contracts found absent during the recording are kept as `None`, so their absence can be
+/// replayed.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WitnessBundle {
+    /// Current block height.
+    pub block_height: u32,
+    /// Coinbase of the current block.
+    pub coinbase: Address,
+    /// Blocks whose timestamp or hash were read, by height. Only the current block keeps its
+    /// coinbase, the others have the default one.
+    pub blocks: BTreeMap<u32, BlockInfo>,
+    /// Code of the contracts, by id.
+    pub contracts_raw_code: BTreeMap<ContractId, Option<Contract>>,
+    /// Salt and code root of the contracts, by id.
+    pub contracts_info: BTreeMap<ContractId, Option<(Salt, Bytes32)>>,
+    /// Proofs of the state slots and balances.
+    pub proofs: StorageProofs,
+}
+
+impl WitnessBundle {
+    /// Current version of the binary encoding.
+    pub const VERSION: u32 = 1;
+
+    /// Magic bytes starting the binary encoding.
+    pub const MAGIC: [u8; 4] = *b"FVMW";
+
+    /// Write the bundle in a binary form, with the same conventions as
+    /// [`StorageSnapshot::write_binary`](super::StorageSnapshot::write_binary).
+    ///
+    /// Absent values are written as a `0` byte, and present ones as a `1` byte followed by the
+    /// value.
+    pub fn write_binary<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let w = &mut writer;
+
+        w.write_all(&Self::MAGIC)?;
+        w.write_all(&Self::VERSION.to_be_bytes())?;
+        w.write_all(&self.block_height.to_be_bytes())?;
+        w.write_all(self.coinbase.as_ref())?;
+
+        write_len(w, self.blocks.len())?;
+        for (height, block) in &self.blocks {
+            w.write_all(&height.to_be_bytes())?;
+            write_block(w, block)?;
+        }
+
+        write_len(w, self.contracts_raw_code.len())?;
+        for (contract, code) in &self.contracts_raw_code {
+            w.write_all(contract.as_ref())?;
+            write_option(w, code.as_ref(), |w, code| write_code(w, code.as_ref()))?;
+        }
+
+        write_len(w, self.contracts_info.len())?;
+        for (contract, info) in &self.contracts_info {
+            w.write_all(contract.as_ref())?;
+            write_option(w, info.as_ref(), |w, (salt, root)| {
+                w.write_all(salt.as_ref())?;
+                w.write_all(root.as_ref())
+            })?;
+        }
+
+        write_len(w, self.proofs.state.len())?;
+        for (key, proof) in &self.proofs.state {
+            w.write_all(key.as_ref())?;
+            write_option(w, proof.value.as_ref(), |w, value| w.write_all(value.as_ref()))?;
+            write_proof(w, &proof.proof)?;
+        }
+
+        write_len(w, self.proofs.balances.len())?;
+        for (key, proof) in &self.proofs.balances {
+            w.write_all(key.as_ref())?;
+            write_option(w, proof.value.as_ref(), |w, value| w.write_all(&value.to_be_bytes()))?;
+            write_proof(w, &proof.proof)?;
+        }
+
+        Ok(())
+    }
+
+    /// Read a bundle written by [`Self::write_binary`].
+    pub fn read_binary<R: Read>(mut reader: R) -> io::Result<Self> {
+        let r = &mut reader;
+
+        if read_array(r)? != Self::MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "not a witness bundle"));
+        }
+
+        let version = u32::from_be_bytes(read_array(r)?);
+        if version != Self::VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported witness bundle version {version}"),
+            ));
+        }
+
+        let block_height = u32::from_be_bytes(read_array(r)?);
+        let coinbase = read_array(r)?.into();
+
+        let blocks = (0..read_len(r)?)
+            .map(|_| Ok((u32::from_be_bytes(read_array(r)?), read_block(r)?)))
+            .collect::<io::Result<_>>()?;
+
+        let contracts_raw_code = (0..read_len(r)?)
+            .map(|_| {
+                let contract = read_array(r)?.into();
+                let code = read_option(r, |r| read_code(r).map(Contract::from))?;
+
+                Ok((contract, code))
+            })
+            .collect::<io::Result<_>>()?;
+
+        let contracts_info = (0..read_len(r)?)
+            .map(|_| {
+                let contract = read_array(r)?.into();
+                let info = read_option(r, |r| Ok((read_array(r)?.into(), read_array(r)?.into())))?;
+
+                Ok((contract, info))
+            })
+            .collect::<io::Result<_>>()?;
+
+        let state = (0..read_len(r)?)
+            .map(|_| {
+                let key = ContractsStateKey::from_array(read_array(r)?);
+                let value = read_option(r, |r| Ok(read_array(r)?.into()))?;
+                let proof = read_proof(r)?;
+
+                Ok((key, SlotProof { value, proof }))
+            })
+            .collect::<io::Result<_>>()?;
+
+        let balances = (0..read_len(r)?)
+            .map(|_| {
+                let key = ContractsAssetKey::from_array(read_array(r)?);
+                let value = read_option(r, |r| Ok(Word::from_be_bytes(read_array(r)?)))?;
+                let proof = read_proof(r)?;
+
+                Ok((key, SlotProof { value, proof }))
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Self {
+            block_height,
+            coinbase,
+            blocks,
+            contracts_raw_code,
+            contracts_info,
+            proofs: StorageProofs { state, balances },
+        })
+    }
+}
+
+fn write_option<W, T, F>(writer: &mut W, value: Option<T>, write: F) -> io::Result<()>
+where
+    W: Write,
+    F: FnOnce(&mut W, T) -> io::Result<()>,
+{
+    match value {
+        Some(value) => {
+            writer.write_all(&[1])?;
+            write(writer, value)
+        }
+        None => writer.write_all(&[0]),
+    }
+}
+
+fn read_option<R, T, F>(reader: &mut R, read: F) -> io::Result<Option<T>>
+where
+    R: Read,
+    F: FnOnce(&mut R) -> io::Result<T>,
+{
+    match read_array(reader)? {
+        [0] => Ok(None),
+        [1] => read(reader).map(Some),
+        _ => Err(io::Error::new(io::ErrorKind::InvalidData, "invalid option tag")),
+    }
+}
+
+fn write_proof<W: Write>(writer: &mut W, proof: &Proof) -> io::Result<()> {
+    write_option(writer, proof.leaf.as_ref(), |w, (key, data)| {
+        w.write_all(key)?;
+        w.write_all(data)
+    })?;
+
+    write_len(writer, proof.side_nodes.len())?;
+    proof.side_nodes.iter().try_for_each(|node| writer.write_all(node))
+}
+
+fn read_proof<R: Read>(reader: &mut R) -> io::Result<Proof> {
+    let leaf = read_option(reader, |r| Ok((read_array(r)?, read_array(r)?)))?;
+    let side_nodes = (0..read_len(reader)?)
+        .map(|_| read_array(reader))
+        .collect::<io::Result<_>>()?;
+
+    Ok(Proof { leaf, side_nodes })
+}
+
+/// Check the current block and the blocks of the bundle against the trusted ones, the last trusted
+/// block being the current one.
+fn check_blocks(trusted: &BTreeMap<u32, BlockInfo>, bundle: &WitnessBundle) -> io::Result<()> {
+    match trusted.last_key_value() {
+        Some((height, block)) if *height == bundle.block_height && block.coinbase == bundle.coinbase => (),
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the current block of the bundle doesn't match the trusted one",
+            ))
+        }
+    }
+
+    bundle.blocks.iter().try_for_each(|(height, block)| {
+        let coinbase = (*height == bundle.block_height).then_some(block.coinbase);
+
+        match trusted.get(height) {
+            Some(trusted)
+                if trusted.hash == block.hash
+                    && trusted.timestamp == block.timestamp
+                    && coinbase.map_or(true, |coinbase| coinbase == trusted.coinbase) =>
+            {
+                Ok(())
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("block {height} of the bundle doesn't match the trusted one"),
+            )),
+        }
+    })
+}
+
+/// Check the code root of a contract of the bundle, `None` if the contract is absent, against the
+/// trusted one.
+fn check_code_root(
+    code_roots: &BTreeMap<ContractId, Bytes32>,
+    contract: &ContractId,
+    root: Option<Bytes32>,
+) -> io::Result<()> {
+    if code_roots.get(contract) == root.as_ref() {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("the code of contract {contract} doesn't match its trusted code root"),
+        ))
+    }
+}
+
+fn not_in_bundle(what: String) -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, format!("{what} is not in the witness bundle"))
+}
+
+/// Storage recording a [`WitnessBundle`] of the accesses to the wrapped storage.
+///
+/// The proofs are built as with [`ProvingStorage`], so `prover` must hold the state from before
+/// the execution.
+#[derive(Debug)]
+pub struct WitnessRecorder<S, P> {
+    storage: ProvingStorage<S, P>,
+    contracts_raw_code: RefCell<BTreeMap<ContractId, Option<Contract>>>,
+    contracts_info: RefCell<BTreeMap<ContractId, Option<(Salt, Bytes32)>>>,
+    blocks: RefCell<BTreeMap<u32, BlockInfo>>,
+}
+
+impl<S, P> WitnessRecorder<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    /// Record the accesses to `storage`, with the proofs built by `prover`.
+    pub fn new(storage: S, prover: P) -> Self {
+        Self {
+            storage: ProvingStorage::new(storage, prover),
+            contracts_raw_code: Default::default(),
+            contracts_info: Default::default(),
+            blocks: Default::default(),
+        }
+    }
+
+    /// Bundle of the accesses recorded so far.
+    pub fn bundle(&self) -> io::Result<WitnessBundle> {
+        Ok(WitnessBundle {
+            block_height: self.storage.block_height()?,
+            coinbase: self.storage.coinbase()?,
+            blocks: self.blocks.borrow().clone(),
+            contracts_raw_code: self.contracts_raw_code.borrow().clone(),
+            contracts_info: self.contracts_info.borrow().clone(),
+            proofs: self.storage.proofs(),
+        })
+    }
+
+    /// Underlying storage and recorded bundle.
+    pub fn into_inner(self) -> io::Result<(S, WitnessBundle)> {
+        let bundle = self.bundle()?;
+        let (storage, _) = self.storage.into_inner();
+
+        Ok((storage, bundle))
+    }
+
+    fn record_code(&self, contract: &ContractId) -> io::Result<()> {
+        if !self.contracts_raw_code.borrow().contains_key(contract) {
+            let code = StorageInspect::<ContractsRawCode>::get(&self.storage, contract)?.map(Cow::into_owned);
+            self.contracts_raw_code.borrow_mut().insert(*contract, code);
+        }
+
+        Ok(())
+    }
+
+    fn record_info(&self, contract: &ContractId) -> io::Result<()> {
+        if !self.contracts_info.borrow().contains_key(contract) {
+            let info = StorageInspect::<ContractsInfo>::get(&self.storage, contract)?.map(Cow::into_owned);
+            self.contracts_info.borrow_mut().insert(*contract, info);
+        }
+
+        Ok(())
+    }
+
+    fn record_block(&self, height: u32) -> io::Result<()> {
+        if !self.blocks.borrow().contains_key(&height) {
+            let coinbase = if height == self.storage.block_height()? {
+                self.storage.coinbase()?
+            } else {
+                Address::default()
+            };
+            let block = BlockInfo {
+                hash: self.storage.block_hash(height)?,
+                timestamp: self.storage.timestamp(height)?,
+                coinbase,
+            };
+            self.blocks.borrow_mut().insert(height, block);
+        }
+
+        Ok(())
+    }
+}
+
+impl<S, P> StorageInspect<ContractsRawCode> for WitnessRecorder<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    type Error = io::Error;
+
+    fn get(&self, key: &ContractId) -> io::Result<Option<Cow<'_, Contract>>> {
+        self.record_code(key)?;
+
+        StorageInspect::<ContractsRawCode>::get(&self.storage, key)
+    }
+
+    fn contains_key(&self, key: &ContractId) -> io::Result<bool> {
+        self.record_code(key)?;
+
+        StorageInspect::<ContractsRawCode>::contains_key(&self.storage, key)
+    }
+}
+
+impl<S, P> StorageMutate<ContractsRawCode> for WitnessRecorder<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    fn insert(&mut self, key: &ContractId, value: &[u8]) -> io::Result<Option<Contract>> {
+        self.record_code(key)?;
+
+        StorageMutate::<ContractsRawCode>::insert(&mut self.storage, key, value)
+    }
+
+    fn remove(&mut self, key: &ContractId) -> io::Result<Option<Contract>> {
+        self.record_code(key)?;
+
+        StorageMutate::<ContractsRawCode>::remove(&mut self.storage, key)
+    }
+}
+
+impl<S, P> StorageSize<ContractsRawCode> for WitnessRecorder<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    fn size_of_value(&self, key: &ContractId) -> io::Result<Option<usize>> {
+        self.record_code(key)?;
+
+        StorageSize::<ContractsRawCode>::size_of_value(&self.storage, key)
+    }
+}
+
+impl<S, P> StorageRead<ContractsRawCode> for WitnessRecorder<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    fn read(&self, key: &ContractId, buf: &mut [u8]) -> io::Result<Option<usize>> {
+        self.record_code(key)?;
+
+        StorageRead::<ContractsRawCode>::read(&self.storage, key, buf)
+    }
+
+    fn read_alloc(&self, key: &ContractId) -> io::Result<Option<Vec<u8>>> {
+        self.record_code(key)?;
+
+        StorageRead::<ContractsRawCode>::read_alloc(&self.storage, key)
+    }
+}
+
+impl<S, P> StorageInspect<ContractsInfo> for WitnessRecorder<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    type Error = io::Error;
+
+    fn get(&self, key: &ContractId) -> io::Result<Option<Cow<'_, (Salt, Bytes32)>>> {
+        self.record_info(key)?;
+
+        StorageInspect::<ContractsInfo>::get(&self.storage, key)
+    }
+
+    fn contains_key(&self, key: &ContractId) -> io::Result<bool> {
+        self.record_info(key)?;
+
+        StorageInspect::<ContractsInfo>::contains_key(&self.storage, key)
+    }
+}
+
+impl<S, P> StorageMutate<ContractsInfo> for WitnessRecorder<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    fn insert(&mut self, key: &ContractId, value: &(Salt, Bytes32)) -> io::Result<Option<(Salt, Bytes32)>> {
+        self.record_info(key)?;
+
+        StorageMutate::<ContractsInfo>::insert(&mut self.storage, key, value)
+    }
+
+    fn remove(&mut self, key: &ContractId) -> io::Result<Option<(Salt, Bytes32)>> {
+        self.record_info(key)?;
+
+        StorageMutate::<ContractsInfo>::remove(&mut self.storage, key)
+    }
+}
+
+impl<S, P> StorageInspect<ContractsAssets> for WitnessRecorder<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    type Error = io::Error;
+
+    fn get(&self, key: &<ContractsAssets as Mappable>::Key) -> io::Result<Option<Cow<'_, Word>>> {
+        StorageInspect::<ContractsAssets>::get(&self.storage, key)
+    }
+
+    fn contains_key(&self, key: &<ContractsAssets as Mappable>::Key) -> io::Result<bool> {
+        StorageInspect::<ContractsAssets>::contains_key(&self.storage, key)
+    }
+}
+
+impl<S, P> StorageMutate<ContractsAssets> for WitnessRecorder<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    fn insert(&mut self, key: &<ContractsAssets as Mappable>::Key, value: &Word) -> io::Result<Option<Word>> {
+        StorageMutate::<ContractsAssets>::insert(&mut self.storage, key, value)
+    }
+
+    fn remove(&mut self, key: &<ContractsAssets as Mappable>::Key) -> io::Result<Option<Word>> {
+        StorageMutate::<ContractsAssets>::remove(&mut self.storage, key)
+    }
+}
+
+impl<S, P> MerkleRootStorage<ContractId, ContractsAssets> for WitnessRecorder<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    fn root(&self, parent: &ContractId) -> io::Result<MerkleRoot> {
+        MerkleRootStorage::<ContractId, ContractsAssets>::root(&self.storage, parent)
+    }
+}
+
+impl<S, P> StorageInspect<ContractsState> for WitnessRecorder<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    type Error = io::Error;
+
+    fn get(&self, key: &<ContractsState as Mappable>::Key) -> io::Result<Option<Cow<'_, Bytes32>>> {
+        StorageInspect::<ContractsState>::get(&self.storage, key)
+    }
+
+    fn contains_key(&self, key: &<ContractsState as Mappable>::Key) -> io::Result<bool> {
+        StorageInspect::<ContractsState>::contains_key(&self.storage, key)
+    }
+}
+
+impl<S, P> StorageMutate<ContractsState> for WitnessRecorder<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    fn insert(&mut self, key: &<ContractsState as Mappable>::Key, value: &Bytes32) -> io::Result<Option<Bytes32>> {
+        StorageMutate::<ContractsState>::insert(&mut self.storage, key, value)
+    }
+
+    fn remove(&mut self, key: &<ContractsState as Mappable>::Key) -> io::Result<Option<Bytes32>> {
+        StorageMutate::<ContractsState>::remove(&mut self.storage, key)
+    }
+}
+
+impl<S, P> MerkleRootStorage<ContractId, ContractsState> for WitnessRecorder<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    fn root(&self, parent: &ContractId) -> io::Result<MerkleRoot> {
+        MerkleRootStorage::<ContractId, ContractsState>::root(&self.storage, parent)
+    }
+}
+
+impl<S, P> InterpreterStorage for WitnessRecorder<S, P>
+where
+    S: InterpreterStorage,
+    P: StorageProver,
+{
+    type DataError = io::Error;
+
+    fn block_height(&self) -> io::Result<u32> {
+        self.storage.block_height()
+    }
+
+    fn timestamp(&self, height: u32) -> io::Result<Word> {
+        self.record_block(height)?;
+
+        self.storage.timestamp(height)
+    }
+
+    fn block_hash(&self, block_height: u32) -> io::Result<Bytes32> {
+        self.record_block(block_height)?;
+
+        self.storage.block_hash(block_height)
+    }
+
+    fn coinbase(&self) -> io::Result<Address> {
+        self.storage.coinbase()
+    }
+
+    fn merkle_contract_state_range(
+        &self,
+        id: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> io::Result<Vec<Option<Cow<'_, Bytes32>>>> {
+        self.storage.merkle_contract_state_range(id, start_key, range)
+    }
+
+    fn merkle_contract_state_insert_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: &[Bytes32],
+    ) -> io::Result<Option<()>> {
+        self.storage
+            .merkle_contract_state_insert_range(contract, start_key, values)
+    }
+
+    fn merkle_contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> io::Result<Option<()>> {
+        self.storage
+            .merkle_contract_state_remove_range(contract, start_key, range)
+    }
+}
+
+/// Storage executing a transaction from a [`WitnessBundle`] alone.
+///
+/// The proofs of the bundle are checked against the roots of the contract inputs, the code and
+/// info of each contract against its trusted code root, and the blocks against the trusted blocks.
+/// Any access outside the bundle fails with [`io::ErrorKind::NotFound`].
+#[derive(Debug)]
+pub struct WitnessStorage {
+    storage: VerifierStorage<MemoryStorage>,
+    contracts_raw_code: HashSet<ContractId>,
+    contracts_info: HashSet<ContractId>,
+    blocks: HashSet<u32>,
+}
+
+impl WitnessStorage {
+    /// Serve the accesses recorded by `bundle` for the transaction with the provided inputs.
+    ///
+    /// `code_roots` are the trusted code roots of the contracts, e.g. from the chain state, since
+    /// the bundle can't tie a code to its contract id on its own. A contract of the bundle without
+    /// a trusted code root must be absent from the bundle as well.
+    ///
+    /// `blocks` are the trusted blocks of the chain, by height, the last one being the current
+    /// block. They must include every block of the bundle, which only serves to limit the blocks
+    /// the transaction can read.
+    pub fn new(
+        bundle: WitnessBundle,
+        inputs: &[Input],
+        code_roots: &BTreeMap<ContractId, Bytes32>,
+        blocks: &BTreeMap<u32, BlockInfo>,
+    ) -> io::Result<Self> {
+        check_blocks(blocks, &bundle)?;
+
+        let mut storage = MemoryStorage::new(bundle.block_height, bundle.coinbase);
+
+        for height in bundle.blocks.keys().chain([&bundle.block_height]) {
+            storage.set_block(*height, blocks[height]);
+        }
+
+        for (contract, code) in &bundle.contracts_raw_code {
+            check_code_root(code_roots, contract, code.as_ref().map(Contract::root))?;
+
+            if let Some(code) = code {
+                StorageMutate::<ContractsRawCode>::insert(&mut storage, contract, code.as_ref())?;
+            }
+        }
+
+        for (contract, info) in &bundle.contracts_info {
+            check_code_root(code_roots, contract, info.map(|(_, root)| root))?;
+
+            if let Some(info) = info {
+                StorageMutate::<ContractsInfo>::insert(&mut storage, contract, info)?;
+            }
+        }
+
+        Ok(Self {
+            storage: VerifierStorage::new(storage, bundle.proofs, inputs)?,
+            contracts_raw_code: bundle.contracts_raw_code.into_keys().collect(),
+            contracts_info: bundle.contracts_info.into_keys().collect(),
+            blocks: bundle.blocks.into_keys().collect(),
+        })
+    }
+
+    fn check_code(&self, contract: &ContractId) -> io::Result<()> {
+        if self.contracts_raw_code.contains(contract) {
+            Ok(())
+        } else {
+            Err(not_in_bundle(format!("code of contract {contract}")))
+        }
+    }
+
+    fn check_info(&self, contract: &ContractId) -> io::Result<()> {
+        if self.contracts_info.contains(contract) {
+            Ok(())
+        } else {
+            Err(not_in_bundle(format!("info of contract {contract}")))
+        }
+    }
+
+    fn check_block(&self, height: u32) -> io::Result<()> {
+        if self.blocks.contains(&height) {
+            Ok(())
+        } else {
+            Err(not_in_bundle(format!("block {height}")))
+        }
+    }
+}
+
+impl StorageInspect<ContractsRawCode> for WitnessStorage {
+    type Error = io::Error;
+
+    fn get(&self, key: &ContractId) -> io::Result<Option<Cow<'_, Contract>>> {
+        self.check_code(key)?;
+
+        StorageInspect::<ContractsRawCode>::get(&self.storage, key)
+    }
+
+    fn contains_key(&self, key: &ContractId) -> io::Result<bool> {
+        self.check_code(key)?;
+
+        StorageInspect::<ContractsRawCode>::contains_key(&self.storage, key)
+    }
+}
+
+impl StorageMutate<ContractsRawCode> for WitnessStorage {
+    fn insert(&mut self, key: &ContractId, value: &[u8]) -> io::Result<Option<Contract>> {
+        self.check_code(key)?;
+
+        StorageMutate::<ContractsRawCode>::insert(&mut self.storage, key, value)
+    }
+
+    fn remove(&mut self, key: &ContractId) -> io::Result<Option<Contract>> {
+        self.check_code(key)?;
+
+        StorageMutate::<ContractsRawCode>::remove(&mut self.storage, key)
+    }
+}
+
+impl StorageSize<ContractsRawCode> for WitnessStorage {
+    fn size_of_value(&self, key: &ContractId) -> io::Result<Option<usize>> {
+        self.check_code(key)?;
+
+        StorageSize::<ContractsRawCode>::size_of_value(&self.storage, key)
+    }
+}
+
+impl StorageRead<ContractsRawCode> for WitnessStorage {
+    fn read(&self, key: &ContractId, buf: &mut [u8]) -> io::Result<Option<usize>> {
+        self.check_code(key)?;
+
+        StorageRead::<ContractsRawCode>::read(&self.storage, key, buf)
+    }
+
+    fn read_alloc(&self, key: &ContractId) -> io::Result<Option<Vec<u8>>> {
+        self.check_code(key)?;
+
+        StorageRead::<ContractsRawCode>::read_alloc(&self.storage, key)
+    }
+}
+
+impl StorageInspect<ContractsInfo> for WitnessStorage {
+    type Error = io::Error;
+
+    fn get(&self, key: &ContractId) -> io::Result<Option<Cow<'_, (Salt, Bytes32)>>> {
+        self.check_info(key)?;
+
+        StorageInspect::<ContractsInfo>::get(&self.storage, key)
+    }
+
+    fn contains_key(&self, key: &ContractId) -> io::Result<bool> {
+        self.check_info(key)?;
+
+        StorageInspect::<ContractsInfo>::contains_key(&self.storage, key)
+    }
+}
+
+impl StorageMutate<ContractsInfo> for WitnessStorage {
+    fn insert(&mut self, key: &ContractId, value: &(Salt, Bytes32)) -> io::Result<Option<(Salt, Bytes32)>> {
+        self.check_info(key)?;
+
+        StorageMutate::<ContractsInfo>::insert(&mut self.storage, key, value)
+    }
+
+    fn remove(&mut self, key: &ContractId) -> io::Result<Option<(Salt, Bytes32)>> {
+        self.check_info(key)?;
+
+        StorageMutate::<ContractsInfo>::remove(&mut self.storage, key)
+    }
+}
+
+impl StorageInspect<ContractsAssets> for WitnessStorage {
+    type Error = io::Error;
+
+    fn get(&self, key: &<ContractsAssets as Mappable>::Key) -> io::Result<Option<Cow<'_, Word>>> {
+        StorageInspect::<ContractsAssets>::get(&self.storage, key)
+    }
+
+    fn contains_key(&self, key: &<ContractsAssets as Mappable>::Key) -> io::Result<bool> {
+        StorageInspect::<ContractsAssets>::contains_key(&self.storage, key)
+    }
+}
+
+impl StorageMutate<ContractsAssets> for WitnessStorage {
+    fn insert(&mut self, key: &<ContractsAssets as Mappable>::Key, value: &Word) -> io::Result<Option<Word>> {
+        StorageMutate::<ContractsAssets>::insert(&mut self.storage, key, value)
+    }
+
+    fn remove(&mut self, key: &<ContractsAssets as Mappable>::Key) -> io::Result<Option<Word>> {
+        StorageMutate::<ContractsAssets>::remove(&mut self.storage, key)
+    }
+}
+
+impl MerkleRootStorage<ContractId, ContractsAssets> for WitnessStorage {
+    fn root(&self, parent: &ContractId) -> io::Result<MerkleRoot> {
+        MerkleRootStorage::<ContractId, ContractsAssets>::root(&self.storage, parent)
+    }
+}
+
+impl StorageInspect<ContractsState> for WitnessStorage {
+    type Error = io::Error;
+
+    fn get(&self, key: &<ContractsState as Mappable>::Key) -> io::Result<Option<Cow<'_, Bytes32>>> {
+        StorageInspect::<ContractsState>::get(&self.storage, key)
+    }
+
+    fn contains_key(&self, key: &<ContractsState as Mappable>::Key) -> io::Result<bool> {
+        StorageInspect::<ContractsState>::contains_key(&self.storage, key)
+    }
+}
+
+impl StorageMutate<ContractsState> for WitnessStorage {
+    fn insert(&mut self, key: &<ContractsState as Mappable>::Key, value: &Bytes32) -> io::Result<Option<Bytes32>> {
+        StorageMutate::<ContractsState>::insert(&mut self.storage, key, value)
+    }
+
+    fn remove(&mut self, key: &<ContractsState as Mappable>::Key) -> io::Result<Option<Bytes32>> {
+        StorageMutate::<ContractsState>::remove(&mut self.storage, key)
+    }
+}
+
+impl MerkleRootStorage<ContractId, ContractsState> for WitnessStorage {
+    fn root(&self, parent: &ContractId) -> io::Result<MerkleRoot> {
+        MerkleRootStorage::<ContractId, ContractsState>::root(&self.storage, parent)
+    }
+}
+
+impl InterpreterStorage for WitnessStorage {
+    type DataError = io::Error;
+
+    fn block_height(&self) -> io::Result<u32> {
+        self.storage.block_height()
+    }
+
+    fn timestamp(&self, height: u32) -> io::Result<Word> {
+        self.check_block(height)?;
+
+        self.storage.timestamp(height)
+    }
+
+    fn block_hash(&self, block_height: u32) -> io::Result<Bytes32> {
+        self.check_block(block_height)?;
+
+        self.storage.block_hash(block_height)
+    }
+
+    fn coinbase(&self) -> io::Result<Address> {
+        self.storage.coinbase()
+    }
+
+    fn merkle_contract_state_range(
+        &self,
+        id: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> io::Result<Vec<Option<Cow<'_, Bytes32>>>> {
+        self.storage.merkle_contract_state_range(id, start_key, range)
+    }
+
+    fn merkle_contract_state_insert_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: &[Bytes32],
+    ) -> io::Result<Option<()>> {
+        self.storage
+            .merkle_contract_state_insert_range(contract, start_key, values)
+    }
+
+    fn merkle_contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> io::Result<Option<()>> {
+        self.storage
+            .merkle_contract_state_remove_range(contract, start_key, range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checked_transaction::Checked;
+    use crate::prelude::*;
+    use crate::util::test_helpers::{call_contract, deploy_contract, transact_receipts};
+
+    use fuel_asm::{op, RegId};
+    use fuel_tx::field::Inputs;
+
+    /// Deploy a contract incrementing the first word of its state slot keyed by its id, and
+    /// returning the new value.
+    fn deploy(storage: &mut MemoryStorage) -> ContractId {
+        deploy_contract(
+            storage,
+            vec![
+                op::srw(0x10, 0x11, RegId::FP),
+                op::addi(0x10, 0x10, 1),
+                op::sww(RegId::FP, 0x11, 0x10),
+                op::ret(0x10),
+            ],
+            &[],
+        )
+    }
+
+    /// Trusted code root of the contract.
+    fn code_roots(storage: &MemoryStorage, contract: ContractId) -> BTreeMap<ContractId, Bytes32> {
+        let (_, root) = *storage.storage::<ContractsInfo>().get(&contract).unwrap().unwrap();
+
+        BTreeMap::from([(contract, root)])
+    }
+
+    /// Trusted blocks of the chain.
+    fn blocks(storage: &MemoryStorage) -> BTreeMap<u32, BlockInfo> {
+        (1..=storage.block_height().unwrap())
+            .filter_map(|height| Some((height, *storage.block(height)?)))
+            .collect()
+    }
+
+    /// Trusted code roots and blocks.
+    type Trusted = (BTreeMap<ContractId, Bytes32>, BTreeMap<u32, BlockInfo>);
+
+    fn record() -> (ContractId, Checked<Script>, Vec<Receipt>, WitnessBundle, Trusted) {
+        let mut storage = MemoryStorage::default();
+        let contract = deploy(&mut storage);
+        storage
+            .push_block(BlockInfo {
+                hash: [1; 32].into(),
+                timestamp: 1_000,
+                coinbase: [2; 32].into(),
+            })
+            .unwrap();
+        storage
+            .merkle_contract_state_insert(&contract, &[3; 32].into(), &[3; 32].into())
+            .unwrap();
+
+        // Log the timestamp of the current block before calling the contract
+        let tx = call_contract(
+            &storage,
+            contract,
+            &[
+                op::bhei(0x12),
+                op::time(0x13, 0x12),
+                op::log(0x13, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+            ],
+        );
+        let prover = storage.clone();
+        let mut recorder = WitnessRecorder::new(&mut storage, &prover);
+        let receipts = transact_receipts(&mut recorder, tx.clone()).expect("failed to execute the call");
+        let bundle = recorder.bundle().unwrap();
+
+        let trusted = (code_roots(&storage, contract), blocks(&storage));
+
+        (contract, tx, receipts, bundle, trusted)
+    }
+
+    #[test]
+    fn bundle_replays_the_execution() {
+        let (contract, tx, expected, bundle, (code_roots, blocks)) = record();
+
+        assert!(expected
+            .iter()
+            .any(|receipt| matches!(receipt, Receipt::Log { ra: 1_000, .. })));
+        assert!(expected
+            .iter()
+            .any(|receipt| matches!(receipt, Receipt::Return { val: 1, .. })));
+        assert_eq!(bundle.block_height, 2);
+        assert_eq!(bundle.blocks.keys().copied().collect::<Vec<_>>(), vec![2]);
+        assert_eq!(bundle.blocks[&2].coinbase, bundle.coinbase);
+        assert!(matches!(bundle.contracts_raw_code.get(&contract), Some(Some(_))));
+        assert_eq!(bundle.proofs.state.len(), 1);
+
+        let mut buf = Vec::new();
+        bundle.write_binary(&mut buf).unwrap();
+        let decoded = WitnessBundle::read_binary(buf.as_slice()).unwrap();
+        assert_eq!(decoded, bundle);
+        assert!(WitnessBundle::read_binary(&buf[..buf.len() - 1]).is_err());
+
+        let storage =
+            WitnessStorage::new(decoded, tx.transaction().inputs(), &code_roots, &blocks).expect("invalid bundle");
+        assert_eq!(transact_receipts(storage, tx), Some(expected));
+    }
+
+    #[test]
+    fn accesses_outside_the_bundle_are_rejected() {
+        let (contract, tx, _, bundle, (code_roots, blocks)) = record();
+        let inputs = tx.transaction().inputs();
+
+        let storage = WitnessStorage::new(bundle.clone(), inputs, &code_roots, &blocks).expect("invalid bundle");
+        let other = ContractId::from([4; 32]);
+        let err = storage.storage::<ContractsRawCode>().get(&other).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert_eq!(
+            err.to_string(),
+            format!("code of contract {other} is not in the witness bundle")
+        );
+        assert_eq!(storage.timestamp(1).unwrap_err().kind(), io::ErrorKind::NotFound);
+        assert_eq!(
+            storage
+                .merkle_contract_state(&contract, &[3; 32].into())
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::NotFound
+        );
+
+        let mut missing = bundle.clone();
+        missing.blocks.clear();
+        let storage = WitnessStorage::new(missing, inputs, &code_roots, &blocks).expect("invalid bundle");
+        assert_eq!(transact_receipts(storage, tx.clone()), None);
+    }
+
+    #[test]
+    fn code_must_match_the_trusted_code_roots() {
+        let (contract, tx, _, bundle, (code_roots, blocks)) = record();
+        let inputs = tx.transaction().inputs();
+
+        // A code consistent with a forged info still doesn't match the trusted root
+        let forged: Contract = vec![op::ret(RegId::ONE)].into_iter().collect::<Vec<u8>>().into();
+        let mut tampered = bundle.clone();
+        tampered.contracts_raw_code.insert(contract, Some(forged.clone()));
+        tampered
+            .contracts_info
+            .insert(contract, Some((Salt::zeroed(), forged.root())));
+        let err = WitnessStorage::new(tampered, inputs, &code_roots, &blocks).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let mut absent = bundle.clone();
+        absent.contracts_raw_code.insert(contract, None);
+        let err = WitnessStorage::new(absent, inputs, &code_roots, &blocks).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let err = WitnessStorage::new(bundle, inputs, &BTreeMap::new(), &blocks).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn blocks_must_match_the_trusted_blocks() {
+        let (_, tx, _, bundle, (code_roots, blocks)) = record();
+        let inputs = tx.transaction().inputs();
+
+        let mut tampered = bundle.clone();
+        tampered.blocks.get_mut(&2).unwrap().timestamp = 2_000;
+        let err = WitnessStorage::new(tampered, inputs, &code_roots, &blocks).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "block 2 of the bundle doesn't match the trusted one");
+
+        let mut tampered = bundle.clone();
+        tampered.coinbase = [5; 32].into();
+        let err = WitnessStorage::new(tampered, inputs, &code_roots, &blocks).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        // The current block of the bundle must be the last trusted one
+        let mut later = blocks.clone();
+        later.insert(3, BlockInfo::default());
+        let err = WitnessStorage::new(bundle, inputs, &code_roots, &later).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,260 @@
+//! Per-instruction execution trace, for auditing and differential testing against other VM
+//! implementations.
+//!
+//! A [`Tracer`] set with [`crate::interpreter::Interpreter::with_tracer`] receives a
+//! [`TraceStep`] after every executed instruction. [`JsonLinesTracer`] streams the steps as
+//! [JSON Lines](https://jsonlines.org).
+
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use dyn_clone::DynClone;
+
+use fuel_asm::{Instruction, PanicReason};
+use fuel_types::{AssetId, Bytes32, ContractId, RegisterId, Word};
+
+use crate::consts::VM_REGISTER_COUNT;
+
+/// Receives the steps of an execution.
+pub trait Tracer: DynClone {
+    /// Called after an instruction is executed, including instructions that panicked.
+    fn on_step(&mut self, step: &TraceStep);
+}
+
+dyn_clone::clone_trait_object!(Tracer);
+
+/// Execution of a single instruction.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TraceStep {
+    /// `$pc` of the instruction.
+    pub pc: Word,
+    /// Number of call frames when the instruction was executed, `0` for the script.
+    pub depth: usize,
+    /// The executed instruction.
+    pub instruction: Instruction,
+    /// Registers whose value changed, with their new value.
+    pub registers: Vec<RegisterChange>,
+    /// Memory written, with the contents after the instruction.
+    pub memory: Vec<MemoryWrite>,
+    /// Contract state and balance accesses, in execution order.
+    pub storage: Vec<StorageAccess>,
+    /// `$ggas` before the instruction.
+    pub gas_before: Word,
+    /// `$ggas` after the instruction.
+    pub gas_after: Word,
+    /// Reason of the panic, if the instruction panicked.
+    pub panic: Option<PanicReason>,
+}
+
+/// New value of a register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct RegisterChange {
+    /// Index of the register.
+    pub register: RegisterId,
+    /// Value after the instruction.
+    pub value: Word,
+}
+
+/// Contiguous memory written by an instruction.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct MemoryWrite {
+    /// Start address of the write.
+    pub address: Word,
+    /// Written bytes.
+    pub data: Vec<u8>,
+}
+
+/// Access to contract storage.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum StorageAccess {
+    /// Read of consecutive state slots starting at `key`, `None` for unset slots.
+    StateRead {
+        /// Contract owning the state.
+        contract: ContractId,
+        /// Key of the first slot.
+        key: Bytes32,
+        /// Value of each slot.
+        values: Vec<Option<Bytes32>>,
+    },
+    /// Write of consecutive state slots starting at `key`.
+    StateWrite {
+        /// Contract owning the state.
+        contract: ContractId,
+        /// Key of the first slot.
+        key: Bytes32,
+        /// New value of each slot.
+        values: Vec<Bytes32>,
+    },
+    /// Removal of consecutive state slots starting at `key`.
+    StateClear {
+        /// Contract owning the state.
+        contract: ContractId,
+        /// Key of the first slot.
+        key: Bytes32,
+        /// Number of slots removed.
+        slots: Word,
+    },
+    /// Read of an asset balance.
+    BalanceRead {
+        /// Contract holding the balance.
+        contract: ContractId,
+        /// Asset of the balance.
+        asset_id: AssetId,
+        /// Value of the balance.
+        value: Word,
+    },
+    /// Update of an asset balance.
+    BalanceWrite {
+        /// Contract holding the balance.
+        contract: ContractId,
+        /// Asset of the balance.
+        asset_id: AssetId,
+        /// New value of the balance.
+        value: Word,
+    },
+}
+
+/// State of the interpreter when the instruction being traced started.
+#[derive(Debug, Clone)]
+pub(crate) struct StepStart {
+    pub(crate) pc: Word,
+    pub(crate) depth: usize,
+    pub(crate) instruction: Instruction,
+    pub(crate) registers: [Word; VM_REGISTER_COUNT],
+}
+
+/// Tracer state of an interpreter.
+#[derive(Default, Clone)]
+pub(crate) struct Trace {
+    tracer: Option<Box<dyn Tracer + Send + Sync>>,
+    start: Option<StepStart>,
+    storage: Vec<StorageAccess>,
+}
+
+impl Trace {
+    pub(crate) fn set_tracer(&mut self, tracer: Box<dyn Tracer + Send + Sync>) {
+        self.tracer = Some(tracer);
+    }
+
+    pub(crate) fn remove_tracer(&mut self) {
+        self.tracer = None;
+    }
+
+    pub(crate) const fn is_enabled(&self) -> bool {
+        self.tracer.is_some()
+    }
+
+    pub(crate) fn begin_step(&mut self, start: StepStart) {
+        self.start = Some(start);
+        self.storage.clear();
+    }
+
+    pub(crate) fn take_start(&mut self) -> Option<StepStart> {
+        self.start.take()
+    }
+
+    pub(crate) fn record_storage(&mut self, access: StorageAccess) {
+        self.storage.push(access);
+    }
+
+    pub(crate) fn take_storage(&mut self) -> Vec<StorageAccess> {
+        core::mem::take(&mut self.storage)
+    }
+
+    pub(crate) fn on_step(&mut self, step: &TraceStep) {
+        if let Some(tracer) = &mut self.tracer {
+            tracer.on_step(step);
+        }
+    }
+}
+
+impl fmt::Debug for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Trace(tracer={})",
+            match self.tracer {
+                Some(_) => "enabled",
+                None => "disabled",
+            }
+        )
+    }
+}
+
+/// Writes every step as a JSON object on its own line.
+///
+/// Clones share the same writer, so the steps of an interpreter and of its clones end up in the
+/// same stream. Write errors don't interrupt the execution: the first one is kept and returned by
+/// [`Self::flush`].
+#[derive(Debug)]
+pub struct JsonLinesTracer<W> {
+    inner: Arc<Mutex<JsonLines<W>>>,
+}
+
+#[derive(Debug)]
+struct JsonLines<W> {
+    writer: W,
+    error: Option<io::Error>,
+}
+
+impl<W> Clone for JsonLinesTracer<W> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<W> JsonLinesTracer<W>
+where
+    W: Write,
+{
+    /// Stream the steps to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(JsonLines { writer, error: None })),
+        }
+    }
+
+    /// Flush the writer, returning the first error that occurred while writing the steps.
+    pub fn flush(&self) -> io::Result<()> {
+        let mut inner = self.lock();
+
+        match inner.error.take() {
+            Some(e) => Err(e),
+            None => inner.writer.flush(),
+        }
+    }
+
+    /// Access the underlying writer, e.g. to read back an in-memory buffer.
+    pub fn with_writer<R>(&self, f: impl FnOnce(&mut W) -> R) -> R {
+        f(&mut self.lock().writer)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, JsonLines<W>> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<W> Tracer for JsonLinesTracer<W>
+where
+    W: Write,
+{
+    fn on_step(&mut self, step: &TraceStep) {
+        let mut inner = self.lock();
+
+        if inner.error.is_some() {
+            return;
+        }
+
+        let JsonLines { writer, .. } = &mut *inner;
+        let result = serde_json::to_writer(&mut *writer, step)
+            .map_err(io::Error::from)
+            .and_then(|_| writer.write_all(b"\n"));
+
+        if let Err(e) = result {
+            inner.error = Some(e);
+        }
+    }
+}
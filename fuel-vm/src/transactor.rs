@@ -1,14 +1,20 @@
 //! State machine of the interpreter.
 
-use crate::checked_transaction::{Checked, IntoChecked};
-use crate::error::InterpreterError;
+use crate::checked_transaction::{CheckPredicates, Checked, IntoChecked};
+use crate::error::{Bug, BugId, BugVariant, InterpreterError};
 use crate::gas::GasCosts;
-use crate::interpreter::{CheckedMetadata, ExecutableTransaction, Interpreter};
+use crate::interpreter::{CheckedMetadata, ExecutableTransaction, Interpreter, Memory};
 use crate::state::{StateTransition, StateTransitionRef};
-use crate::storage::InterpreterStorage;
+use crate::storage::{InterpreterStorage, OverlayStorage};
 use crate::{backtrace::Backtrace, state::ProgramState};
 
-use fuel_tx::{ConsensusParameters, Create, Receipt, Script};
+use fuel_asm::{Instruction, PanicReason, RawInstruction};
+use fuel_tx::field::{GasLimit, GasPrice, Inputs, Outputs};
+use fuel_tx::{
+    Chargeable, CheckError, ConsensusParameters, Create, Input, Output, Receipt, Script, ScriptExecutionResult,
+    TransactionFee,
+};
+use fuel_types::Word;
 
 #[derive(Debug)]
 /// State machine to execute transactions and provide runtime entities on
@@ -101,6 +107,20 @@ where
         self.into()
     }
 
+    /// Reset the underlying interpreter and the result of the last transaction, so the
+    /// transactor behaves as a freshly constructed one over the same storage.
+    ///
+    /// See [`Interpreter::reset`].
+    pub fn reset(&mut self) -> &mut Self
+    where
+        Tx: Default,
+    {
+        self.interpreter.reset();
+        self.program_state.take();
+        self.error.take();
+        self
+    }
+
     /// Consensus parameters
     pub const fn params(&self) -> &ConsensusParameters {
         self.interpreter.params()
@@ -169,6 +189,230 @@ where
     }
 }
 
+impl<S, Tx> Transactor<S, Tx>
+where
+    S: InterpreterStorage,
+    Tx: ExecutableTransaction,
+    <Tx as IntoChecked>::Metadata: CheckedMetadata,
+{
+    /// Execute a transaction on top of the storage without persisting any of its changes, and
+    /// return its state transition.
+    ///
+    /// The result of the last transaction executed with [`Self::transact`] is kept.
+    pub fn dry_run(&self, tx: Checked<Tx>) -> Result<StateTransition<Tx>, InterpreterError> {
+        let storage = OverlayStorage::new(self.interpreter.as_ref());
+        let mut interpreter = Interpreter::with_storage(storage, *self.params(), self.gas_costs().clone());
+
+        interpreter.transact(tx).map(Into::into)
+    }
+}
+
+impl<S> Transactor<S, Script>
+where
+    S: InterpreterStorage,
+{
+    /// Estimate the gas limit of a script by dry-running it with the maximum gas per transaction.
+    ///
+    /// The script runs with a zero gas price, so the estimation doesn't depend on the inputs
+    /// covering the maximum fee, and its signatures aren't checked since they don't sign the
+    /// modified limit and price. The fee is then computed for the estimated limit and the gas
+    /// price of `tx`.
+    ///
+    /// The storage is never modified, see [`Self::dry_run`].
+    pub fn estimate_gas(&self, tx: Script) -> Result<GasEstimate, InterpreterError> {
+        let params = *self.params();
+        let gas_price = tx.price();
+
+        let (predicates_gas_used, vm) = self.dry_run_script(tx)?;
+
+        let (result, gas_used) = vm
+            .receipts()
+            .iter()
+            .find_map(|receipt| match receipt {
+                Receipt::ScriptResult { result, gas_used } => Some((*result, *gas_used)),
+                _ => None,
+            })
+            .ok_or_else(|| Bug::new(BugId::ID008, BugVariant::ScriptResultMissing))?;
+
+        let metered_bytes = vm.transaction().metered_bytes_size() as Word;
+        let fee = TransactionFee::checked_from_values(&params, metered_bytes, gas_used, gas_price);
+
+        Ok(GasEstimate {
+            gas_used,
+            predicates_gas_used,
+            result,
+            fee,
+        })
+    }
+
+    /// Declare the contract inputs and variable outputs a script is missing.
+    ///
+    /// The script is dry-run as in [`Self::estimate_gas`]. When it panics with
+    /// [`PanicReason::ContractNotInInputs`], the contract is appended to the inputs along with its
+    /// `Output::Contract`. When a `TRO` panics with [`PanicReason::OutputNotFound`] because its
+    /// output index is one past the last output, an `Output::Variable` is appended. The script is
+    /// dry-run again after each addition, up to `max_additions` additions.
+    ///
+    /// The returned script can still fail for other reasons, such as a `TRO` targeting an output
+    /// that isn't variable, and needs to be signed again since its inputs and outputs changed. The
+    /// appended contract inputs have zeroed UTXO id, roots and pointer.
+    ///
+    /// Fails with [`InterpreterError::Panic`] if the script still misses a dependency after
+    /// `max_additions` additions, and with [`CheckError::TransactionInputsMax`] if a contract input
+    /// doesn't fit.
+    pub fn discover_dependencies(&self, mut tx: Script, max_additions: usize) -> Result<Script, InterpreterError> {
+        let mut additions = 0;
+
+        loop {
+            let (_, vm) = self.dry_run_script(tx.clone())?;
+
+            let panic = vm.receipts().iter().find_map(|receipt| match receipt {
+                Receipt::Panic {
+                    reason,
+                    pc,
+                    contract_id,
+                    ..
+                } => Some((*reason.reason(), *pc, *contract_id)),
+                _ => None,
+            });
+
+            let missing_contract = match panic {
+                Some((PanicReason::ContractNotInInputs, _, Some(contract_id))) => Some(contract_id),
+
+                Some((PanicReason::OutputNotFound, pc, _))
+                    if transfers_to_next_output(&tx, vm.memory(), vm.registers(), pc) =>
+                {
+                    None
+                }
+
+                _ => return Ok(tx),
+            };
+
+            if additions == max_additions {
+                let reason = match missing_contract {
+                    Some(_) => PanicReason::ContractNotInInputs,
+                    None => PanicReason::OutputNotFound,
+                };
+
+                return Err(InterpreterError::Panic(reason));
+            }
+
+            match missing_contract {
+                Some(contract_id) => {
+                    let input_index = u8::try_from(tx.inputs().len()).map_err(|_| CheckError::TransactionInputsMax)?;
+
+                    tx.inputs_mut().push(Input::contract(
+                        Default::default(),
+                        Default::default(),
+                        Default::default(),
+                        Default::default(),
+                        contract_id,
+                    ));
+                    tx.outputs_mut()
+                        .push(Output::contract(input_index, Default::default(), Default::default()));
+                }
+
+                None => tx
+                    .outputs_mut()
+                    .push(Output::variable(Default::default(), 0, Default::default())),
+            }
+
+            additions += 1;
+        }
+    }
+
+    /// Dry-run a script with the maximum gas per transaction and a zero gas price, returning the
+    /// gas used by its predicates and the VM after its execution.
+    fn dry_run_script(
+        &self,
+        mut tx: Script,
+    ) -> Result<(Word, Interpreter<OverlayStorage<'_, S>, Script>), InterpreterError> {
+        let params = *self.params();
+
+        *tx.gas_limit_mut() = params.max_gas_per_tx;
+        *tx.gas_price_mut() = 0;
+
+        let block_height = self
+            .interpreter
+            .as_ref()
+            .block_height()
+            .map_err(InterpreterError::from_io)?;
+
+        let checked = tx
+            .into_checked_basic(block_height as Word, &params)?
+            .check_predicates(&params, self.gas_costs())?;
+        let predicates_gas_used = checked.metadata().gas_used_by_predicates();
+
+        let storage = OverlayStorage::new(self.interpreter.as_ref());
+        let mut vm = Interpreter::with_storage(storage, params, self.gas_costs().clone());
+        vm.transact(checked)?;
+
+        Ok((predicates_gas_used, vm))
+    }
+}
+
+/// Check if the instruction at `pc` is a `TRO` to the output that would be appended to `tx`,
+/// given the `memory` and `registers` of the VM when it panicked.
+///
+/// The instruction of a panic receipt is the outermost `CALL` when the panic happened in a
+/// contract, so the instruction is read from memory instead.
+fn transfers_to_next_output(tx: &Script, memory: &Memory, registers: &[Word], pc: Word) -> bool {
+    let pc = pc as usize;
+    let instruction = pc
+        .checked_add(Instruction::SIZE)
+        .filter(|end| *end <= memory.len())
+        .map(|_| RawInstruction::from_be_bytes(memory.read_bytes(pc)))
+        .map(Instruction::try_from);
+
+    match instruction {
+        Some(Ok(Instruction::TRO(tro))) => {
+            let (_, output, _, _) = tro.unpack();
+            registers[output] == tx.outputs().len() as Word
+        }
+        _ => false,
+    }
+}
+
+/// Gas used by a dry run of a script, produced by [`Transactor::estimate_gas`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GasEstimate {
+    gas_used: Word,
+    predicates_gas_used: Word,
+    result: ScriptExecutionResult,
+    fee: Option<TransactionFee>,
+}
+
+impl GasEstimate {
+    /// Gas used by the predicates and the script.
+    pub const fn gas_used(&self) -> Word {
+        self.gas_used
+    }
+
+    /// Gas used by the predicates only.
+    pub const fn predicates_gas_used(&self) -> Word {
+        self.predicates_gas_used
+    }
+
+    /// Gas limit of the script, exactly [`Self::gas_used`] without any margin.
+    ///
+    /// The execution is deterministic, so this limit is enough as long as the state read by the
+    /// script doesn't change before it is executed. Otherwise, the caller adds its own margin.
+    pub const fn gas_limit(&self) -> Word {
+        self.gas_used
+    }
+
+    /// Result of the dry run. A reverted script may use less gas than a successful one.
+    pub const fn result(&self) -> &ScriptExecutionResult {
+        &self.result
+    }
+
+    /// Fee of the script with [`Self::gas_limit`] and its gas price, or `None` if it
+    /// overflows.
+    pub const fn fee(&self) -> Option<&TransactionFee> {
+        self.fee.as_ref()
+    }
+}
+
 impl<S, Tx> From<Interpreter<S, Tx>> for Transactor<S, Tx>
 where
     Tx: ExecutableTransaction,
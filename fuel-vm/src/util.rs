@@ -72,16 +72,18 @@ macro_rules! script_with_data_offset {
 /// Testing utilities
 pub mod test_helpers {
     use crate::checked_transaction::{builder::TransactionBuilderExt, Checked, IntoChecked};
+    use crate::consts::WORD_SIZE;
     use crate::gas::GasCosts;
     use crate::memory_client::MemoryClient;
     use crate::state::StateTransition;
-    use crate::storage::{InterpreterStorage, MemoryStorage};
+    use crate::storage::{ContractsAssets, ContractsState, InterpreterStorage, MemoryStorage};
     use crate::transactor::Transactor;
     use anyhow::anyhow;
 
     use crate::interpreter::{CheckedMetadata, ExecutableTransaction};
     use crate::prelude::Call;
     use fuel_asm::{op, GTFArgs, Instruction, PanicReason, RegId};
+    use fuel_storage::MerkleRootStorage;
     use fuel_tx::field::Outputs;
     use fuel_tx::{
         ConsensusParameters, Contract, Create, Input, Output, Receipt, Script, StorageSlot, Transaction,
@@ -343,8 +345,10 @@ pub mod test_helpers {
             // verify serialized tx == referenced tx
             let transaction: Transaction = interpreter.transaction().clone().into();
             let tx_offset = self.params.tx_offset();
-            let tx_mem = &interpreter.memory()[tx_offset..(tx_offset + transaction.serialized_size())];
-            let deser_tx = Transaction::from_bytes(tx_mem).unwrap();
+            let tx_mem = interpreter
+                .memory()
+                .read(tx_offset..(tx_offset + transaction.serialized_size()));
+            let deser_tx = Transaction::from_bytes(&tx_mem).unwrap();
 
             assert_eq!(deser_tx, transaction);
 
@@ -485,6 +489,83 @@ pub mod test_helpers {
         });
         change.unwrap_or_else(|| panic!("no change matching asset ID {:x} was found", &find_asset_id))
     }
+
+    /// Deploy `program` with a zeroed salt and the provided storage slots, returning the id of the
+    /// contract.
+    pub fn deploy_contract<S>(storage: &mut S, program: Vec<Instruction>, slots: &[StorageSlot]) -> ContractId
+    where
+        S: InterpreterStorage,
+    {
+        let contract: Contract = program.into_iter().collect::<Vec<u8>>().into();
+        let id = contract.id(
+            &Salt::zeroed(),
+            &contract.root(),
+            &Contract::initial_state_root(slots.iter()),
+        );
+        storage
+            .deploy_contract(&Salt::zeroed(), slots, &contract)
+            .expect("failed to deploy the contract");
+
+        id
+    }
+
+    /// Script running `prefix`, then calling `contract` with the balance and state roots it has in
+    /// `storage`.
+    pub fn call_contract(storage: &MemoryStorage, contract: ContractId, prefix: &[Instruction]) -> Checked<Script> {
+        let params = ConsensusParameters::DEFAULT;
+        let (script, _) = script_with_data_offset!(
+            data_offset,
+            prefix
+                .iter()
+                .copied()
+                .chain([
+                    op::movi(0x10, data_offset),
+                    op::call(0x10, RegId::ZERO, 0x10, RegId::CGAS),
+                    op::ret(RegId::ONE),
+                ])
+                .collect::<Vec<Instruction>>(),
+            params.tx_offset()
+        );
+        let mut script_data = contract.to_vec();
+        script_data.extend([0u8; WORD_SIZE * 2]);
+
+        let balance_root = MerkleRootStorage::<ContractId, ContractsAssets>::root(storage, &contract).unwrap();
+        let state_root = MerkleRootStorage::<ContractId, ContractsState>::root(storage, &contract).unwrap();
+
+        Transaction::script(
+            0,
+            1_000_000,
+            0,
+            script.into_iter().collect(),
+            script_data,
+            vec![Input::contract(
+                Default::default(),
+                balance_root.into(),
+                state_root.into(),
+                Default::default(),
+                contract,
+            )],
+            vec![Output::contract(0, Default::default(), Default::default())],
+            vec![],
+        )
+        .into_checked(0, &params, &GasCosts::default())
+        .expect("failed to generate a checked tx")
+    }
+
+    /// Receipts of `tx` executed on `storage` with the default parameters, `None` if the execution
+    /// failed.
+    pub fn transact_receipts<S>(storage: S, tx: Checked<Script>) -> Option<Vec<Receipt>>
+    where
+        S: InterpreterStorage,
+    {
+        let mut transactor = Transactor::new(storage, Default::default(), Default::default());
+
+        transactor
+            .transact(tx)
+            .result()
+            .ok()
+            .map(|result| result.receipts().to_vec())
+    }
 }
 
 #[allow(missing_docs)]
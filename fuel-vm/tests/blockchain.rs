@@ -1388,6 +1388,78 @@ fn smo_instruction_works() {
     execute_test(rng, 1_000, 0, vec![0xfa; 15]);
 }
 
+#[test]
+fn block_history_is_exposed_to_scripts() {
+    let mut client = MemoryClient::default();
+    let params = *client.params();
+
+    let blocks: Vec<_> = (0..3u8)
+        .map(|i| BlockInfo {
+            hash: Bytes32::from([i + 1; 32]),
+            timestamp: 4_611_686_020_108_779_339 + i as Word * 3,
+            coinbase: Address::from([i + 0x10; 32]),
+        })
+        .collect();
+
+    let start = client.as_ref().block_height().expect("failed to read the block height");
+    for block in &blocks {
+        client.as_mut().push_block(*block).unwrap();
+    }
+    let height = start + blocks.len() as u32;
+    assert_eq!(client.as_ref().block(start + 2), Some(&blocks[1]));
+
+    let script = |client: &MemoryClient, queried: u32| {
+        #[rustfmt::skip]
+        let script = vec![
+            op::movi(0x11, queried),
+            op::move_(0x13, RegId::SP),
+            op::cfei(64),
+            op::bhsh(0x13, 0x11),
+            op::bhei(0x10),
+            op::time(0x12, 0x11),
+            op::log(0x10, 0x12, RegId::ZERO, RegId::ZERO),
+            op::addi(0x14, 0x13, 32),
+            op::cb(0x14),
+            op::movi(0x15, 64),
+            op::logd(RegId::ZERO, RegId::ZERO, 0x13, 0x15),
+            op::ret(RegId::ONE),
+        ];
+
+        TransactionBuilder::script(script.into_iter().collect(), vec![])
+            .gas_limit(1_000_000)
+            .finalize_checked(height as Word, &params, client.gas_costs())
+    };
+
+    let tx = script(&client, start + 2);
+    let receipts = client.transact(tx).to_vec();
+
+    assert_eq!(receipts[0].ra(), Some(height as Word));
+    assert_eq!(receipts[0].rb(), Some(blocks[1].timestamp));
+    assert_eq!(
+        receipts[1].data(),
+        Some(
+            [blocks[1].hash.as_ref(), blocks[2].coinbase.as_ref()]
+                .concat()
+                .as_slice()
+        )
+    );
+
+    // Blocks past the recorded history are unknown to the storage
+    let tx = script(&client, height + 1);
+    let gas_costs = client.gas_costs().clone();
+    let mut transactor = Transactor::new(client.as_mut(), params, gas_costs);
+    transactor.transact(tx);
+
+    assert!(matches!(
+        transactor.result(),
+        Err(InterpreterError::Io(e)) if e.kind() == std::io::ErrorKind::NotFound
+    ));
+
+    let mut storage = MemoryStorage::new(u32::MAX - 1, Default::default());
+    assert_eq!(storage.push_block(blocks[0]).unwrap(), u32::MAX);
+    assert!(storage.push_block(blocks[1]).is_err());
+}
+
 #[test]
 fn timestamp_works() {
     let mut client = MemoryClient::default();
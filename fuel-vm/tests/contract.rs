@@ -1,6 +1,6 @@
 use fuel_asm::op;
 use fuel_asm::RegId;
-use fuel_tx::field::ScriptData;
+use fuel_tx::field::{ReceiptsRoot, ScriptData};
 use fuel_vm::prelude::*;
 use fuel_vm::script_with_data_offset;
 use fuel_vm::util::test_helpers::TestBuilder;
@@ -289,6 +289,133 @@ fn mint_burn() {
     assert_eq!(0, storage_balance);
 }
 
+#[test]
+fn mint_burn_with_sub_id() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let sub_id = Bytes32::from([7; 32]);
+
+    let contract_id = test_context
+        .setup_contract(
+            vec![
+                op::addi(0x10, RegId::FP, CallFrame::a_offset() as Immediate12),
+                op::lw(0x10, 0x10, 0),
+                op::movi(0x11, 100),
+                op::mints(0x11, 0x10),
+                op::movi(0x11, 40),
+                op::burns(0x11, 0x10),
+                op::ret(RegId::ONE),
+            ],
+            None,
+            None,
+        )
+        .contract_id;
+
+    let (script, data_offset) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset),
+            op::call(0x10, RegId::ZERO, 0x10, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.tx_offset()
+    );
+    // The sub id follows the call structure in the script data
+    let sub_id_offset = data_offset as Word + Call::new(contract_id, 0, 0).serialized_size() as Word;
+    let mut script_data = Call::new(contract_id, sub_id_offset, 0).to_bytes();
+    script_data.extend(sub_id.as_ref());
+
+    let result = test_context
+        .start_script(script, script_data)
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .contract_output(&contract_id)
+        .execute();
+
+    let asset_id = Contract::asset_id(&contract_id, &sub_id);
+    let supply: Vec<_> = result
+        .receipts()
+        .iter()
+        .filter(|r| matches!(r, Receipt::Mint { .. } | Receipt::Burn { .. }))
+        .cloned()
+        .collect();
+
+    assert_eq!(
+        supply,
+        vec![
+            Receipt::mint(
+                contract_id,
+                asset_id,
+                100,
+                supply[0].pc().unwrap(),
+                supply[0].is().unwrap()
+            ),
+            Receipt::burn(
+                contract_id,
+                asset_id,
+                40,
+                supply[1].pc().unwrap(),
+                supply[1].is().unwrap()
+            ),
+        ]
+    );
+    assert_eq!(test_context.get_contract_balance(&contract_id, &asset_id), 60);
+    assert_eq!(
+        test_context.get_contract_balance(&contract_id, &AssetId::from(*contract_id)),
+        0
+    );
+}
+
+#[test]
+fn mint_burn_receipts_are_in_receipts_root() {
+    let mut test_context = TestBuilder::new(2322u64);
+
+    let contract_id = test_context
+        .setup_contract(
+            vec![
+                op::movi(0x11, 50),
+                op::mint(0x11),
+                op::movi(0x11, 20),
+                op::burn(0x11),
+                op::ret(RegId::ONE),
+            ],
+            None,
+            None,
+        )
+        .contract_id;
+
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset),
+            op::call(0x10, RegId::ZERO, 0x10, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.tx_offset()
+    );
+
+    let result = test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .contract_output(&contract_id)
+        .execute();
+
+    let asset_id = AssetId::from(*contract_id);
+    let receipts = result.receipts();
+
+    assert!(matches!(
+        receipts[1],
+        Receipt::Mint { contract_id: c, asset_id: a, amount: 50, .. } if c == contract_id && a == asset_id
+    ));
+    assert!(matches!(
+        receipts[2],
+        Receipt::Burn { contract_id: c, asset_id: a, amount: 20, .. } if c == contract_id && a == asset_id
+    ));
+
+    let root = fuel_vm::crypto::ephemeral_merkle_root(receipts.iter().map(|r| r.clone().to_bytes()));
+    assert_eq!(result.tx().receipts_root(), &root);
+}
+
 #[test]
 fn call_increases_contract_asset_balance_and_balance_register() {
     let rng = &mut StdRng::seed_from_u64(2322u64);
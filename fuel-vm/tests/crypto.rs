@@ -1,9 +1,10 @@
 use fuel_asm::PanicReason::{ArithmeticOverflow, ErrorFlag, MemoryOverflow};
 use fuel_asm::{op, GTFArgs, RegId};
 use fuel_crypto::Hasher;
+use fuel_tx::field::ReceiptsRoot;
 use fuel_tx::TransactionBuilder;
 use rand::rngs::StdRng;
-use rand::SeedableRng;
+use rand::{Rng, SeedableRng};
 use sha3::{Digest, Keccak256};
 
 use fuel_vm::prelude::*;
@@ -320,3 +321,99 @@ fn k256_b_gt_vmaxram_sub_c() {
 
     check_expected_reason_for_instructions(script, MemoryOverflow);
 }
+
+#[test]
+fn receipt_proofs_verify_against_receipts_root() {
+    let mut test_context = fuel_vm::util::test_helpers::TestBuilder::new(2322u64);
+
+    #[rustfmt::skip]
+    let script = vec![
+        op::log(RegId::ONE, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::movi(0x10, 16),
+        op::aloc(0x10),
+        op::addi(0x11, RegId::HP, 1),
+        op::logd(RegId::ZERO, RegId::ONE, 0x11, 0x10),
+        op::log(0x10, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ];
+
+    let result = test_context.start_script(script, vec![]).gas_limit(1_000_000).execute();
+    let receipts = result.receipts();
+    let root = *result.tx().receipts_root();
+
+    assert_eq!(receipts.len(), 5);
+
+    for (index, receipt) in receipts.iter().enumerate() {
+        let (proof_root, proof_set) = result.receipt_proof(index).expect("receipt is in the tree");
+
+        assert_eq!(proof_root, root);
+        assert!(fuel_vm::crypto::verify_receipt_proof(
+            &root,
+            receipt,
+            &proof_set,
+            index,
+            receipts.len()
+        ));
+        assert!(!fuel_vm::crypto::verify_receipt_proof(
+            &root,
+            &receipts[(index + 1) % receipts.len()],
+            &proof_set,
+            index,
+            receipts.len()
+        ));
+    }
+
+    assert!(result.receipt_proof(receipts.len()).is_none());
+}
+
+#[test]
+fn message_out_proofs_verify_against_block_messages_root() {
+    use fuel_vm::crypto::{message_out_proof, message_outs_root, verify_message_out_proof};
+
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+
+    let message = |rng: &mut StdRng, txid: &Bytes32, idx: Word| {
+        Receipt::message_out_from_tx_output(txid, idx, rng.gen(), rng.gen(), rng.gen(), vec![0xfa; 15])
+    };
+
+    let (tx_a, tx_b): (Bytes32, Bytes32) = (rng.gen(), rng.gen());
+    let block = [
+        vec![
+            message(rng, &tx_a, 0),
+            Receipt::log(ContractId::zeroed(), 0, 0, 0, 0, 0, 0),
+            message(rng, &tx_a, 1),
+        ],
+        vec![message(rng, &tx_b, 0)],
+    ];
+    let receipts: Vec<&Receipt> = block.iter().flatten().collect();
+    let messages: Vec<&Receipt> = receipts.iter().copied().filter(|r| r.message_id().is_some()).collect();
+
+    let root = message_outs_root(receipts.iter().copied());
+
+    for (index, receipt) in messages.iter().enumerate() {
+        let proof = message_out_proof(receipts.iter().copied(), receipt.message_id().unwrap()).unwrap();
+
+        assert_eq!(proof.root, root);
+        assert_eq!(proof.index, index as u64);
+        assert_eq!(proof.messages_count, 3);
+        assert!(verify_message_out_proof(&root, receipt, &proof));
+    }
+
+    let proof = message_out_proof(receipts.iter().copied(), messages[1].message_id().unwrap()).unwrap();
+    assert!(!verify_message_out_proof(&root, messages[0], &proof));
+    assert!(!verify_message_out_proof(&root, receipts[1], &proof));
+
+    let mut altered = messages[1].clone();
+    if let Receipt::MessageOut { amount, .. } = &mut altered {
+        *amount += 1;
+    }
+    assert!(!verify_message_out_proof(&root, &altered, &proof));
+
+    // A proof over another tree is rejected against the trusted root
+    let foreign = message(rng, &tx_b, 1);
+    let proof = message_out_proof([&foreign, messages[0]], foreign.message_id().unwrap()).unwrap();
+    assert!(verify_message_out_proof(&proof.root, &foreign, &proof));
+    assert!(!verify_message_out_proof(&root, &foreign, &proof));
+
+    assert!(message_out_proof(receipts.iter().copied(), &rng.gen()).is_none());
+}
@@ -0,0 +1,142 @@
+use fuel_asm::{op, PanicReason, RegId};
+use fuel_tx::field::{GasLimit, Outputs};
+use fuel_vm::consts::WORD_SIZE;
+use fuel_vm::prelude::*;
+use fuel_vm::script_with_data_offset;
+use fuel_vm::util::test_helpers::deploy_contract;
+
+/// Deploy a contract sending 10 units of the zeroed asset to the variable output at `output`.
+fn deploy(storage: &mut MemoryStorage, output: u16) -> ContractId {
+    let contract_id = deploy_contract(
+        storage,
+        vec![
+            op::addi(0x11, RegId::FP, AssetId::LEN as u16),
+            op::movi(0x12, 10),
+            op::movi(0x13, output.into()),
+            op::tro(RegId::FP, 0x13, 0x12, 0x11),
+            op::ret(RegId::ONE),
+        ],
+        &[],
+    );
+
+    storage
+        .merkle_contract_asset_id_balance_insert(&contract_id, &AssetId::zeroed(), 100)
+        .unwrap();
+
+    contract_id
+}
+
+/// Script calling `contract`, without declaring any input or output.
+fn call(contract: ContractId) -> Script {
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset),
+            op::addi(0x11, 0x10, (ContractId::LEN + WORD_SIZE * 2) as u16),
+            op::call(0x10, RegId::ZERO, 0x11, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        ConsensusParameters::DEFAULT.tx_offset()
+    );
+    let mut script_data = contract.to_vec();
+    script_data.extend([0u8; WORD_SIZE * 2]);
+    script_data.extend(AssetId::zeroed().as_ref());
+
+    Transaction::script(
+        0,
+        1_000_000,
+        0,
+        script.into_iter().collect(),
+        script_data,
+        vec![],
+        vec![],
+        vec![],
+    )
+}
+
+#[test]
+fn missing_contract_and_variable_output_are_declared() {
+    let params = ConsensusParameters::DEFAULT;
+    let mut storage = MemoryStorage::default();
+    let contract = deploy(&mut storage, 1);
+
+    let transactor = Transactor::<_, Script>::new(&mut storage, params, GasCosts::default());
+    let tx = transactor
+        .discover_dependencies(call(contract), 10)
+        .expect("failed to discover dependencies");
+
+    assert_eq!(tx.input_contracts().copied().collect::<Vec<_>>(), vec![contract]);
+    assert!(matches!(
+        tx.outputs().as_slice(),
+        [
+            Output::Contract { input_index: 0, .. },
+            Output::Variable { amount: 0, .. }
+        ]
+    ));
+
+    // A script declaring everything is returned as is
+    let again = transactor
+        .discover_dependencies(tx.clone(), 1)
+        .expect("failed to discover dependencies");
+    assert_eq!(again, tx);
+
+    let tx = tx
+        .into_checked(0, &params, &GasCosts::default())
+        .expect("failed to generate a checked tx");
+    let mut transactor = Transactor::new(&mut storage, params, GasCosts::default());
+    transactor.transact(tx);
+
+    let receipts = transactor.receipts().expect("failed to execute script");
+    assert!(receipts.iter().any(|receipt| matches!(
+        receipt,
+        Receipt::TransferOut { id, amount: 10, .. } if *id == contract
+    )));
+    assert!(!receipts.iter().any(|receipt| matches!(receipt, Receipt::Panic { .. })));
+}
+
+#[test]
+fn discovery_is_bounded() {
+    let params = ConsensusParameters::DEFAULT;
+    let mut storage = MemoryStorage::default();
+    let contract = deploy(&mut storage, 1);
+
+    let transactor = Transactor::<_, Script>::new(&mut storage, params, GasCosts::default());
+
+    // The contract is the first addition and the variable output the second
+    let err = transactor
+        .discover_dependencies(call(contract), 0)
+        .expect_err("the contract is missing");
+    assert_eq!(err.panic_reason(), Some(PanicReason::ContractNotInInputs));
+
+    let err = transactor
+        .discover_dependencies(call(contract), 1)
+        .expect_err("the variable output is still missing");
+    assert_eq!(err.panic_reason(), Some(PanicReason::OutputNotFound));
+
+    let mut tx = transactor
+        .discover_dependencies(call(contract), 2)
+        .expect("failed to discover dependencies");
+    assert_eq!(tx.outputs().len(), 2);
+
+    // The caller's gas limit is kept
+    assert_eq!(*tx.gas_limit_mut(), 1_000_000);
+}
+
+#[test]
+fn transfer_to_a_declared_output_is_left_as_is() {
+    let params = ConsensusParameters::DEFAULT;
+    let mut storage = MemoryStorage::default();
+    // The output at index 0 is the contract output of the discovered input
+    let contract = deploy(&mut storage, 0);
+
+    let transactor = Transactor::<_, Script>::new(&mut storage, params, GasCosts::default());
+    let tx = transactor
+        .discover_dependencies(call(contract), 10)
+        .expect("failed to discover dependencies");
+
+    assert_eq!(tx.input_contracts().copied().collect::<Vec<_>>(), vec![contract]);
+    assert!(matches!(
+        tx.outputs().as_slice(),
+        [Output::Contract { input_index: 0, .. }]
+    ));
+}
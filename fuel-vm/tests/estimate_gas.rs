@@ -0,0 +1,163 @@
+use fuel_asm::{op, PanicReason, RegId};
+use fuel_tx::field::GasLimit;
+use fuel_tx::TransactionBuilder;
+use fuel_vm::consts::WORD_SIZE;
+use fuel_vm::prelude::*;
+use fuel_vm::script_with_data_offset;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Execute a script, returning the `ScriptResult` receipt and the panic reason, if any.
+fn execute(storage: &mut MemoryStorage, tx: Script) -> (ScriptExecutionResult, Word, Option<PanicReason>) {
+    let params = ConsensusParameters::DEFAULT;
+    let tx = tx
+        .into_checked(0, &params, &GasCosts::default())
+        .expect("failed to generate a checked tx");
+
+    let mut transactor = Transactor::new(storage, params, GasCosts::default());
+    transactor.transact(tx);
+
+    let receipts = transactor.receipts().expect("failed to execute script");
+    let panic = receipts.iter().find_map(|receipt| match receipt {
+        Receipt::Panic { reason, .. } => Some(*reason.reason()),
+        _ => None,
+    });
+    let (result, gas_used) = receipts
+        .iter()
+        .find_map(|receipt| match receipt {
+            Receipt::ScriptResult { result, gas_used } => Some((*result, *gas_used)),
+            _ => None,
+        })
+        .expect("missing script result");
+
+    (result, gas_used, panic)
+}
+
+#[test]
+fn estimated_limit_covers_script_and_predicates() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let params = ConsensusParameters::DEFAULT;
+    let gas_price = 10;
+
+    let predicate: Vec<u8> = vec![op::movi(0x10, 3), op::muli(0x10, 0x10, 3), op::ret(RegId::ONE)]
+        .into_iter()
+        .collect();
+    let owner = Input::predicate_owner(&predicate);
+    let script = vec![
+        op::movi(0x10, 100),
+        op::subi(0x10, 0x10, 1),
+        op::jnzi(0x10, 1),
+        op::ret(RegId::ONE),
+    ];
+
+    let mut builder = TransactionBuilder::script(script.into_iter().collect(), vec![]);
+    builder
+        .gas_price(gas_price)
+        .gas_limit(0)
+        .add_input(Input::coin_predicate(
+            rng.gen(),
+            owner,
+            1_000_000,
+            AssetId::BASE,
+            rng.gen(),
+            0,
+            predicate,
+            vec![],
+        ))
+        .add_output(Output::change(rng.gen(), 0, AssetId::BASE));
+    let tx = builder.finalize();
+
+    let mut storage = MemoryStorage::default();
+    let estimate = Transactor::<_, Script>::new(&mut storage, params, GasCosts::default())
+        .estimate_gas(tx.clone())
+        .expect("failed to estimate gas");
+
+    assert_eq!(estimate.result(), &ScriptExecutionResult::Success);
+    assert!(estimate.predicates_gas_used() > 0);
+    assert!(estimate.gas_used() > estimate.predicates_gas_used());
+    assert_eq!(
+        estimate.fee(),
+        TransactionFee::checked_from_values(
+            &params,
+            tx.metered_bytes_size() as Word,
+            estimate.gas_limit(),
+            gas_price
+        )
+        .as_ref()
+    );
+
+    let mut exact = tx.clone();
+    *exact.gas_limit_mut() = estimate.gas_limit();
+    let (result, gas_used, _) = execute(&mut storage, exact);
+    assert_eq!(result, ScriptExecutionResult::Success);
+    assert_eq!(gas_used, estimate.gas_used());
+
+    let mut short = tx;
+    *short.gas_limit_mut() = estimate.gas_limit() - 1;
+    let (_, _, panic) = execute(&mut storage, short);
+    assert_eq!(panic, Some(PanicReason::OutOfGas));
+}
+
+#[test]
+fn estimation_never_modifies_storage() {
+    let params = ConsensusParameters::DEFAULT;
+    let mut storage = MemoryStorage::default();
+
+    // Increment the counter stored under the contract id
+    let contract: Contract = vec![
+        op::srw(0x10, 0x11, RegId::FP),
+        op::addi(0x10, 0x10, 1),
+        op::sww(RegId::FP, 0x11, 0x10),
+        op::ret(0x10),
+    ]
+    .into_iter()
+    .collect::<Vec<u8>>()
+    .into();
+    let contract_id = contract.id(&Salt::zeroed(), &contract.root(), &Contract::default_state_root());
+    storage.deploy_contract(&Salt::zeroed(), &[], &contract).unwrap();
+
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset),
+            op::call(0x10, RegId::ZERO, 0x10, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        params.tx_offset()
+    );
+    let mut script_data = contract_id.to_vec();
+    script_data.extend([0u8; WORD_SIZE * 2]);
+    let tx = Transaction::script(
+        0,
+        0,
+        0,
+        script.into_iter().collect(),
+        script_data,
+        vec![Input::contract(
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            contract_id,
+        )],
+        vec![Output::contract(0, Default::default(), Default::default())],
+        vec![],
+    );
+
+    let key = Bytes32::new(*contract_id);
+    let before = storage.contract_state(&contract_id, &key).into_owned();
+
+    let transactor = Transactor::<_, Script>::new(&mut storage, params, GasCosts::default());
+    let first = transactor.estimate_gas(tx.clone()).expect("failed to estimate gas");
+    let second = transactor.estimate_gas(tx.clone()).expect("failed to estimate gas");
+
+    assert_eq!(first, second);
+    assert_eq!(first.result(), &ScriptExecutionResult::Success);
+    assert_eq!(storage.contract_state(&contract_id, &key).into_owned(), before);
+
+    let mut tx = tx;
+    *tx.gas_limit_mut() = first.gas_limit();
+    let (result, gas_used, _) = execute(&mut storage, tx);
+    assert_eq!(result, ScriptExecutionResult::Success);
+    assert_eq!(gas_used, first.gas_used());
+    assert_ne!(storage.contract_state(&contract_id, &key).into_owned(), before);
+}
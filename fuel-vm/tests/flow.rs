@@ -853,3 +853,137 @@ fn logd_from_top_of_heap() {
     assert_eq!(receipts.len(), 3);
     assert!(matches!(receipts[0], Receipt::LogData { .. }));
 }
+
+#[test]
+fn decode_cache_matches_uncached_execution() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+
+    let mut storage = MemoryStorage::default();
+    let params = ConsensusParameters::DEFAULT;
+    let gas_costs = GasCosts::default();
+
+    let mut deploy = |code: Vec<Instruction>| {
+        let program = Witness::from(code.into_iter().collect::<Vec<u8>>());
+        let salt: Salt = rng.gen();
+        let contract = Contract::from(program.as_ref());
+        let state_root = Contract::default_state_root();
+        let id = contract.id(&salt, &contract.root(), &state_root);
+
+        let tx = Transaction::create(
+            0,
+            1_000_000,
+            0,
+            0,
+            salt,
+            vec![],
+            vec![],
+            vec![Output::contract_created(id, state_root)],
+            vec![program],
+        )
+        .into_checked(0, &params, &gas_costs)
+        .expect("failed to generate a checked tx");
+
+        assert!(Transactor::new(&mut storage, params, gas_costs.clone())
+            .transact(tx)
+            .is_success());
+
+        id
+    };
+
+    let logger = vec![
+        op::movi(0x10, 7),
+        op::log(0x10, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::ret(0x10),
+    ];
+    let logger_len = (logger.len() * Instruction::SIZE) as Immediate18;
+    let logger = deploy(logger);
+
+    // Load the logger code with LDC and jump into it
+    let loader = deploy(vec![
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::addi(0x10, 0x10, 96),
+        op::movi(0x11, logger_len),
+        op::move_(0x12, RegId::SSP),
+        op::ldc(0x10, RegId::ZERO, 0x11),
+        op::sub(0x12, 0x12, RegId::IS),
+        op::divi(0x12, 0x12, Instruction::SIZE as Immediate12),
+        op::jmp(0x12),
+    ]);
+
+    let mut script_ops = vec![
+        op::movi(0x10, 0x00),
+        op::addi(0x11, 0x10, 128),
+        op::call(0x10, RegId::ZERO, 0x11, RegId::CGAS),
+        op::call(0x10, RegId::ZERO, 0x11, RegId::CGAS),
+        op::addi(0x12, 0x10, 48),
+        op::call(0x12, RegId::ZERO, 0x11, RegId::CGAS),
+        op::ret(RegId::ONE),
+    ];
+
+    let script_data: Vec<u8> = [
+        logger.as_ref(),
+        &[0; 16],
+        loader.as_ref(),
+        &[0; 16],
+        logger.as_ref(),
+        &[0; 32],
+    ]
+    .concat();
+    let inputs = vec![
+        Input::contract(rng.gen(), rng.gen(), rng.gen(), rng.gen(), logger),
+        Input::contract(rng.gen(), rng.gen(), rng.gen(), rng.gen(), loader),
+    ];
+    let outputs = vec![
+        Output::contract(0, rng.gen(), rng.gen()),
+        Output::contract(1, rng.gen(), rng.gen()),
+    ];
+
+    let tx = |script_ops: &[Instruction]| {
+        Transaction::script(
+            0,
+            1_000_000,
+            0,
+            script_ops.iter().copied().collect(),
+            script_data.clone(),
+            inputs.clone(),
+            outputs.clone(),
+            vec![],
+        )
+        .into_checked(0, &params, &gas_costs)
+        .expect("failed to generate a checked tx")
+    };
+
+    let script_data_mem = params.tx_offset() + tx(&script_ops).transaction().script_data_offset();
+    script_ops[0] = op::movi(0x10, script_data_mem as Immediate18);
+    let tx = tx(&script_ops);
+
+    let uncached = Transactor::new(storage.clone(), params, gas_costs.clone())
+        .transact(tx.clone())
+        .receipts()
+        .expect("Failed to execute script")
+        .to_owned();
+
+    let mut cached = Transactor::from(
+        Interpreter::with_storage(storage, params, gas_costs)
+            .with_decode_cache(DecodeCache::new())
+            .build(),
+    );
+    let receipts = cached
+        .transact(tx)
+        .receipts()
+        .expect("Failed to execute script")
+        .to_owned();
+
+    assert_eq!(receipts, uncached);
+    assert_eq!(
+        receipts
+            .iter()
+            .filter(|r| matches!(r, Receipt::Log { ra: 7, .. }))
+            .count(),
+        3
+    );
+
+    let vm: &Interpreter<MemoryStorage, Script> = cached.as_ref();
+    let cache = vm.decode_cache().expect("decode cache is enabled");
+    assert_eq!((cache.hits(), cache.misses(), cache.len()), (1, 2, 2));
+}
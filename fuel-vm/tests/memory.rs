@@ -1,5 +1,6 @@
 use fuel_asm::op;
 use fuel_asm::RegId;
+use fuel_vm::checked_transaction::Checked;
 use fuel_vm::prelude::*;
 
 fn setup(program: Vec<Instruction>) -> Transactor<MemoryStorage, Script> {
@@ -56,3 +57,72 @@ fn test_lb() {
     let result = vm.registers()[0x13_usize] as u8;
     assert_eq!(1, result);
 }
+
+fn script(program: Vec<Instruction>) -> Checked<Script> {
+    let params = ConsensusParameters::default();
+    let script = program.into_iter().collect();
+
+    Transaction::script(0, 1_000_000, 0, script, vec![], vec![], vec![], vec![])
+        .into_checked(0, &params, &GasCosts::default())
+        .expect("failed to check tx")
+}
+
+#[test]
+fn reused_transactor_matches_fresh_one() {
+    // Dirty the heap, then read it back uninitialized from a later transaction
+    let programs = vec![
+        vec![
+            op::movi(0x10, 64),
+            op::aloc(0x10),
+            op::addi(0x11, RegId::HP, 1),
+            op::movi(0x12, 0x3ffff),
+            op::sw(0x11, 0x12, 0),
+            op::ret(RegId::ONE),
+        ],
+        vec![
+            op::movi(0x10, 64),
+            op::aloc(0x10),
+            op::addi(0x11, RegId::HP, 1),
+            op::lw(0x13, 0x11, 0),
+            op::log(0x13, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+            op::ret(0x13),
+        ],
+        vec![op::rvrt(RegId::ONE)],
+    ];
+
+    let mut reused = Transactor::<_, Script>::new(MemoryStorage::default(), Default::default(), Default::default());
+
+    for program in programs {
+        let mut fresh = Transactor::<_, Script>::new(MemoryStorage::default(), Default::default(), Default::default());
+        fresh.transact(script(program.clone()));
+        reused.transact(script(program));
+
+        assert_eq!(fresh.receipts(), reused.receipts());
+
+        let fresh: &Interpreter<MemoryStorage, Script> = fresh.as_ref();
+        let vm: &Interpreter<MemoryStorage, Script> = reused.as_ref();
+        assert!(fresh == vm, "reused interpreter diverged from a fresh one");
+    }
+}
+
+#[test]
+fn reset_transactor_matches_fresh_one() {
+    let mut transactor = setup(vec![
+        op::movi(0x10, 64),
+        op::aloc(0x10),
+        op::cfei(128),
+        op::log(RegId::ONE, RegId::ONE, RegId::ONE, RegId::ONE),
+        op::ret(RegId::ONE),
+    ]);
+    assert!(transactor.is_success());
+
+    transactor.reset();
+
+    let fresh =
+        Interpreter::<MemoryStorage, Script>::with_storage(Default::default(), Default::default(), Default::default());
+    assert!(!transactor.is_success());
+    assert!(!transactor.is_error());
+
+    let vm: &Interpreter<MemoryStorage, Script> = transactor.as_ref();
+    assert!(vm == &fresh);
+}
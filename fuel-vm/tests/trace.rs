@@ -0,0 +1,240 @@
+use fuel_asm::{op, RegId};
+use fuel_vm::{consts::*, prelude::*, script_with_data_offset};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+fn read_steps(tracer: &JsonLinesTracer<Vec<u8>>) -> Vec<TraceStep> {
+    tracer.flush().expect("failed to write the trace");
+    tracer.with_writer(|buf| {
+        std::str::from_utf8(buf)
+            .expect("trace is not utf-8")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("failed to parse a trace step"))
+            .collect()
+    })
+}
+
+#[test]
+fn trace_reports_every_executed_instruction() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+
+    let mut storage = MemoryStorage::default();
+    let params = ConsensusParameters::DEFAULT;
+    let gas_costs = GasCosts::default();
+
+    // Store a word under the contract id as key and read it back
+    let program: Vec<u8> = vec![
+        op::movi(0x10, 42),
+        op::sww(RegId::FP, 0x11, 0x10),
+        op::srw(0x12, 0x13, RegId::FP),
+        op::ret(0x12),
+    ]
+    .into_iter()
+    .collect();
+    let program = Witness::from(program);
+
+    let salt: Salt = rng.gen();
+    let contract = Contract::from(program.as_ref());
+    let state_root = Contract::default_state_root();
+    let contract = contract.id(&salt, &contract.root(), &state_root);
+
+    let tx = Transaction::create(
+        0,
+        1_000_000,
+        0,
+        0,
+        salt,
+        vec![],
+        vec![],
+        vec![Output::contract_created(contract, state_root)],
+        vec![program],
+    )
+    .into_checked(0, &params, &gas_costs)
+    .expect("failed to generate a checked tx");
+
+    assert!(Transactor::new(&mut storage, params, gas_costs.clone())
+        .transact(tx)
+        .is_success());
+
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset),
+            op::call(0x10, RegId::ZERO, 0x10, RegId::CGAS),
+            op::move_(0x11, RegId::SP),
+            op::cfei(WORD_SIZE as Immediate24),
+            op::sw(0x11, RegId::RET, 0),
+            op::ret(RegId::ONE),
+        ],
+        params.tx_offset()
+    );
+    let mut script_data = contract.to_vec();
+    script_data.extend([0u8; WORD_SIZE * 2]);
+
+    let tx = Transaction::script(
+        0,
+        1_000_000,
+        0,
+        script.into_iter().collect(),
+        script_data,
+        vec![Input::contract(rng.gen(), rng.gen(), rng.gen(), rng.gen(), contract)],
+        vec![Output::contract(0, rng.gen(), rng.gen())],
+        vec![],
+    )
+    .into_checked(0, &params, &gas_costs)
+    .expect("failed to generate a checked tx");
+
+    let tracer = JsonLinesTracer::new(Vec::new());
+    let mut transactor = Transactor::from(
+        Interpreter::with_storage(storage, params, gas_costs)
+            .with_tracer(tracer.clone())
+            .build(),
+    );
+
+    let receipts = transactor
+        .transact(tx)
+        .receipts()
+        .expect("failed to execute script")
+        .to_owned();
+    assert!(matches!(
+        receipts.last(),
+        Some(Receipt::ScriptResult {
+            result: ScriptExecutionResult::Success,
+            ..
+        })
+    ));
+
+    let steps = read_steps(&tracer);
+    let opcodes: Vec<_> = steps.iter().map(|step| step.instruction.opcode()).collect();
+    assert_eq!(
+        opcodes,
+        vec![
+            Opcode::MOVI,
+            Opcode::CALL,
+            Opcode::MOVI,
+            Opcode::SWW,
+            Opcode::SRW,
+            Opcode::RET,
+            Opcode::MOVE,
+            Opcode::CFEI,
+            Opcode::SW,
+            Opcode::RET,
+        ]
+    );
+
+    assert!(steps.iter().all(|step| step.gas_before > step.gas_after));
+    assert!(steps.iter().all(|step| step.panic.is_none()));
+    assert_eq!(
+        steps.iter().map(|step| step.depth).collect::<Vec<_>>(),
+        vec![0, 0, 1, 1, 1, 1, 0, 0, 0, 0]
+    );
+
+    // The call pushes a frame to the stack
+    assert!(!steps[1].memory.is_empty());
+
+    assert_eq!(
+        steps[2].registers,
+        vec![
+            RegisterChange {
+                register: RegId::PC.to_u8() as RegisterId,
+                value: steps[3].pc,
+            },
+            RegisterChange {
+                register: RegId::GGAS.to_u8() as RegisterId,
+                value: steps[2].gas_after,
+            },
+            RegisterChange {
+                register: RegId::CGAS.to_u8() as RegisterId,
+                value: steps[2].gas_after,
+            },
+            RegisterChange {
+                register: 0x10,
+                value: 42,
+            },
+        ]
+    );
+
+    let mut value = Bytes32::zeroed();
+    value[..WORD_SIZE].copy_from_slice(&42u64.to_be_bytes());
+    let key = Bytes32::new(*contract);
+
+    assert_eq!(
+        steps[3].storage,
+        vec![StorageAccess::StateWrite {
+            contract,
+            key,
+            values: vec![value],
+        }]
+    );
+    assert_eq!(
+        steps[4].storage,
+        vec![StorageAccess::StateRead {
+            contract,
+            key,
+            values: vec![Some(value)],
+        }]
+    );
+    // The call credits the forwarded amount to the contract, using the call as asset id
+    let asset_id = AssetId::new(*contract);
+    assert_eq!(
+        steps[1].storage,
+        vec![
+            StorageAccess::BalanceRead {
+                contract,
+                asset_id,
+                value: 0,
+            },
+            StorageAccess::BalanceWrite {
+                contract,
+                asset_id,
+                value: 0,
+            },
+        ]
+    );
+    assert!(steps
+        .iter()
+        .enumerate()
+        .all(|(i, step)| step.storage.is_empty() || matches!(i, 1 | 3 | 4)));
+
+    let sw = &steps[8];
+    let address = sw
+        .memory
+        .first()
+        .expect("SW should report the memory it writes")
+        .address;
+    assert_eq!(
+        sw.memory,
+        vec![MemoryWrite {
+            address,
+            data: 42u64.to_be_bytes().to_vec(),
+        }]
+    );
+}
+
+#[test]
+fn trace_reports_panics() {
+    let tracer = JsonLinesTracer::new(Vec::new());
+    let mut transactor = Transactor::from(Interpreter::with_memory_storage().with_tracer(tracer.clone()).build());
+
+    let tx = Transaction::script(
+        0,
+        1_000_000,
+        0,
+        vec![op::movi(0x10, 1), op::div(0x10, 0x10, RegId::ZERO)]
+            .into_iter()
+            .collect(),
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+    )
+    .into_checked(0, &ConsensusParameters::DEFAULT, &GasCosts::default())
+    .expect("failed to generate a checked tx");
+
+    transactor.transact(tx);
+
+    let steps = read_steps(&tracer);
+
+    assert_eq!(steps.len(), 2);
+    assert_eq!(steps[1].instruction, op::div(0x10, 0x10, RegId::ZERO));
+    assert_eq!(steps[1].panic, Some(PanicReason::ErrorFlag));
+}